@@ -0,0 +1,100 @@
+//! Runs the 2023 days from one binary: `aoc run --all` solves every part in
+//! parallel, `aoc run <day> [<part>]` just those, `aoc list` shows which
+//! inputs are there and `aoc tui` browses them, running parts and showing
+//! their logs. Build with `--features visualize` to watch the days that draw
+//! themselves from the TUI.
+
+use std::process::ExitCode;
+
+use aoc_common::runner::{answer, Part, Registry};
+
+static REGISTRY: Registry = Registry {
+    year: 2023,
+    workspace: concat!(env!("CARGO_MANIFEST_DIR"), "/.."),
+    input: "inputs/input1.txt",
+    parts: &[
+        Part::new("day-01", 1, |input| answer(day_01::part1::process(input))).answer("53651"),
+        Part::new("day-01", 2, |input| answer(day_01::part2::process(input))).answer("53894"),
+        Part::new("day-02", 1, |input| answer(day_02::part1::process(input))).answer("2720"),
+        Part::new("day-02", 2, |input| answer(day_02::part2::process(input))).answer("71535"),
+        Part::new("day-03", 1, |input| answer(day_03::part1::process(input))).answer("550064"),
+        Part::new("day-03", 2, |input| answer(day_03::part2::process(input))).answer("85010461"),
+        Part::new("day-04", 1, |input| answer(day_04::part1::process(input))).answer("24542"),
+        Part::new("day-04", 2, |input| answer(day_04::part2::process(input))).answer("8736438"),
+        Part::new("day-05", 1, |input| answer(day_05::part1::process(input))).answer("1181555926"),
+        Part::new("day-05", 2, |input| answer(day_05::part2::process(input))).answer("37806486"),
+        Part::new("day-06", 1, |input| answer(day_06::part1::process(input))).answer("5133600"),
+        Part::new("day-06", 2, |input| answer(day_06::part2::process(input))).answer("40651271"),
+        Part::new("day-07", 1, |input| answer(day_07::part1::process(input))).answer("246912307"),
+        Part::new("day-07", 2, |input| answer(day_07::part2::process(input))).answer("246894760"),
+        Part::new("day-08", 1, |input| answer(day_08::part1::process(input))).answer("22357"),
+        Part::new("day-08", 2, |input| answer(day_08::part2::process(input)))
+            .answer("10371555451871"),
+        Part::new("day-09", 1, |input| answer(day_09::part1::process(input))).answer("1637452029"),
+        Part::new("day-09", 2, |input| answer(day_09::part2::process(input))).answer("908"),
+        Part::new("day-10", 1, |input| answer(day_10::part1::process(input))).answer("6856"),
+        Part::new("day-10", 2, |input| answer(day_10::part2::process(input))).answer("501"),
+        Part::new("day-11", 1, |input| answer(day_11::part1::process(input))).answer("9769724"),
+        Part::new("day-11", 2, |input| answer(day_11::part2::process(input)))
+            .answer("603020563700"),
+        Part::new("day-12", 1, |input| answer(day_12::part1::process(input))).answer("7191"),
+        Part::new("day-12", 2, |input| answer(day_12::part2::process(input)))
+            .answer("6512849198636"),
+        Part::new("day-12-dfa", 1, |input| {
+            answer(day_12_dfa::part1::process(input))
+        })
+        .answer("7191"),
+        Part::new("day-12-dfa", 2, |input| {
+            answer(day_12_dfa::part2::process(input))
+        })
+        .answer("6512849198636"),
+        Part::new("day-13", 1, |input| answer(day_13::part1::process(input))).answer("37113"),
+        Part::new("day-13", 2, |input| answer(day_13::part2::process(input))).answer("30449"),
+        Part::new("day-14", 1, |input| answer(day_14::part1::process(input))).answer("108935"),
+        Part::new("day-14", 2, |input| answer(day_14::part2::process(input))).answer("100876"),
+        Part::new("day-15", 1, |input| answer(day_15::part1::process(input))).answer("506869"),
+        Part::new("day-15", 2, |input| answer(day_15::part2::process(input))).answer("271384"),
+        Part::new("day-16", 1, |input| answer(day_16::part1::process(input)))
+            .answer("6978")
+            .visualize(),
+        Part::new("day-16", 2, |input| answer(day_16::part2::process(input)))
+            .answer("7315")
+            .visualize(),
+        Part::new("day-17", 1, |input| answer(day_17::part1::process(input))).answer("851"),
+        Part::new("day-17", 2, |input| answer(day_17::part2::process(input))).answer("982"),
+        Part::new("day-18", 1, |input| answer(day_18::part1::process(input))).answer("48400"),
+        Part::new("day-18", 2, |input| answer(day_18::part2::process(input)))
+            .answer("72811019847283"),
+        Part::new("day-19", 1, |input| answer(day_19::part1::process(input))).answer("509597"),
+        Part::new("day-19", 2, |input| answer(day_19::part2::process(input)))
+            .answer("143219569011526"),
+        Part::new("day-20", 1, |input| answer(day_20::part1::process(input))).answer("861743850"),
+        Part::new("day-20", 2, |input| answer(day_20::part2::process(input)))
+            .answer("247023644760071"),
+        Part::new("day-21", 1, |input| {
+            answer(day_21::part1::process(input, 64))
+        })
+        .answer("3646"),
+        Part::new("day-21", 2, |input| {
+            answer(day_21::part2::process(input, 26501365))
+        }),
+        Part::new("day-22", 1, |input| answer(day_22::part1::process(input))).answer("530"),
+        Part::new("day-22", 2, |input| answer(day_22::part2::process(input))).answer("93292"),
+        Part::new("day-23", 1, |input| answer(day_23::part1::process(input))).answer("2070"),
+        Part::new("day-23", 2, |input| answer(day_23::part2::process(input))),
+        Part::new("day-24", 1, |input| {
+            answer(day_24::part1::process(
+                input,
+                200000000000000f64..=400000000000000f64,
+            ))
+        })
+        .answer("16050"),
+        Part::new("day-24", 2, |input| answer(day_24::part2::process(input))),
+        Part::new("day-25", 1, |input| answer(day_25::part1::process(input))).answer("533628"),
+    ],
+    visualize: cfg!(feature = "visualize"),
+};
+
+fn main() -> ExitCode {
+    aoc_common::runner::main(&REGISTRY)
+}
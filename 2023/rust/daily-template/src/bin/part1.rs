@@ -1,18 +1,21 @@
 use {{crate_name}}::part1::process;
 
 use miette::Context;
-use tracing_subscriber::prelude::*;
-use tracing_subscriber::EnvFilter;
+#[cfg(not(feature = "profile"))]
+use tracing_subscriber::{prelude::*, EnvFilter};
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
+    #[cfg(not(feature = "profile"))]
     tracing_subscriber::registry()
         .with(EnvFilter::from_default_env())
         .with(tracing_subscriber::fmt::layer().compact())
         .init();
+    #[cfg(feature = "profile")]
+    let _profile = aoc_common::profile::init(tracing_subscriber::fmt::layer().compact());
 
     let input = include_str!("../../inputs/input1.txt");
-    let output = process(input).context("process part 1")?;
+    let output = aoc_common::memory::report(|| process(input)).context("process part 1")?;
     println!("Output is {output}");
     Ok(())
 }
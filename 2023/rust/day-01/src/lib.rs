@@ -0,0 +1,125 @@
+use std::io::BufRead;
+
+use aoc_common::matcher::Matcher;
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+pub const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const ENGLISH: [(&str, u32); 18] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Reads calibration values, made of the first and last digit on a line,
+/// where a digit is any word of the vocabulary. Spelled digits may share
+/// letters, as in `twone`.
+pub struct Calibration {
+    forward: Matcher<u32>,
+    backward: Matcher<u32>,
+}
+
+impl Calibration {
+    pub fn new<'a>(vocabulary: impl IntoIterator<Item = &'a (&'a str, u32)>) -> Self {
+        let vocabulary = vocabulary.into_iter().copied().collect::<Vec<_>>();
+        Calibration {
+            forward: Matcher::new(vocabulary.iter().copied()),
+            backward: Matcher::reversed(vocabulary),
+        }
+    }
+
+    pub fn value(&self, line: &[u8]) -> Option<u32> {
+        let first = self.forward.first(line)?;
+        let last = self.backward.first(line)?;
+        Some(first.value * 10 + last.value)
+    }
+
+    /// Sums the values of a document one line at a time, so it never holds
+    /// more than a line in memory. Blank lines are skipped and `\r\n` line
+    /// endings are fine.
+    pub fn sum(&self, mut document: impl BufRead) -> Result<u64, AocError> {
+        let mut line = Vec::new();
+        let mut total = 0;
+
+        for number in 1.. {
+            line.clear();
+            if document.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let trimmed = line.strip_suffix(b"\n").unwrap_or(&line);
+            let trimmed = trimmed.strip_suffix(b"\r").unwrap_or(trimmed);
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            total += self.value(trimmed).ok_or(AocError::NoDigits(number))? as u64;
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_letters() {
+        let calibration = Calibration::new(&ENGLISH);
+        assert_eq!(Some(21), calibration.value(b"xtwone"));
+        assert_eq!(Some(83), calibration.value(b"eighthree"));
+        assert_eq!(Some(77), calibration.value(b"seven"));
+        assert_eq!(None, calibration.value(b"treb_uchet"));
+    }
+
+    #[test]
+    fn other_vocabularies() {
+        let spanish = [("uno", 1), ("dos", 2), ("tres", 3), ("seis", 6)];
+        let calibration = Calibration::new(&spanish);
+        assert_eq!(Some(13), calibration.value(b"unodostres"));
+        assert_eq!(Some(62), calibration.value(b"seisdos"));
+    }
+
+    #[test]
+    fn crlf_and_blank_lines() -> miette::Result<()> {
+        let calibration = Calibration::new(&ENGLISH);
+        assert_eq!(
+            29 + 83,
+            calibration.sum("two1nine\r\n\r\neightwothree\r\n".as_bytes())?
+        );
+        assert!(matches!(
+            calibration.sum("1abc2\nabc\n".as_bytes()),
+            Err(AocError::NoDigits(2))
+        ));
+        Ok(())
+    }
+}
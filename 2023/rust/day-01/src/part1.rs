@@ -0,0 +1,37 @@
+use std::io::BufRead;
+
+use crate::{custom_error::AocError, Calibration, DIGITS};
+
+/// Sums the calibration values of a document read line by line, so it never
+/// has to be held in memory whole.
+pub fn process_document(document: impl BufRead) -> miette::Result<u64, AocError> {
+    Calibration::new(&DIGITS).sum(document)
+}
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    process_document(input.as_bytes())
+}
+
+#[cfg(test)]
+mod day_01_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let output = process(
+            "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+        )?;
+        assert_eq!(output, 142);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let output = process(include_str!("../inputs/input1.txt"))?;
+        assert_eq!(output, 53651);
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use std::io::BufRead;
+
+use crate::{custom_error::AocError, Calibration, ENGLISH};
+
+/// Sums the calibration values of a document read line by line, so it never
+/// has to be held in memory whole.
+pub fn process_document(document: impl BufRead) -> miette::Result<u64, AocError> {
+    Calibration::new(&ENGLISH).sum(document)
+}
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    process_document(input.as_bytes())
+}
+
+#[cfg(test)]
+mod day_01_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let output = process(
+            "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+        )?;
+        assert_eq!(output, 281);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let output = process(include_str!("../inputs/input1.txt"))?;
+        assert_eq!(output, 53894);
+        Ok(())
+    }
+}
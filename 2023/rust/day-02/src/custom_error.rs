@@ -0,0 +1,17 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("the power of the cubes doesn't fit in a u64")]
+    #[diagnostic(code(aoc::power_overflow))]
+    PowerOverflow,
+}
@@ -1,110 +1,100 @@
-#[derive(Debug, PartialEq)]
-enum CubeColor {
-    Red,
-    Green,
-    Blue,
-}
+use std::collections::BTreeMap;
 
-#[derive(Debug)]
-struct CubeCount {
-    color: CubeColor,
-    count: u32,
-}
+use aoc_common::parsers::number;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, multispace0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair},
+    IResult, Parser,
+};
 
-impl CubeCount {
-    fn new(color: CubeColor, count: u32) -> CubeCount {
-        CubeCount {
-            color: color,
-            count: count,
-        }
-    }
-}
+use crate::custom_error::AocError;
 
-#[derive(Debug)]
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+/// The colours of the cubes in the puzzle's bag.
+pub const PALETTE: [&str; 3] = ["red", "green", "blue"];
+
+/// A multiset of cubes, counted by colour name. Colours that aren't in the
+/// set count as zero.
+///
+/// Counts are kept in `u64` while draws only parse `u32` ones, so a colour
+/// named over and over in a draw can't overflow.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct CubeSet {
-    cube_counts: Vec<CubeCount>,
+    counts: BTreeMap<String, u64>,
 }
 
 impl CubeSet {
-    pub fn new_from_rgb(red: u32, green: u32, blue: u32) -> CubeSet {
-        CubeSet {
-            cube_counts: vec![
-                CubeCount::new(CubeColor::Red, red),
-                CubeCount::new(CubeColor::Green, green),
-                CubeCount::new(CubeColor::Blue, blue),
-            ],
-        }
+    pub fn new() -> CubeSet {
+        CubeSet::default()
     }
 
-    pub fn default() -> CubeSet {
-        CubeSet {
-            cube_counts: vec![
-                CubeCount::new(CubeColor::Red, 0),
-                CubeCount::new(CubeColor::Green, 0),
-                CubeCount::new(CubeColor::Blue, 0),
-            ],
-        }
+    pub fn count(&self, color: &str) -> u64 {
+        self.counts.get(color).copied().unwrap_or(0)
     }
 
-    fn add_cubes(&mut self, color: CubeColor, count: u32) {
-        match color {
-            CubeColor::Red => {
-                self.cube_counts
-                    .iter_mut()
-                    .find(|cube_count| cube_count.color == CubeColor::Red)
-                    .unwrap()
-                    .count += count
-            }
-            CubeColor::Green => {
-                self.cube_counts
-                    .iter_mut()
-                    .find(|cube_count| cube_count.color == CubeColor::Green)
-                    .unwrap()
-                    .count += count
-            }
-            CubeColor::Blue => {
-                self.cube_counts
-                    .iter_mut()
-                    .find(|cube_count| cube_count.color == CubeColor::Blue)
-                    .unwrap()
-                    .count += count
-            }
+    pub fn add_cubes(&mut self, color: &str, count: u64) {
+        if count > 0 {
+            *self.counts.entry(color.to_string()).or_insert(0) += count;
         }
     }
 
-    pub fn can_be_created_from(&self, other: &CubeSet) -> bool {
-        self.cube_counts
+    pub fn colors(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.counts
             .iter()
-            .map(|cube_count| {
-                let other_count = other
-                    .cube_counts
-                    .iter()
-                    .find(|other_cube_count| other_cube_count.color == cube_count.color)
-                    .unwrap();
-
-                (cube_count.count, other_count.count)
-            })
-            .all(|(self_count, other_count)| self_count <= other_count)
-    }
-
-    pub fn update_with_max(&mut self, other: &CubeSet) -> () {
-        for cube_count in self.cube_counts.iter_mut() {
-            let other_count = other
-                .cube_counts
-                .iter()
-                .find(|other_cube_count| other_cube_count.color == cube_count.color)
-                .unwrap();
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Whether every cube in this set could be drawn from `other`.
+    pub fn can_be_created_from(&self, other: &CubeSet) -> bool {
+        self.colors()
+            .all(|(color, count)| count <= other.count(color))
+    }
 
-            if other_count.count > cube_count.count {
-                cube_count.count = other_count.count;
-            }
+    pub fn update_with_max(&mut self, other: &CubeSet) {
+        for (color, count) in other.colors() {
+            let current = self.counts.entry(color.to_string()).or_insert(0);
+            *current = (*current).max(count);
         }
     }
 
-    pub fn power(&self) -> u32 {
-        self.cube_counts
-            .iter()
-            .fold(1, |acc, cube_count| acc * cube_count.count)
+    /// Product of the counts of every colour in the palette, so a colour
+    /// that's missing from the set makes it 0.
+    pub fn power(&self, palette: &[&str]) -> Result<u64, AocError> {
+        palette.iter().try_fold(1_u64, |power, color| {
+            power
+                .checked_mul(self.count(color))
+                .ok_or(AocError::PowerOverflow)
+        })
+    }
+
+    /// The most cubes of each colour either set has.
+    pub fn union(&self, other: &CubeSet) -> CubeSet {
+        let mut union = self.clone();
+        union.update_with_max(other);
+        union
+    }
+
+    /// The cubes left after taking out those of `other`.
+    pub fn difference(&self, other: &CubeSet) -> CubeSet {
+        self.colors()
+            .map(|(color, count)| (color, count.saturating_sub(other.count(color))))
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u64)> for CubeSet {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u64)>>(iter: I) -> Self {
+        let mut cube_set = CubeSet::new();
+        for (color, count) in iter {
+            cube_set.add_cubes(color, count);
+        }
+        cube_set
     }
 }
 
@@ -115,35 +105,159 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn new_from_aoc_input(input: &str) -> Game {
-        let v: Vec<&str> = input.split(": ").collect();
-
-        Game {
-            id: v[0].split(" ").collect::<Vec<&str>>()[1]
-                .parse::<u32>()
-                .unwrap(),
-            cube_sets: v[1]
-                .split("; ")
-                .map(|cube_set_str| {
-                    let mut cube_set = CubeSet::default();
-
-                    for cube_count_str in cube_set_str.split(", ") {
-                        let cube_count: Vec<&str> = cube_count_str.split(" ").collect();
-
-                        let count = cube_count[0].parse::<u32>().unwrap();
-                        let color = match cube_count[1] {
-                            "red" => CubeColor::Red,
-                            "green" => CubeColor::Green,
-                            "blue" => CubeColor::Blue,
-                            _ => panic!("Invalid color"),
-                        };
-
-                        cube_set.add_cubes(color, count);
-                    }
-
-                    cube_set
-                })
+    /// The fewest cubes of each colour the bag could have held.
+    pub fn minimum_set(&self) -> CubeSet {
+        self.cube_sets
+            .iter()
+            .fold(CubeSet::new(), |minimum, cube_set| minimum.union(cube_set))
+    }
+
+    pub fn is_possible_with(&self, bag: &CubeSet) -> bool {
+        self.cube_sets
+            .iter()
+            .all(|cube_set| cube_set.can_be_created_from(bag))
+    }
+}
+
+/// Answers which games are possible with a bag, for as many bags as needed,
+/// working out each game's minimum set only once.
+#[derive(Debug)]
+pub struct GameIndex {
+    games: Vec<(u32, CubeSet)>,
+}
+
+impl GameIndex {
+    pub fn new(games: &[Game]) -> GameIndex {
+        GameIndex {
+            games: games
+                .iter()
+                .map(|game| (game.id, game.minimum_set()))
                 .collect(),
         }
     }
+
+    pub fn possible_with<'a>(&'a self, bag: &'a CubeSet) -> impl Iterator<Item = u32> + 'a {
+        self.games
+            .iter()
+            .filter(|(_, minimum)| minimum.can_be_created_from(bag))
+            .map(|(id, _)| *id)
+    }
+
+    /// The ids of the games possible with each bag, in the order of `bags`.
+    pub fn possible_with_each(&self, bags: &[CubeSet]) -> Vec<Vec<u32>> {
+        bags.iter()
+            .map(|bag| self.possible_with(bag).collect())
+            .collect()
+    }
+}
+
+fn cube_set(input: &str) -> IResult<&str, CubeSet> {
+    separated_list1(tag(", "), separated_pair(number::<u32>, space1, alpha1))
+        .map(|counts| {
+            counts
+                .into_iter()
+                .map(|(count, color)| (color, count.into()))
+                .collect()
+        })
+        .parse(input)
+}
+
+fn game(input: &str) -> IResult<&str, Game> {
+    separated_pair(
+        preceded(tag("Game "), number),
+        tag(": "),
+        separated_list1(tag("; "), cube_set),
+    )
+    .map(|(id, cube_sets)| Game { id, cube_sets })
+    .parse(input)
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, AocError> {
+    all_consuming(delimited(
+        multispace0,
+        separated_list1(line_ending, game),
+        multispace0,
+    ))(input)
+    .map(|(_, games)| games)
+    .map_err(|e| AocError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiset_operations() -> miette::Result<()> {
+        let bag = CubeSet::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+        let draw = CubeSet::from_iter([("red", 3), ("purple", 0), ("blue", 20)]);
+
+        assert_eq!(0, draw.count("purple"));
+        assert!(!draw.can_be_created_from(&bag));
+        assert_eq!(
+            CubeSet::from_iter([("red", 12), ("green", 13), ("blue", 20)]),
+            bag.union(&draw)
+        );
+        assert_eq!(
+            CubeSet::from_iter([("red", 9), ("green", 13)]),
+            bag.difference(&draw)
+        );
+        assert_eq!(
+            60,
+            draw.union(&CubeSet::from_iter([("teal", 1)]))
+                .power(&["red", "blue", "teal"])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn power_with_missing_colors() -> miette::Result<()> {
+        let games = parse_games("Game 1: 3 blue, 4 red; 1 red, 6 blue")?;
+        let minimum = games[0].minimum_set();
+
+        assert_eq!(0, minimum.power(&PALETTE)?);
+        assert_eq!(24, minimum.power(&["red", "blue"])?);
+        assert_eq!(1, minimum.power(&[])?);
+
+        let games = parse_games("Game 1: 4000000000 red, 4000000000 red, 1 green, 1 blue")?;
+        assert_eq!(8_000_000_000, games[0].minimum_set().power(&PALETTE)?);
+        assert!(matches!(
+            CubeSet::from_iter([("red", u64::MAX), ("green", 2), ("blue", 1)]).power(&PALETTE),
+            Err(AocError::PowerOverflow)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn many_bags() -> miette::Result<()> {
+        let games = parse_games(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 2 teal, 1 red
+",
+        )?;
+        let index = GameIndex::new(&games);
+
+        assert_eq!(
+            vec![vec![1, 2], vec![2], vec![3], vec![]],
+            index.possible_with_each(&[
+                CubeSet::from_iter([("red", 4), ("green", 3), ("blue", 6)]),
+                CubeSet::from_iter([("red", 1), ("green", 3), ("blue", 4)]),
+                CubeSet::from_iter([("red", 1), ("teal", 2)]),
+                CubeSet::new(),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_games() {
+        for input in [
+            "Game 1 3 blue",
+            "Game x: 3 blue",
+            "Game 1: 3",
+            "Game 1: blue 3",
+        ] {
+            assert!(matches!(parse_games(input), Err(AocError::ParseError(_))));
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let games = parse_games(input)?;
+    let bag = CubeSet::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+
+    Ok(GameIndex::new(&games)
+        .possible_with(&bag)
+        .map(u64::from)
+        .sum())
+}
+
+#[cfg(test)]
+mod day_02_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        assert_eq!(process(input)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        assert_eq!(process(input)?, 2720);
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let games = parse_games(input)?;
+
+    games.iter().try_fold(0_u64, |sum, game| {
+        sum.checked_add(game.minimum_set().power(&PALETTE)?)
+            .ok_or(AocError::PowerOverflow)
+    })
+}
+
+#[cfg(test)]
+mod day_02_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        assert_eq!(process(input)?, 2286);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        assert_eq!(process(input)?, 71535);
+        Ok(())
+    }
+}
@@ -1,46 +1,11 @@
-use day_03::*;
+use day_03::{part1::process, EngineSchematic};
+use miette::Context;
 
-fn main() {
+fn main() -> miette::Result<()> {
     let input = include_str!("../../inputs/input1.txt");
-    let output = process(input);
-    println!("Output is {output}");
-}
-
-fn process(input: &str) -> u32 {
-    let engine_schematic = EngineSchematic::new(input);
-    println!("Engine schematic is \n{}", engine_schematic);
-
-    engine_schematic
-        .part_numbers()
-        .iter()
-        .map(|num_in_engine| num_in_engine.value)
-        .sum()
-}
-
-#[cfg(test)]
-mod day_03_part1 {
-    use super::*;
+    println!("Engine schematic is \n{}", EngineSchematic::new(input)?);
 
-    #[test]
-    fn example() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        let output = process(input);
-        assert_eq!(output, 4361);
-    }
-
-    #[test]
-    fn input1() {
-        let input = include_str!("../../inputs/input1.txt");
-        let output = process(input);
-        assert_eq!(output, 550064);
-    }
+    let output = aoc_common::memory::report(|| process(input)).context("process part 1")?;
+    println!("Output is {output}");
+    Ok(())
 }
@@ -0,0 +1,33 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("the engine schematic is empty")]
+    #[diagnostic(code(aoc::empty_schematic))]
+    EmptySchematic,
+
+    #[error("row {row} is {len} long, but the first row is {expected}")]
+    #[diagnostic(code(aoc::ragged_row))]
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+
+    #[error("row {0} ends in \\r, the schematic should use \\n line endings")]
+    #[diagnostic(code(aoc::crlf_line_ending))]
+    CrlfLineEnding(usize),
+
+    #[error("the number at row {row}, column {col} is invalid: {reason}")]
+    #[diagnostic(code(aoc::bad_number))]
+    BadNumber {
+        row: usize,
+        col: usize,
+        reason: String,
+    },
+}
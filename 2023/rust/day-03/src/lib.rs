@@ -1,4 +1,10 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
 
 #[derive(Debug, Clone)]
 pub struct EngineSchematic {
@@ -7,97 +13,157 @@ pub struct EngineSchematic {
     cols: usize,
 }
 
+/// Anything that isn't a digit or a `.`.
+pub fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
 impl EngineSchematic {
-    pub fn new(input_str: &str) -> EngineSchematic {
+    /// Fails on rows of different lengths and on `\r\n` line endings. A single
+    /// trailing newline is fine.
+    pub fn new(input_str: &str) -> Result<EngineSchematic, AocError> {
+        let input_str = input_str.strip_suffix('\n').unwrap_or(input_str);
+        if input_str.is_empty() {
+            return Err(AocError::EmptySchematic);
+        }
         let lines: Vec<&str> = input_str.split('\n').collect();
 
-        let mut data: Vec<char> = Vec::with_capacity(lines.len() * lines[0].len());
         let rows = lines.len();
-        let cols = lines[0].len();
+        let cols = lines[0].chars().count();
+        let mut data: Vec<char> = Vec::with_capacity(rows * cols);
 
-        lines.iter().for_each(|line| {
-            data.extend_from_slice(line.chars().collect::<Vec<char>>().as_slice());
-        });
+        for (row, line) in lines.iter().enumerate() {
+            if line.ends_with('\r') {
+                return Err(AocError::CrlfLineEnding(row + 1));
+            }
+            let len = line.chars().count();
+            if len != cols {
+                return Err(AocError::RaggedRow {
+                    row: row + 1,
+                    len,
+                    expected: cols,
+                });
+            }
+            data.extend(line.chars());
+        }
 
-        EngineSchematic { data, rows, cols }
+        Ok(EngineSchematic { data, rows, cols })
     }
 
-    fn num_at(&self, start: usize, len: usize) -> u32 {
+    fn num_at(&self, start: usize, len: usize) -> Result<u32, AocError> {
         self.data
             .iter()
             .skip(start)
             .take(len)
             .collect::<String>()
             .parse::<u32>()
-            .unwrap()
+            .map_err(|e| AocError::BadNumber {
+                row: start / self.cols + 1,
+                col: start % self.cols + 1,
+                reason: e.to_string(),
+            })
+    }
+
+    pub fn numbers(&self) -> Result<Vec<NumInEngine>, AocError> {
+        self.clone().into_iter().collect()
     }
 
-    pub fn part_numbers(&self) -> Vec<NumInEngine> {
-        let engine_schematic = self.clone();
+    /// Every symbol in the schematic, row by row.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| is_symbol(**c))
+            .map(|(idx, &value)| Symbol {
+                value,
+                row: idx / self.cols,
+                col: idx % self.cols,
+            })
+    }
 
-        engine_schematic
+    /// The cells around a number, clipped to the schematic.
+    fn border(&self, num_in_engine: &NumInEngine) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let rows = num_in_engine.row.saturating_sub(1)..(num_in_engine.row + 2).min(self.rows);
+        let cols = num_in_engine.col.saturating_sub(1)
+            ..(num_in_engine.col + num_in_engine.len + 1).min(self.cols);
+        let (row, num_cols) = (
+            num_in_engine.row,
+            num_in_engine.col..num_in_engine.col + num_in_engine.len,
+        );
+
+        rows.flat_map(move |i| cols.clone().map(move |j| (i, j)))
+            .filter(move |(i, j)| !(*i == row && num_cols.contains(j)))
+    }
+
+    /// The symbols touching a number, diagonals included.
+    pub fn adjacent_symbols<'a>(
+        &'a self,
+        num_in_engine: &'a NumInEngine,
+    ) -> impl Iterator<Item = Symbol> + 'a {
+        self.border(num_in_engine).filter_map(|(row, col)| {
+            let value = self.data[row * self.cols + col];
+            is_symbol(value).then_some(Symbol { value, row, col })
+        })
+    }
+
+    /// The numbers touching at least one symbol of the class.
+    pub fn numbers_adjacent_to(
+        &self,
+        class: impl Fn(char) -> bool,
+    ) -> Result<Vec<NumInEngine>, AocError> {
+        Ok(self
+            .numbers()?
             .into_iter()
-            .filter(|num_in_engine| self.is_part_number(num_in_engine))
-            .collect()
+            .filter(|num_in_engine| {
+                self.adjacent_symbols(num_in_engine)
+                    .any(|symbol| class(symbol.value))
+            })
+            .collect())
     }
 
-    pub fn gears(&self) -> HashMap<(i32, i32), Vec<NumInEngine>> {
-        let part_numbers = self.part_numbers();
-
-        let mut gears: HashMap<(i32, i32), Vec<NumInEngine>> = HashMap::new();
-
-        for num_in_engine in part_numbers {
-            let col_idx =
-                num_in_engine.col as i32..num_in_engine.col as i32 + num_in_engine.len as i32;
-
-            for i in (num_in_engine.row as i32 - 1)..=(num_in_engine.row as i32 + 1) {
-                for j in (num_in_engine.col as i32 - 1)
-                    ..(num_in_engine.col as i32 + num_in_engine.len as i32 + 1)
-                {
-                    if !(i == num_in_engine.row as i32 && col_idx.contains(&j))
-                        && (0 <= i && i < self.rows as i32)
-                        && (0 <= j && j < self.cols as i32)
-                    {
-                        let idx = i * self.cols as i32 + j;
-                        let current_char = self.data[idx as usize];
-
-                        if current_char == '*' {
-                            gears
-                                .entry((i, j))
-                                .or_insert(Vec::new())
-                                .push(num_in_engine.clone());
-                        }
-                    }
+    /// Every symbol of the class with the numbers touching it, row by row.
+    /// Symbols without any neighbouring number are included.
+    pub fn adjacency(
+        &self,
+        class: impl Fn(char) -> bool,
+    ) -> Result<Vec<(Symbol, Vec<NumInEngine>)>, AocError> {
+        let mut adjacency: BTreeMap<Symbol, Vec<NumInEngine>> = self
+            .symbols()
+            .filter(|symbol| class(symbol.value))
+            .map(|symbol| (symbol, Vec::new()))
+            .collect();
+
+        for num_in_engine in self.numbers()? {
+            for symbol in self.adjacent_symbols(&num_in_engine) {
+                if let Some(numbers) = adjacency.get_mut(&symbol) {
+                    numbers.push(num_in_engine.clone());
                 }
             }
         }
 
-        gears.retain(|_, v| v.len() == 2);
-        gears
+        Ok(adjacency.into_iter().collect())
     }
 
-    fn is_part_number(&self, num_in_engine: &NumInEngine) -> bool {
-        let mut flag = false;
-        let col_idx = num_in_engine.col as i32..num_in_engine.col as i32 + num_in_engine.len as i32;
+    /// Symbols of the class touching exactly `count` numbers.
+    pub fn symbols_with_exactly(
+        &self,
+        class: impl Fn(char) -> bool,
+        count: usize,
+    ) -> Result<Vec<(Symbol, Vec<NumInEngine>)>, AocError> {
+        Ok(self
+            .adjacency(class)?
+            .into_iter()
+            .filter(|(_, numbers)| numbers.len() == count)
+            .collect())
+    }
 
-        for i in (num_in_engine.row as i32 - 1)..=(num_in_engine.row as i32 + 1) {
-            for j in (num_in_engine.col as i32 - 1)
-                ..(num_in_engine.col as i32 + num_in_engine.len as i32 + 1)
-            {
-                if !(i == num_in_engine.row as i32 && col_idx.contains(&j))
-                    && (0 <= i && i < self.rows as i32)
-                    && (0 <= j && j < self.cols as i32)
-                {
-                    let idx = i * self.cols as i32 + j;
-                    let current_char = self.data[idx as usize];
+    pub fn part_numbers(&self) -> Result<Vec<NumInEngine>, AocError> {
+        self.numbers_adjacent_to(is_symbol)
+    }
 
-                    if !current_char.is_digit(10) && current_char != '.' {
-                        flag = true;
-                    }
-                }
-            }
-        }
-        flag
+    /// `*` symbols touching exactly two numbers.
+    pub fn gears(&self) -> Result<Vec<(Symbol, Vec<NumInEngine>)>, AocError> {
+        self.symbols_with_exactly(|c| c == '*', 2)
     }
 }
 
@@ -114,7 +180,15 @@ impl std::fmt::Display for EngineSchematic {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A symbol and its position, ordered row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Symbol {
+    pub row: usize,
+    pub col: usize,
+    pub value: char,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NumInEngine {
     pub value: u32,
     pub row: usize,
@@ -123,7 +197,7 @@ pub struct NumInEngine {
 }
 
 impl IntoIterator for EngineSchematic {
-    type Item = NumInEngine;
+    type Item = Result<NumInEngine, AocError>;
     type IntoIter = NumInEngineIntoIterator;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -144,14 +218,14 @@ pub struct NumInEngineIntoIterator {
 }
 
 impl Iterator for NumInEngineIntoIterator {
-    type Item = NumInEngine;
+    type Item = Result<NumInEngine, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         for i in self.row..self.engine_schematic.rows {
             for j in self.col..self.engine_schematic.cols {
                 let idx = i * self.engine_schematic.cols + j;
 
-                if self.engine_schematic.data[idx].is_digit(10) {
+                if self.engine_schematic.data[idx].is_ascii_digit() {
                     if !self.found_digits {
                         self.found_digits = true;
                         self.row = i;
@@ -161,12 +235,15 @@ impl Iterator for NumInEngineIntoIterator {
                         let start = self.row * self.engine_schematic.cols + self.col;
                         let len = (i - self.row) * self.engine_schematic.cols + (j - self.col) + 1;
 
-                        let num_in_engine = NumInEngine {
-                            value: self.engine_schematic.num_at(start, len),
-                            row: self.row,
-                            col: self.col,
-                            len,
-                        };
+                        let num_in_engine =
+                            self.engine_schematic
+                                .num_at(start, len)
+                                .map(|value| NumInEngine {
+                                    value,
+                                    row: self.row,
+                                    col: self.col,
+                                    len,
+                                });
 
                         self.found_digits = false;
                         self.row = i + 1;
@@ -179,12 +256,15 @@ impl Iterator for NumInEngineIntoIterator {
                         let start = self.row * self.engine_schematic.cols + self.col;
                         let len = (i - self.row) * self.engine_schematic.cols + (j - self.col);
 
-                        let num_in_engine = NumInEngine {
-                            value: self.engine_schematic.num_at(start, len),
-                            row: self.row,
-                            col: self.col,
-                            len,
-                        };
+                        let num_in_engine =
+                            self.engine_schematic
+                                .num_at(start, len)
+                                .map(|value| NumInEngine {
+                                    value,
+                                    row: self.row,
+                                    col: self.col,
+                                    len,
+                                });
 
                         self.found_digits = false;
                         self.row = i;
@@ -200,3 +280,102 @@ impl Iterator for NumInEngineIntoIterator {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    fn values(numbers: &[NumInEngine]) -> Vec<u32> {
+        numbers
+            .iter()
+            .map(|num_in_engine| num_in_engine.value)
+            .collect()
+    }
+
+    #[test]
+    fn symbol_queries() -> miette::Result<()> {
+        let engine_schematic = EngineSchematic::new(EXAMPLE)?;
+
+        assert_eq!(
+            vec![592],
+            values(&engine_schematic.numbers_adjacent_to(|c| c == '+')?)
+        );
+        assert_eq!(
+            vec![633],
+            values(&engine_schematic.numbers_adjacent_to(|c| c == '#')?)
+        );
+
+        let stars = engine_schematic.adjacency(|c| c == '*')?;
+        assert_eq!(
+            vec![vec![467, 35], vec![617], vec![755, 598]],
+            stars
+                .iter()
+                .map(|(_, numbers)| values(numbers))
+                .collect::<Vec<_>>()
+        );
+        let lonely = engine_schematic.symbols_with_exactly(|c| c == '*', 1)?;
+        assert_eq!(
+            vec![Symbol {
+                row: 4,
+                col: 3,
+                value: '*'
+            }],
+            lonely
+                .into_iter()
+                .map(|(symbol, _)| symbol)
+                .collect::<Vec<_>>()
+        );
+
+        let number = &engine_schematic.numbers()?[0];
+        assert_eq!(
+            vec!['*'],
+            engine_schematic
+                .adjacent_symbols(number)
+                .map(|symbol| symbol.value)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_schematics() {
+        assert!(matches!(
+            EngineSchematic::new("467..\n...*\n..35."),
+            Err(AocError::RaggedRow {
+                row: 2,
+                len: 4,
+                expected: 5
+            })
+        ));
+        assert!(matches!(
+            EngineSchematic::new("467..\r\n...*.\r\n"),
+            Err(AocError::CrlfLineEnding(1))
+        ));
+        assert!(matches!(
+            EngineSchematic::new(""),
+            Err(AocError::EmptySchematic)
+        ));
+        assert!(EngineSchematic::new("467..\n...*.\n").is_ok());
+    }
+
+    #[test]
+    fn numbers_past_u32() -> miette::Result<()> {
+        let engine_schematic = EngineSchematic::new("*...........\n.99999999999\n")?;
+        assert!(matches!(
+            engine_schematic.part_numbers(),
+            Err(AocError::BadNumber { row: 2, col: 2, .. })
+        ));
+        Ok(())
+    }
+}
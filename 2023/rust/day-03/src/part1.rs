@@ -0,0 +1,45 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let engine_schematic = EngineSchematic::new(input)?;
+
+    Ok(engine_schematic
+        .part_numbers()?
+        .iter()
+        .map(|num_in_engine| u64::from(num_in_engine.value))
+        .sum())
+}
+
+#[cfg(test)]
+mod day_03_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        let output = process(input)?;
+        assert_eq!(output, 4361);
+
+        let engine_schematic = EngineSchematic::new(input)?;
+        insta::assert_snapshot!("example_schematic", engine_schematic.to_string());
+        insta::assert_debug_snapshot!("example_part_numbers", engine_schematic.part_numbers()?);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 550064);
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u128, AocError> {
+    let engine_schematic = EngineSchematic::new(input)?;
+
+    Ok(engine_schematic
+        .gears()?
+        .iter()
+        .map(|(_, v)| v.iter().fold(1, |acc, x| acc * u128::from(x.value)))
+        .sum())
+}
+
+#[cfg(test)]
+mod day_03_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        let output = process(input)?;
+        assert_eq!(output, 467835);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 85010461);
+        Ok(())
+    }
+}
@@ -1,47 +1,9 @@
-use day_04::*;
+use day_04::part1::process;
+use miette::Context;
 
-fn main() {
+fn main() -> miette::Result<()> {
     let input = include_str!("../../inputs/input1.txt");
-    let output = process(input);
+    let output = aoc_common::memory::report(|| process(input)).context("process part 1")?;
     println!("Output is {output}");
-}
-
-fn process(input: &str) -> u32 {
-    let cards = parse_cards(input);
-
-    cards
-        .iter()
-        .map(|card| {
-            let intersection = card.winning_numbers.intersection(&card.my_numbers);
-
-            match intersection.count() {
-                0 => 0,
-                n => 2_u32.pow(n as u32 - 1),
-            }
-        })
-        .sum()
-}
-
-#[cfg(test)]
-mod day_04_part1 {
-    use super::*;
-
-    #[test]
-    fn example() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let output = process(input);
-        assert_eq!(output, 13);
-    }
-
-    #[test]
-    fn input1() {
-        let input = include_str!("../../inputs/input1.txt");
-        let output = process(input);
-        assert_eq!(output, 24542);
-    }
+    Ok(())
 }
@@ -0,0 +1,21 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("card {0} has more copies than fit in a u64")]
+    #[diagnostic(code(aoc::too_many_copies))]
+    TooManyCopies(u32),
+
+    #[error("the points up to card {0} don't fit in a u64")]
+    #[diagnostic(code(aoc::too_many_points))]
+    TooManyPoints(u32),
+}
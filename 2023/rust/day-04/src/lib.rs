@@ -1,48 +1,253 @@
 use std::collections::HashSet;
 
-#[derive(Debug)]
-#[allow(dead_code)]
+use aoc_common::parsers::{number, numbers};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{line_ending, multispace0, space0, space1},
+    combinator::{all_consuming, map},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, tuple},
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+/// Set of the numbers on a card. Puzzle cards only have small numbers, which
+/// go in a bitset sized to the largest one; sets with a number past
+/// [`NumberSet::DENSE_LIMIT`] hash them instead.
+#[derive(Debug, Clone)]
+pub enum NumberSet {
+    Dense(Vec<u64>),
+    Sparse(HashSet<u32>),
+}
+
+impl NumberSet {
+    pub const DENSE_LIMIT: u32 = 4096;
+
+    pub fn from_numbers(numbers: impl IntoIterator<Item = u32>) -> NumberSet {
+        let numbers = numbers.into_iter().collect::<Vec<_>>();
+        match numbers.iter().max() {
+            Some(&max) if max >= NumberSet::DENSE_LIMIT => {
+                NumberSet::Sparse(numbers.into_iter().collect())
+            }
+            max => {
+                let mut bits = vec![0_u64; max.map_or(0, |&max| max as usize / 64 + 1)];
+                for number in numbers {
+                    bits[number as usize / 64] |= 1 << (number % 64);
+                }
+                NumberSet::Dense(bits)
+            }
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            NumberSet::Dense(bits) => bits
+                .get(number as usize / 64)
+                .is_some_and(|word| word & (1 << (number % 64)) != 0),
+            NumberSet::Sparse(numbers) => numbers.contains(&number),
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match self {
+            NumberSet::Dense(bits) => Box::new(bits.iter().enumerate().flat_map(|(i, &word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| i as u32 * 64 + bit)
+            })),
+            NumberSet::Sparse(numbers) => Box::new(numbers.iter().copied()),
+        }
+    }
+
+    pub fn intersection(&self, other: &NumberSet) -> NumberSet {
+        match (self, other) {
+            (NumberSet::Dense(bits), NumberSet::Dense(other)) => {
+                NumberSet::Dense(bits.iter().zip(other).map(|(a, b)| a & b).collect())
+            }
+            _ => NumberSet::from_numbers(self.iter().filter(|&number| other.contains(number))),
+        }
+    }
+
+    pub fn len(&self) -> u32 {
+        match self {
+            NumberSet::Dense(bits) => bits.iter().map(|bits| bits.count_ones()).sum(),
+            NumberSet::Sparse(numbers) => numbers.len() as u32,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Card {
     pub id: u32,
-    pub winning_numbers: HashSet<u32>,
-    pub my_numbers: HashSet<u32>,
-}
-
-pub fn parse_cards(input: &str) -> Vec<Card> {
-    input.lines().map(|line| parse_card(line)).collect()
-}
-
-pub fn parse_card(input: &str) -> Card {
-    let mut parts = input.split(": ");
-    let id = parts
-        .next()
-        .unwrap()
-        .trim()
-        .split(" ")
-        .filter(|id_str| !id_str.is_empty())
-        .nth(1)
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-
-    let numbers = parts
-        .next()
-        .unwrap()
-        .trim()
-        .split(" | ")
-        .collect::<Vec<&str>>();
-
-    Card {
+    pub winning_numbers: NumberSet,
+    pub my_numbers: NumberSet,
+}
+
+impl Card {
+    /// How many of my numbers are winning numbers.
+    pub fn matches(&self) -> u32 {
+        self.winning_numbers.intersection(&self.my_numbers).len()
+    }
+}
+
+fn number_set(input: &str) -> IResult<&str, NumberSet> {
+    map(numbers, NumberSet::from_numbers)(input)
+}
+
+fn card(input: &str) -> IResult<&str, Card> {
+    tuple((
+        delimited(tuple((tag("Card"), space1)), number, tag(":")),
+        separated_pair(number_set, delimited(space1, tag("|"), space0), number_set),
+    ))
+    .map(|(id, (winning_numbers, my_numbers))| Card {
         id,
-        winning_numbers: numbers[0]
-            .split(" ")
-            .filter(|number_str| !number_str.is_empty())
-            .map(|number_str| number_str.trim().parse::<u32>().unwrap())
-            .collect(),
-        my_numbers: numbers[1]
-            .split(" ")
-            .filter(|number_str| !number_str.is_empty())
-            .map(|number_str| number_str.trim().parse::<u32>().unwrap())
-            .collect(),
+        winning_numbers,
+        my_numbers,
+    })
+    .parse(input)
+}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, AocError> {
+    all_consuming(delimited(
+        multispace0,
+        separated_list1(line_ending, card),
+        multispace0,
+    ))(input)
+    .map(|(_, cards)| cards)
+    .map_err(|e| AocError::ParseError(e.to_string()))
+}
+
+/// How many copies of each card end up in the pile, in the order of `cards`.
+///
+/// A card with `n` matches wins a copy of each of the next `n` cards, once
+/// for every copy of it there is. Copies only ever flow forward, so a single
+/// pass works: each card adds its count to a running total of won copies,
+/// and that total drops the count again once past the last card it reaches.
+pub fn copies(cards: &[Card]) -> Result<Vec<u64>, AocError> {
+    let mut copies = Vec::with_capacity(cards.len());
+    // copies stop being won at each position, indexed up to one past the end
+    let mut expiring = vec![0_u64; cards.len() + 1];
+    let mut won: u64 = 0;
+
+    for (i, card) in cards.iter().enumerate() {
+        won -= expiring[i];
+        let count = won.checked_add(1).ok_or(AocError::TooManyCopies(card.id))?;
+        copies.push(count);
+
+        let end = (i + 1 + card.matches() as usize).min(cards.len());
+        if end > i + 1 {
+            won = won
+                .checked_add(count)
+                .ok_or(AocError::TooManyCopies(card.id))?;
+            expiring[end] += count;
+        }
+    }
+    Ok(copies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(id: u32, winning: &[u32], mine: &[u32]) -> Card {
+        Card {
+            id,
+            winning_numbers: NumberSet::from_numbers(winning.iter().copied()),
+            my_numbers: NumberSet::from_numbers(mine.iter().copied()),
+        }
+    }
+
+    #[test]
+    fn number_sets() {
+        let set = NumberSet::from_numbers([0, 63, 64, 127]);
+        assert_eq!(4, set.len());
+        assert!(set.contains(64) && !set.contains(65) && !set.contains(300));
+        assert!(set
+            .intersection(&NumberSet::from_numbers([1, 2]))
+            .is_empty());
+
+        let big = NumberSet::from_numbers([64, 200, 5000, u32::MAX]);
+        assert!(matches!(big, NumberSet::Sparse(_)));
+        assert!(big.contains(u32::MAX) && !big.contains(0));
+        assert_eq!(vec![64], big.intersection(&set).iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![64, 200],
+            NumberSet::from_numbers([200, 64, 3])
+                .intersection(&big)
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert!(NumberSet::from_numbers([]).is_empty());
+    }
+
+    #[test]
+    fn copies_per_card() -> miette::Result<()> {
+        let cards = parse_cards(
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+",
+        )?;
+        assert_eq!(
+            vec![4, 2, 2, 1, 0, 0],
+            cards.iter().map(Card::matches).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], copies(&cards)?);
+        Ok(())
+    }
+
+    #[test]
+    fn millions_of_cards() -> miette::Result<()> {
+        // every fourth card wins the next three, the last one runs off the end
+        let cards = (1..=3_000_000)
+            .map(|id| match id % 4 {
+                0 => card(id, &[1, 2, 3], &[1, 2, 3]),
+                _ => card(id, &[1, 2, 3], &[4, 5, 6]),
+            })
+            .collect::<Vec<_>>();
+        let copies = copies(&cards)?;
+
+        assert_eq!(3_000_000, copies.len());
+        // a winning card only reaches the next three, which don't win
+        assert!(copies.iter().all(|&count| count <= 2));
+        assert_eq!(3_000_000 + 3 * 749_999, copies.iter().sum::<u64>());
+        Ok(())
+    }
+
+    #[test]
+    fn runaway_copies() {
+        let cards = (1..=100)
+            .map(|id| card(id, &[1, 2], &[1, 2]))
+            .collect::<Vec<_>>();
+        assert!(matches!(copies(&cards), Err(AocError::TooManyCopies(_))));
+    }
+
+    #[test]
+    fn big_numbers() -> miette::Result<()> {
+        let cards = parse_cards("Card 1: 1 200 9000 | 3 200 9000\nCard 2: 5 | 4000000000")?;
+        assert_eq!(
+            vec![2, 0],
+            cards.iter().map(Card::matches).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_cards() {
+        for input in ["Card 1: 1 2 3", "Card: 1 | 2"] {
+            assert!(matches!(parse_cards(input), Err(AocError::ParseError(_))));
+        }
     }
 }
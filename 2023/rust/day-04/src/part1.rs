@@ -0,0 +1,51 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let cards = parse_cards(input)?;
+
+    cards.iter().try_fold(0_u64, |total, card| {
+        let points = match card.matches() {
+            0 => Some(0),
+            n => 1_u64.checked_shl(n - 1),
+        };
+        points
+            .and_then(|points| total.checked_add(points))
+            .ok_or(AocError::TooManyPoints(card.id))
+    })
+}
+
+#[cfg(test)]
+mod day_04_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let output = process(input)?;
+        assert_eq!(output, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn points_past_u64() {
+        let numbers = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = format!("Card 1: {numbers} | {numbers}");
+        assert!(matches!(process(&input), Err(AocError::TooManyPoints(1))));
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 24542);
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("the almanac has no {0} category")]
+    #[diagnostic(code(aoc::unknown_category))]
+    UnknownCategory(String),
+
+    #[error("no chain of maps leads from {from} to {to}")]
+    #[diagnostic(code(aoc::broken_chain))]
+    BrokenChain { from: String, to: String },
+
+    #[error("seeds come in pairs, but there are {0} of them")]
+    #[diagnostic(code(aoc::odd_seed_count))]
+    OddSeedCount(usize),
+
+    #[error("the seed range starting at {start} with length {len} runs past u64::MAX")]
+    #[diagnostic(code(aoc::seed_range_overflow))]
+    SeedRangeOverflow { start: u64, len: u64 },
+
+    #[error("the map line {dst} {src} {len} runs past u64::MAX")]
+    #[diagnostic(code(aoc::piece_overflow))]
+    PieceOverflow { dst: u64, src: u64, len: u64 },
+}
@@ -1,219 +1,475 @@
-use std::cmp;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::ops::Range;
 
+use aoc_common::interner::Interner;
+use aoc_common::parsers::{adjacency, blank_line, number, sections};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, multispace0, space1, u64},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+/// A map section: each `destination source length` line shifts a range of
+/// keys, and keys outside all of them map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    /// `source => (destination, length)`, the pieces don't overlap.
+    pieces: BTreeMap<u64, (u64, u64)>,
+}
+
+impl PiecewiseMap {
+    /// From `(destination, source, length)` lines, none of which may run past
+    /// `u64::MAX` on either side.
+    pub fn new(lines: Vec<(u64, u64, u64)>) -> Result<PiecewiseMap, AocError> {
+        lines
+            .into_iter()
+            .map(|(destination, source, len)| {
+                match (destination.checked_add(len), source.checked_add(len)) {
+                    (Some(_), Some(_)) => Ok((source, (destination, len))),
+                    _ => Err(AocError::PieceOverflow {
+                        dst: destination,
+                        src: source,
+                        len,
+                    }),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(|pieces| PiecewiseMap { pieces })
+    }
+
+    pub fn get(&self, key: u64) -> u64 {
+        match self.pieces.range(..=key).next_back() {
+            Some((src, (dst, len))) if key - src < *len => dst + (key - src),
+            _ => key,
+        }
+    }
+
+    /// Every key that maps to `key`. Pieces can land on keys that map to
+    /// themselves, so there may be several, or none at all.
+    pub fn preimages(&self, key: u64) -> Vec<u64> {
+        let mut preimages = self
+            .pieces
+            .iter()
+            .filter(|(_, (dst, len))| *dst <= key && key - dst < *len)
+            .map(|(src, (dst, _))| src + (key - dst))
+            .collect::<Vec<_>>();
+        if self.get(key) == key {
+            preimages.push(key);
+        }
+        preimages.sort();
+        preimages.dedup();
+        preimages
+    }
+
+    /// Splits `range` where pieces begin and end, pairing every part with
+    /// where its first key maps to.
+    fn split(&self, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let mut parts = Vec::new();
+        if range.is_empty() {
+            return parts;
+        }
+
+        // the piece right before the range may still cover its beginning
+        let mut start = range.start;
+        let before = self.pieces.range(..=start).next_back();
+        let within = self.pieces.range(start + 1..range.end);
+        for (&src, &(dst, len)) in before.into_iter().chain(within) {
+            let end = src.saturating_add(len).min(range.end);
+            if end <= start {
+                continue;
+            }
+            if src > start {
+                parts.push((start..src, start));
+                start = src;
+            }
+            parts.push((start..end, dst + (start - src)));
+            start = end;
+        }
+        if start < range.end {
+            parts.push((start..range.end, start));
+        }
+        parts
+    }
+
+    /// Where a range of keys ends up, as one range per piece it touches.
+    pub fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.split(range)
+            .into_iter()
+            .map(|(part, image)| image..image + (part.end - part.start))
+            .collect()
+    }
+
+    /// The map applying `self` and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = BTreeMap::new();
+
+        for (part, image) in self.split(0..u64::MAX) {
+            let len = part.end - part.start;
+            for (next_part, next_image) in next.split(image..image + len) {
+                let src = part.start + (next_part.start - image);
+                if src != next_image {
+                    pieces.insert(src, (next_image, next_part.end - next_part.start));
+                }
+            }
+        }
+        PiecewiseMap { pieces }
+    }
+}
+
+/// An `X-to-Y map:` section as parsed, its lines not yet checked.
+type Section<'a> = ((&'a str, &'a str), Vec<(u64, u64, u64)>);
+
+/// The seeds and the maps between categories, which form a graph with an
+/// edge per `X-to-Y map:` section.
 #[derive(Debug)]
 pub struct Almanac {
     pub seeds: Vec<u64>,
     pub seeds_v2: Vec<Range<u64>>,
-    seed_to_soil_map: BTreeMap<u64, (u64, u64)>,
-    soil_to_fertilizer_map: BTreeMap<u64, (u64, u64)>,
-    fertilizer_to_water_map: BTreeMap<u64, (u64, u64)>,
-    water_to_light_map: BTreeMap<u64, (u64, u64)>,
-    light_to_temperature_map: BTreeMap<u64, (u64, u64)>,
-    temperature_to_humidity_map: BTreeMap<u64, (u64, u64)>,
-    humidity_to_location_map: BTreeMap<u64, (u64, u64)>,
+    categories: Interner<u16>,
+    /// Maps leaving each category, indexed by its id.
+    maps: Vec<Vec<(u16, PiecewiseMap)>>,
 }
 
 impl Almanac {
-    pub fn new_from_aoc_input(input: &str) -> Almanac {
-        let sections: Vec<&str> = input.split("\n\n").collect();
-
-        let seeds: Vec<u64> = sections[0]
-            .split(": ")
-            .last()
-            .expect("there's no seeds")
-            .split(" ")
-            .map(|seed| seed.parse::<u64>().expect("couldn't parse seed"))
-            .collect();
+    pub fn new_from_aoc_input(input: &str) -> Result<Almanac, AocError> {
+        let (_, (seeds, maps)) =
+            Self::parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+        Self::from_maps(seeds, vec![], maps)
+    }
 
-        Almanac {
-            seeds,
-            seeds_v2: vec![],
-            seed_to_soil_map: Self::parse_map_section(sections[1]),
-            soil_to_fertilizer_map: Self::parse_map_section(sections[2]),
-            fertilizer_to_water_map: Self::parse_map_section(sections[3]),
-            water_to_light_map: Self::parse_map_section(sections[4]),
-            light_to_temperature_map: Self::parse_map_section(sections[5]),
-            temperature_to_humidity_map: Self::parse_map_section(sections[6]),
-            humidity_to_location_map: Self::parse_map_section(sections[7]),
+    pub fn new_from_aoc_input_v2(input: &str) -> Result<Almanac, AocError> {
+        let (_, (seeds, maps)) =
+            Self::parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+
+        let pairs = seeds.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(AocError::OddSeedCount(seeds.len()));
         }
+        let seeds = pairs
+            .map(|pair| match pair[0].checked_add(pair[1]) {
+                Some(end) => Ok(pair[0]..end),
+                None => Err(AocError::SeedRangeOverflow {
+                    start: pair[0],
+                    len: pair[1],
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_maps(vec![], seeds, maps)
     }
 
-    pub fn new_from_aoc_input_v2(input: &str) -> Almanac {
-        let sections: Vec<&str> = input.split("\n\n").collect();
-
-        let seeds: Vec<Range<u64>> = sections[0]
-            .split(": ")
-            .last()
-            .expect("there's no seeds")
-            .split(" ")
-            .collect::<Vec<&str>>()
-            .chunks(2)
-            .map(|chunk| {
-                let start = chunk[0]
-                    .parse::<u64>()
-                    .expect("couldn't parse seed interval start");
-                let len = chunk[1]
-                    .parse::<u64>()
-                    .expect("couldn't parse seed interval len");
-                start..start + len
-            })
-            .collect();
+    fn from_maps(
+        seeds: Vec<u64>,
+        seeds_v2: Vec<Range<u64>>,
+        sections: Vec<Section<'_>>,
+    ) -> Result<Almanac, AocError> {
+        let mut categories = Interner::new();
+        let mut maps: Vec<Vec<(u16, PiecewiseMap)>> = Vec::new();
 
-        Almanac {
-            seeds: vec![],
-            seeds_v2: seeds,
-            seed_to_soil_map: Self::parse_map_section(sections[1]),
-            soil_to_fertilizer_map: Self::parse_map_section(sections[2]),
-            fertilizer_to_water_map: Self::parse_map_section(sections[3]),
-            water_to_light_map: Self::parse_map_section(sections[4]),
-            light_to_temperature_map: Self::parse_map_section(sections[5]),
-            temperature_to_humidity_map: Self::parse_map_section(sections[6]),
-            humidity_to_location_map: Self::parse_map_section(sections[7]),
+        for ((from, to), lines) in sections {
+            let map = PiecewiseMap::new(lines)?;
+            let from = categories.intern(from);
+            let to = categories.intern(to);
+            maps.resize_with(categories.len(), Vec::new);
+            maps[from as usize].push((to, map));
         }
-    }
 
-    fn parse_map_section(section: &str) -> BTreeMap<u64, (u64, u64)> {
-        section
-            .lines()
-            .skip(1)
-            .map(|line| {
-                let mut parts = line.split(" ");
-                let dst = parts
-                    .next()
-                    .expect("no destination range start")
-                    .parse::<u64>()
-                    .expect("couldn't parse destination range start");
-                let src = parts
-                    .next()
-                    .expect("no source range start")
-                    .parse::<u64>()
-                    .expect("couldn't parse source range start");
-                let len = parts
-                    .next()
-                    .expect("no range length")
-                    .parse::<u64>()
-                    .expect("couldn't range length");
-                (src, (dst, len))
-            })
-            .collect()
+        Ok(Almanac {
+            seeds,
+            seeds_v2,
+            categories,
+            maps,
+        })
     }
 
-    pub fn merge_overlapping_intervals(arr: &mut Vec<Vec<u64>>) -> Vec<Vec<u64>> {
-        arr.sort_by(|a, b| a[0].cmp(&b[0]));
+    fn parse(input: &str) -> IResult<&str, (Vec<u64>, Vec<Section<'_>>)> {
+        let (input, (_, seeds)) = adjacency(tag("seeds"), number)(input)?;
+        let (input, _) = blank_line(input)?;
+        let (input, maps) =
+            all_consuming(terminated(sections(Self::parse_map_section), multispace0))(input)?;
 
-        let mut result: Vec<Vec<u64>> = Vec::new();
-        result.push(arr[0].clone());
+        Ok((input, (seeds, maps)))
+    }
 
-        for i in 1..arr.len() {
-            let current: Vec<u64> = arr[i].clone();
-            let j: usize = result.len() - 1;
+    fn parse_map_section(input: &str) -> IResult<&str, Section<'_>> {
+        let (input, categories) = terminated(
+            separated_pair(alpha1, tag("-to-"), alpha1),
+            terminated(tag(" map:"), line_ending),
+        )(input)?;
+        let (input, lines) = separated_list1(
+            line_ending,
+            tuple((u64, preceded(space1, u64), preceded(space1, u64))),
+        )(input)?;
 
-            if current[0] >= result[j][0] && current[0] <= result[j][1] {
-                result[j][1] = cmp::max(current[1], result[j][1]);
-            } else {
-                result.push(current);
-            }
-        }
-        result
+        Ok((input, (categories, lines)))
     }
 
-    pub fn seed_to_soil(&self, key: u64) -> u64 {
-        match self.seed_to_soil_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
-        }
+    fn category(&self, name: &str) -> Result<u16, AocError> {
+        self.categories
+            .get(name)
+            .ok_or_else(|| AocError::UnknownCategory(name.to_string()))
     }
 
-    pub fn soil_to_fertilizer(&self, key: u64) -> u64 {
-        match self.soil_to_fertilizer_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
+    /// The maps to go through, in order, to get from one category to another.
+    /// Empty when both are the same.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&PiecewiseMap>, AocError> {
+        let (start, end) = (self.category(from)?, self.category(to)?);
+
+        // breadth first, remembering the map each category was reached by
+        let mut reached_by: Vec<Option<(u16, &PiecewiseMap)>> = vec![None; self.categories.len()];
+        let mut queue = VecDeque::from([start]);
+        while let Some(category) = queue.pop_front() {
+            if category == end {
+                break;
+            }
+            for (next, map) in self.maps.get(category as usize).into_iter().flatten() {
+                if *next != start && reached_by[*next as usize].is_none() {
+                    reached_by[*next as usize] = Some((category, map));
+                    queue.push_back(*next);
                 }
-                result
             }
-            None => key,
         }
-    }
 
-    pub fn fertilizer_to_water(&self, key: u64) -> u64 {
-        match self.fertilizer_to_water_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
+        let mut chain = Vec::new();
+        let mut category = end;
+        while category != start {
+            let (previous, map) =
+                reached_by[category as usize].ok_or_else(|| AocError::BrokenChain {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                })?;
+            chain.push(map);
+            category = previous;
         }
+        chain.reverse();
+        Ok(chain)
     }
 
-    pub fn water_to_light(&self, key: u64) -> u64 {
-        match self.water_to_light_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
-        }
+    pub fn lookup(&self, from: &str, to: &str, key: u64) -> Result<u64, AocError> {
+        Ok(self
+            .chain(from, to)?
+            .into_iter()
+            .fold(key, |key, map| map.get(key)))
     }
 
-    pub fn light_to_temperature(&self, key: u64) -> u64 {
-        match self.light_to_temperature_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
+    /// Every key of `to` that `from` would map to `key`, the way back along
+    /// the chain from `to` to `from`.
+    pub fn reverse_lookup(&self, from: &str, to: &str, key: u64) -> Result<Vec<u64>, AocError> {
+        let mut keys = vec![key];
+        for map in self.chain(to, from)?.into_iter().rev() {
+            keys = keys
+                .into_iter()
+                .flat_map(|key| map.preimages(key))
+                .collect();
+            keys.sort();
+            keys.dedup();
         }
+        Ok(keys)
     }
 
-    pub fn temperature_to_humidity(&self, key: u64) -> u64 {
-        match self.temperature_to_humidity_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
+    /// The maps between two categories composed into one.
+    pub fn map_between(&self, from: &str, to: &str) -> Result<PiecewiseMap, AocError> {
+        Ok(self
+            .chain(from, to)?
+            .into_iter()
+            .fold(PiecewiseMap::default(), |composed, map| composed.then(map)))
+    }
+
+    /// Pushes ranges through the chain one map at a time, splitting them at
+    /// every piece boundary on the way.
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: &[Range<u64>],
+    ) -> Result<Vec<Range<u64>>, AocError> {
+        Ok(self
+            .chain(from, to)?
+            .into_iter()
+            .fold(ranges.to_vec(), |ranges, map| {
+                ranges
+                    .into_iter()
+                    .flat_map(|range| map.map_range(range))
+                    .collect()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn ranges_split_at_pieces() {
+        let map = PiecewiseMap {
+            pieces: BTreeMap::from([(98, (50, 2)), (50, (52, 48))]),
+        };
+        assert_eq!(vec![99..100, 50..51], map.map_range(97..99));
+        assert_eq!(
+            vec![40..50, 52..100, 50..52, 100..110],
+            map.map_range(40..110)
+        );
+        assert_eq!(vec![5..6], map.map_range(5..6));
+        assert!(map.map_range(7..7).is_empty());
+        assert_eq!(99, map.get(97));
+        assert_eq!(100, map.get(100));
+    }
+
+    #[test]
+    fn composed_maps_agree_with_lookups() -> miette::Result<()> {
+        let almanac = Almanac::new_from_aoc_input_v2(EXAMPLE)?;
+        let composed = almanac.map_between("seed", "location")?;
+
+        for seed in 0..200 {
+            let location = almanac.lookup("seed", "location", seed)?;
+            assert_eq!(location, composed.get(seed), "seed {seed}");
+            assert!(almanac
+                .reverse_lookup("location", "seed", location)?
+                .contains(&seed));
         }
+
+        let locations = |ranges: Vec<Range<u64>>| {
+            let mut locations = ranges.into_iter().flatten().collect::<Vec<_>>();
+            locations.sort();
+            locations
+        };
+        let mapped = almanac
+            .seeds_v2
+            .iter()
+            .flat_map(|range| composed.map_range(range.clone()))
+            .collect();
+        let pushed = almanac.map_ranges("seed", "location", &almanac.seeds_v2)?;
+        assert_eq!(locations(pushed.clone()), locations(mapped));
+        assert_eq!(Some(&46), locations(pushed).first());
+        Ok(())
     }
 
-    pub fn humidity_to_location(&self, key: u64) -> u64 {
-        match self.humidity_to_location_map.range(..=key).next_back() {
-            Some((src, (dst, len))) => {
-                let distance = key - src;
-                let result = dst + distance;
-                if result > dst + len {
-                    return key;
-                }
-                result
-            }
-            None => key,
+    #[test]
+    fn queries_between_any_categories() -> miette::Result<()> {
+        let almanac = Almanac::new_from_aoc_input(EXAMPLE)?;
+
+        assert_eq!(81, almanac.lookup("seed", "soil", 79)?);
+        assert_eq!(78, almanac.lookup("water", "humidity", 81)?);
+        assert_eq!(79, almanac.lookup("light", "light", 79)?);
+        assert_eq!(vec![79], almanac.reverse_lookup("location", "seed", 82)?);
+        // 52 is both where seed 50 lands and a soil key mapping to itself
+        assert_eq!(vec![50], almanac.reverse_lookup("soil", "seed", 52)?);
+
+        assert!(matches!(
+            almanac.lookup("location", "seed", 1),
+            Err(AocError::BrokenChain { .. })
+        ));
+        assert!(matches!(
+            almanac.lookup("seed", "gold", 1),
+            Err(AocError::UnknownCategory(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn broken_chain() -> miette::Result<()> {
+        let almanac = Almanac::new_from_aoc_input(
+            "seeds: 1 2
+
+seed-to-soil map:
+50 98 2
+
+water-to-light map:
+88 18 7",
+        )?;
+
+        assert_eq!(2, almanac.lookup("seed", "soil", 2)?);
+        assert!(matches!(
+            almanac.lookup("seed", "light", 2),
+            Err(AocError::BrokenChain { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_almanacs() {
+        for input in [
+            "seeds: 1 2\n\nseed-to-soil:\n50 98 2",
+            "seeds: 1 2\n\nseed-to-soil map:\n50 98",
+            "seeds: 1 2\n\nseed-to-soil map:\n50 98 2 7",
+        ] {
+            assert!(
+                matches!(
+                    Almanac::new_from_aoc_input(input),
+                    Err(AocError::ParseError(_))
+                ),
+                "{input:?}"
+            );
+        }
+
+        let map = "\n\nseed-to-soil map:\n50 98 2";
+        assert!(matches!(
+            Almanac::new_from_aoc_input_v2(&format!("seeds: 1 2 3{map}")),
+            Err(AocError::OddSeedCount(3))
+        ));
+        assert!(matches!(
+            Almanac::new_from_aoc_input_v2(&format!("seeds: {} 2{map}", u64::MAX)),
+            Err(AocError::SeedRangeOverflow { .. })
+        ));
+
+        for map in [
+            format!("{} 0 10", u64::MAX),
+            format!("0 {} 10", u64::MAX - 3),
+        ] {
+            assert!(matches!(
+                Almanac::new_from_aoc_input(&format!("seeds: 5 3\n\nseed-to-location map:\n{map}")),
+                Err(AocError::PieceOverflow { .. })
+            ));
         }
+        // right up to the last key is fine
+        let almanac = Almanac::new_from_aoc_input(&format!(
+            "seeds: 5\n\nseed-to-location map:\n{} 0 10",
+            u64::MAX - 10
+        ))
+        .unwrap();
+        assert_eq!(u64::MAX - 1, almanac.lookup("seed", "location", 9).unwrap());
     }
 }
@@ -0,0 +1,66 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let almanac = Almanac::new_from_aoc_input(input)?;
+    let seed_to_location = almanac.map_between("seed", "location")?;
+
+    Ok(almanac
+        .seeds
+        .iter()
+        .map(|seed| seed_to_location.get(*seed))
+        .min()
+        .unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod day_05_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+        let output = process(input)?;
+        assert_eq!(output, 35);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 1181555926);
+        Ok(())
+    }
+}
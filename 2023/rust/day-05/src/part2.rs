@@ -0,0 +1,100 @@
+use crate::{custom_error::AocError, *};
+#[cfg(all(feature = "progress", feature = "parallel"))]
+use indicatif::ParallelProgressIterator;
+#[cfg(all(feature = "progress", not(feature = "parallel")))]
+use indicatif::ProgressIterator;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let almanac = Almanac::new_from_aoc_input_v2(input)?;
+    let seed_to_location = almanac.map_between("seed", "location")?;
+
+    Ok(almanac
+        .seeds_v2
+        .iter()
+        .flat_map(|range| seed_to_location.map_range(range.clone()))
+        .map(|locations| locations.start)
+        .min()
+        .unwrap_or(u64::MAX))
+}
+
+/// Looks up every seed one by one, to check [`process`] against.
+pub fn brute_force(input: &str) -> miette::Result<u64, AocError> {
+    let almanac = Almanac::new_from_aoc_input_v2(input)?;
+    let chain = almanac.chain("seed", "location")?;
+
+    #[cfg(feature = "parallel")]
+    let seeds = almanac
+        .seeds_v2
+        .par_iter()
+        .flat_map(|range| range.clone().into_par_iter());
+    #[cfg(not(feature = "parallel"))]
+    let seeds = almanac.seeds_v2.iter().flat_map(|range| range.clone());
+    #[cfg(feature = "progress")]
+    let seeds = seeds.progress_count(
+        almanac
+            .seeds_v2
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum(),
+    );
+
+    let locations = seeds.map(|seed| chain.iter().fold(seed, |key, map| map.get(key)));
+
+    Ok(locations.min().unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod day_05_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+        let output = process(input)?;
+        assert_eq!(output, 46);
+        assert_eq!(brute_force(input)?, 46);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 37806486);
+        Ok(())
+    }
+}
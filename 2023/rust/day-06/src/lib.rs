@@ -1,3 +1,8 @@
+pub mod custom_error;
+
+pub mod part1;
+pub mod part2;
+
 // Millimeters per millisecond
 const VELOCITY: u64 = 1;
 
@@ -7,17 +12,6 @@ fn distance(hold_time_ms: u64, max_time_ms: u64) -> u64 {
     VELOCITY * hold_time_ms * (max_time_ms - hold_time_ms)
 }
 
-/// Solve a quadratic equation `ax^2 + bx + c = 0`
-fn solve_quadratic(a: f64, b: f64, c: f64) -> (f64, f64) {
-    let discriminant = b.powi(2) - 4.0 * a * c;
-    let sqrt_discriminant = discriminant.sqrt();
-
-    let x1 = (-b - sqrt_discriminant) / (2.0 * a);
-    let x2 = (-b + sqrt_discriminant) / (2.0 * a);
-
-    (x1, x2)
-}
-
 #[derive(Debug)]
 pub struct BoatRace {
     max_time: u64,
@@ -48,25 +42,85 @@ impl BoatRace {
             .count() as u64
     }
 
+    /// Whether holding for `hold_time` goes further than the record, worked
+    /// out in `u128` so it can't overflow.
+    fn wins(&self, hold_time: u128) -> bool {
+        let max_time = self.max_time as u128;
+        hold_time <= max_time
+            && VELOCITY as u128 * hold_time * (max_time - hold_time) > self.record_distance as u128
+    }
+
     /// The optimal way to calculate the ways to win is to solve the quadratic
     /// equation `y = x(t - x)`, where:
     /// - `y` is the record distance,
     /// - `x` is the hold time, and
     /// - `t` is the max time.
     ///
-    /// In standard form, this is `x^2 - tx + y = 0`, with constants
-    /// - `a = 1`
-    /// - `b = -t`
-    /// - `c = y`
-    ///
-    /// We add one to the lower solution and subtract one from the upper solution
-    /// to handle the cases where the solutions are integers (where we'd tie the
-    /// record distance and not beat it), then floor/ceil them to the nearest int.
+    /// In standard form, this is `x^2 - tx + y = 0`, whose roots are
+    /// `(t ± sqrt(t^2 - 4y)) / 2`. Everything is exact in `u128`: the integer
+    /// square root puts the lower root within a couple of hold times of the
+    /// first one that wins, and checking those directly handles roots that
+    /// land exactly on the record, which only ties it.
     ///
-    /// We also add one to make the range inclusive.
+    /// Distances are symmetric around `t / 2`, so if `x` is the first winning
+    /// hold time, `t - x` is the last one.
     pub fn ways_to_win(&self) -> u64 {
-        let (x1, x2) = solve_quadratic(1.0, -(self.max_time as f64), self.record_distance as f64);
-        let (x1, x2) = ((x1 + 1.0).floor(), (x2 - 1.0).ceil());
-        x2 as u64 - x1 as u64 + 1
+        let max_time = self.max_time as u128;
+        let Some(discriminant) =
+            (max_time * max_time).checked_sub(4 * self.record_distance as u128)
+        else {
+            return 0;
+        };
+        let lower_root = (max_time - discriminant.isqrt()) / 2;
+
+        match (lower_root.saturating_sub(1)..=lower_root + 1).find(|&x| self.wins(x)) {
+            Some(first) => (max_time - 2 * first + 1) as u64,
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn ways_to_win_matches_naive(max_time in 2u64..5_000, record in any::<prop::sample::Index>()) {
+            // only records that can still be beaten by holding for half the race
+            let best = distance(max_time / 2, max_time);
+            let race = BoatRace::new(max_time, record.index(best as usize) as u64);
+            prop_assert_eq!(race.ways_to_win(), race.naive_ways_to_win());
+        }
+
+        #[test]
+        fn ties_dont_win(max_time in 0u64..5_000, hold_time in any::<prop::sample::Index>()) {
+            // a record some hold time matches exactly, so a root is an integer
+            let record = distance(hold_time.index(max_time as usize + 1) as u64, max_time);
+            let race = BoatRace::new(max_time, record);
+            prop_assert_eq!(race.ways_to_win(), race.naive_ways_to_win());
+        }
+
+        #[test]
+        fn ways_to_win_past_f64_precision(max_time in 1u64 << 53.., record in any::<u64>()) {
+            let race = BoatRace::new(max_time, record);
+            let ways = race.ways_to_win() as u128;
+            prop_assume!(ways > 0);
+
+            // the winning hold times are exactly the ones between the first
+            // and the last
+            let first = (max_time as u128 + 1 - ways) / 2;
+            prop_assert!(race.wins(first) && race.wins(max_time as u128 - first));
+            prop_assert!(!race.wins(first - 1) && !race.wins(max_time as u128 - first + 1));
+        }
+    }
+
+    #[test]
+    fn unwinnable_races() {
+        assert_eq!(0, BoatRace::new(0, 0).ways_to_win());
+        assert_eq!(0, BoatRace::new(4, 4).ways_to_win());
+        assert_eq!(0, BoatRace::new(10, 1_000).ways_to_win());
+        assert_eq!(1, BoatRace::new(4, 3).ways_to_win());
     }
 }
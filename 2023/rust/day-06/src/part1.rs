@@ -0,0 +1,53 @@
+use crate::{custom_error::AocError, *};
+
+use aoc_common::parsers::{adjacency, number};
+use nom::{character::complete::alpha1, IResult};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let boat_races = parse(input);
+    Ok(boat_races.iter().map(|br| br.naive_ways_to_win()).product())
+}
+
+fn parse_line(input: &str) -> IResult<&str, Vec<u64>> {
+    let (input, (_, vals)) = adjacency(alpha1, number)(input)?;
+
+    Ok((input, vals))
+}
+
+fn parse(input: &str) -> Vec<BoatRace> {
+    let parsed = input
+        .lines()
+        .map(|line| {
+            let (_, v) = parse_line(line).expect("a valid line");
+            v
+        })
+        .collect::<Vec<Vec<u64>>>();
+
+    parsed[0]
+        .iter()
+        .zip(parsed[1].iter())
+        .map(|(t, d)| BoatRace::new(*t, *d))
+        .collect()
+}
+
+#[cfg(test)]
+mod day_06_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        let output = process(input)?;
+        assert_eq!(output, 288);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 5133600);
+        Ok(())
+    }
+}
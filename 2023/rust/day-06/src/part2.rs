@@ -0,0 +1,57 @@
+use crate::{custom_error::AocError, *};
+
+use nom::{
+    bytes::complete::take_until,
+    character::complete::{self},
+    IResult, Parser,
+};
+use nom_supreme::{tag::complete::tag, ParserExt};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let boat_race = parse(input);
+    Ok(boat_race.ways_to_win())
+}
+
+fn parse_line(input: &str) -> IResult<&str, u64> {
+    let (input, val) = take_until(":")
+        .precedes(tag(":"))
+        .precedes(complete::u64)
+        .parse(input)?;
+
+    Ok((input, val))
+}
+
+fn parse(input: &str) -> BoatRace {
+    let parsed = input
+        .lines()
+        .map(|line| {
+            let (_, v) = parse_line(line.replace(" ", "").as_str()).expect("a valid line");
+            v
+        })
+        .collect::<Vec<u64>>();
+
+    BoatRace::new(parsed[0], parsed[1])
+}
+
+#[cfg(test)]
+mod day_06_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        let output = process(input)?;
+        assert_eq!(output, 71503);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 40651271);
+        assert_eq!(parse(input).naive_ways_to_win(), output);
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("{0:?} isn't in the card order")]
+    #[diagnostic(code(aoc::unknown_card))]
+    UnknownCard(char),
+
+    #[error("a hand needs at least one card")]
+    #[diagnostic(code(aoc::empty_hand))]
+    EmptyHand,
+
+    #[error("the total winnings don't fit in a u64")]
+    #[diagnostic(code(aoc::winnings_overflow))]
+    WinningsOverflow,
+}
@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+
+use aoc_common::parsers::number;
+use nom::{
+    character::complete::{alphanumeric1, line_ending, multispace0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+};
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+/// Hand types, weakest first. Hands of any length are classified by their
+/// two biggest groups of equal cards, so six cards making two triples are a
+/// full house and three pairs are two pairs.
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPairs,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// From the sizes of the groups of equal cards, biggest first.
+    fn classify(groups: &[u32]) -> HandType {
+        let second = groups.get(1).copied().unwrap_or(0);
+        match groups.first().copied().unwrap_or(0) {
+            5.. => HandType::FiveOfAKind,
+            4 => HandType::FourOfAKind,
+            3 if second >= 2 => HandType::FullHouse,
+            3 => HandType::ThreeOfAKind,
+            2 if second == 2 => HandType::TwoPairs,
+            2 => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// How hands of the same type are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Card by card, in the order they were dealt. Wildcards rank as
+    /// themselves.
+    InOrder,
+    /// Biggest groups first, as in poker. Wildcards rank as the card they
+    /// stand in for.
+    ByGroups,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandRules {
+    /// Every card, weakest first.
+    order: Vec<char>,
+    wildcards: Vec<char>,
+    tie_break: TieBreak,
+}
+
+impl HandRules {
+    pub fn new(order: &str, wildcards: &str, tie_break: TieBreak) -> HandRules {
+        HandRules {
+            order: order.chars().collect(),
+            wildcards: wildcards.chars().collect(),
+            tie_break,
+        }
+    }
+
+    pub fn camel_cards() -> HandRules {
+        HandRules::new("23456789TJQKA", "", TieBreak::InOrder)
+    }
+
+    /// Jacks become jokers, the weakest card, which stand in for whatever
+    /// makes the best hand.
+    pub fn camel_cards_with_jokers() -> HandRules {
+        HandRules::new("J23456789TQKA", "J", TieBreak::InOrder)
+    }
+
+    fn strength(&self, card: char) -> Result<u8, AocError> {
+        self.order
+            .iter()
+            .position(|&c| c == card)
+            .map(|strength| strength as u8)
+            .ok_or(AocError::UnknownCard(card))
+    }
+
+    pub fn hand(&self, cards: &str) -> Result<Hand, AocError> {
+        let strengths = cards
+            .chars()
+            .map(|card| self.strength(card))
+            .collect::<Result<Vec<_>, _>>()?;
+        if strengths.is_empty() {
+            return Err(AocError::EmptyHand);
+        }
+
+        let is_wild = |card: &char| self.wildcards.contains(card);
+        let mut counts = vec![0_u32; self.order.len()];
+        for (card, &strength) in cards.chars().zip(&strengths) {
+            if !is_wild(&card) {
+                counts[strength as usize] += 1;
+            }
+        }
+        let wild = cards.chars().filter(is_wild).count() as u32;
+
+        // every wildcard joins the biggest group, the strongest one on ties;
+        // with nothing but wildcards they become the strongest other card
+        let stand_in = (0..self.order.len())
+            .filter(|&strength| !is_wild(&self.order[strength]))
+            .max_by_key(|&strength| (counts[strength], strength));
+        let best = match stand_in {
+            Some(stand_in) => {
+                counts[stand_in] += wild;
+                cards
+                    .chars()
+                    .map(|card| match is_wild(&card) {
+                        true => self.order[stand_in],
+                        false => card,
+                    })
+                    .collect()
+            }
+            None => cards.to_string(),
+        };
+
+        let mut groups = counts
+            .iter()
+            .copied()
+            .filter(|&n| n > 0)
+            .collect::<Vec<_>>();
+        groups.sort_by_key(|&n| Reverse(n));
+        let hand_type = match stand_in {
+            Some(_) => HandType::classify(&groups),
+            None => HandType::classify(&[wild]),
+        };
+
+        let tie_break = match self.tie_break {
+            TieBreak::InOrder => strengths,
+            TieBreak::ByGroups => {
+                let mut strengths = best
+                    .chars()
+                    .map(|card| self.strength(card))
+                    .collect::<Result<Vec<_>, _>>()?;
+                strengths.sort_by_key(|&strength| Reverse((counts[strength as usize], strength)));
+                strengths
+            }
+        };
+
+        Ok(Hand {
+            hand_type,
+            tie_break,
+            cards: cards.to_string(),
+            best,
+        })
+    }
+}
+
+/// Ordered by type, then by the rules' tie break.
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+pub struct Hand {
+    pub hand_type: HandType,
+    tie_break: Vec<u8>,
+    pub cards: String,
+    /// The hand the wildcards make at best, `cards` when there are none.
+    pub best: String,
+}
+
+pub fn parse_hands(input: &str, rules: &HandRules) -> Result<Vec<(Hand, u64)>, AocError> {
+    let (_, lines) = all_consuming(delimited(
+        multispace0,
+        separated_list1(line_ending, separated_pair(alphanumeric1, space1, number)),
+        multispace0,
+    ))(input)
+    .map_err(|e: nom::Err<nom::error::Error<&str>>| AocError::ParseError(e.to_string()))?;
+
+    lines
+        .into_iter()
+        .map(|(cards, bid)| Ok((rules.hand(cards)?, bid)))
+        .collect()
+}
+
+/// Every bid times the rank of its hand, the weakest ranking 1.
+pub fn total_winnings(mut hands: Vec<(Hand, u64)>) -> Result<u64, AocError> {
+    hands.sort();
+    hands
+        .iter()
+        .enumerate()
+        .try_fold(0_u64, |total, (idx, (_, bid))| {
+            (idx as u64 + 1)
+                .checked_mul(*bid)
+                .and_then(|winnings| total.checked_add(winnings))
+                .ok_or(AocError::WinningsOverflow)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jokers_make_the_best_hand() -> miette::Result<()> {
+        let rules = HandRules::camel_cards_with_jokers();
+
+        for (cards, best, hand_type) in [
+            ("KTJJT", "KTTTT", HandType::FourOfAKind),
+            ("T55J5", "T5555", HandType::FourOfAKind),
+            ("2345J", "23455", HandType::OnePair),
+            ("22J33", "22333", HandType::FullHouse),
+            ("JJJJJ", "AAAAA", HandType::FiveOfAKind),
+            ("32T3K", "32T3K", HandType::OnePair),
+        ] {
+            let hand = rules.hand(cards)?;
+            assert_eq!((best, hand_type), (hand.best.as_str(), hand.hand_type));
+        }
+        // jokers are the weakest card when breaking ties
+        assert!(rules.hand("JKKK2")? < rules.hand("QQQQ2")?);
+        Ok(())
+    }
+
+    #[test]
+    fn hands_of_any_length() -> miette::Result<()> {
+        let rules = HandRules::camel_cards_with_jokers();
+
+        assert_eq!(HandType::FiveOfAKind, rules.hand("AAAAAAJ")?.hand_type);
+        assert_eq!(HandType::FullHouse, rules.hand("222333")?.hand_type);
+        assert_eq!(HandType::TwoPairs, rules.hand("223344")?.hand_type);
+        assert_eq!(HandType::ThreeOfAKind, rules.hand("2345JJ6")?.hand_type);
+        assert_eq!(HandType::OnePair, rules.hand("2J")?.hand_type);
+        assert_eq!(HandType::HighCard, rules.hand("A")?.hand_type);
+        Ok(())
+    }
+
+    #[test]
+    fn tie_breaks() -> miette::Result<()> {
+        let in_order = HandRules::camel_cards();
+        let by_groups = HandRules::new("23456789TJQKA", "", TieBreak::ByGroups);
+
+        assert!(in_order.hand("2AAAA")? < in_order.hand("33332")?);
+        assert!(by_groups.hand("2AAAA")? > by_groups.hand("33332")?);
+        // two pairs, but aces beat kings
+        assert!(in_order.hand("KK223")? > in_order.hand("QQAA2")?);
+        assert!(by_groups.hand("KK223")? < by_groups.hand("QQAA2")?);
+        Ok(())
+    }
+
+    #[test]
+    fn winnings_past_u64() -> miette::Result<()> {
+        let rules = HandRules::camel_cards();
+        let hands = parse_hands("32T3K 684\nQQQJA 9223372036854775807", &rules)?;
+        assert!(matches!(
+            total_winnings(hands),
+            Err(AocError::WinningsOverflow)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_hands() {
+        let rules = HandRules::camel_cards();
+        assert!(matches!(
+            rules.hand("AAXAA"),
+            Err(AocError::UnknownCard('X'))
+        ));
+        assert!(matches!(rules.hand(""), Err(AocError::EmptyHand)));
+        assert!(matches!(
+            parse_hands("AAAAA", &rules),
+            Err(AocError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let hands = parse_hands(input, &HandRules::camel_cards())?;
+    total_winnings(hands)
+}
+
+#[cfg(test)]
+mod day_07_part1 {
+    use super::*;
+
+    fn hand(cards: &str) -> Hand {
+        HandRules::camel_cards().hand(cards).unwrap()
+    }
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let output = process(input)?;
+        assert_eq!(output, 6440);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 246912307);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_hand_type_sort() {
+        assert!(hand("AA8AA") < hand("AAAAA"));
+        assert!(hand("23332") < hand("AA8AA"));
+        assert!(hand("TTT98") < hand("23332"));
+        assert!(hand("23432") < hand("TTT98"));
+        assert!(hand("A23A4") < hand("23432"));
+        assert!(hand("23456") < hand("A23A4"));
+    }
+
+    #[test]
+    fn same_hand_type_sort() {
+        assert!(hand("2AAAA") < hand("33332"));
+        assert!(hand("77788") < hand("77888"));
+    }
+}
@@ -0,0 +1,37 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("there's no node {0}")]
+    #[diagnostic(code(aoc::unknown_node))]
+    UnknownNode(String),
+
+    #[error("walking from {start} never reaches {end}")]
+    #[diagnostic(code(aoc::unreachable))]
+    Unreachable { start: String, end: String },
+
+    #[error("there are no ghosts, no node ends in A")]
+    #[diagnostic(code(aoc::no_ghosts))]
+    NoGhosts,
+
+    #[error("the ghosts never all stand on end nodes at once")]
+    #[diagnostic(code(aoc::never_simultaneous))]
+    NeverSimultaneous,
+
+    #[error("the ghosts' end nodes line up in too many ways to check")]
+    #[diagnostic(code(aoc::too_many_combinations))]
+    TooManyCombinations,
+
+    #[error("the ghosts take too many steps to count")]
+    #[diagnostic(code(aoc::too_many_steps))]
+    TooManySteps,
+}
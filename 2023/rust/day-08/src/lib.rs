@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use aoc_common::{dot, interner::Interner, parsers::key_value};
+use nom::{
+    branch::alt,
+    character::complete::{alphanumeric1, char, multispace0},
+    combinator::{all_consuming, value},
+    multi::{fold_many0, many1},
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use nom_supreme::tag::complete::tag;
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+/// Nodes are indexed by their interned id, holding the `(left, right)` pair.
+#[derive(Debug, Default)]
+pub struct Graph {
+    pub names: Interner<u32>,
+    nodes: Vec<Option<(u32, u32)>>,
+}
+
+impl Graph {
+    fn insert(&mut self, node: &str, (left, right): (&str, &str)) {
+        let [node, left, right] = [node, left, right].map(|name| self.names.intern(name));
+        self.nodes.resize(self.names.len(), None);
+        self.nodes[node as usize] = Some((left, right));
+    }
+
+    pub fn get(&self, node: u32) -> Option<(u32, u32)> {
+        self.nodes.get(node as usize).copied().flatten()
+    }
+
+    pub fn id(&self, name: &str) -> Result<u32, AocError> {
+        self.names
+            .get(name)
+            .ok_or_else(|| AocError::UnknownNode(name.to_string()))
+    }
+
+    pub fn step(&self, node: u32, direction: Direction) -> Result<u32, AocError> {
+        match (self.get(node), direction) {
+            (Some((left, _)), Direction::Left) => Ok(left),
+            (Some((_, right)), Direction::Right) => Ok(right),
+            (None, _) => Err(AocError::UnknownNode(self.names.resolve(node).to_string())),
+        }
+    }
+
+    /// Walks from `start` until a `(node, instruction index)` state repeats,
+    /// noting every step that lands on an end node.
+    pub fn orbit(
+        &self,
+        directions: &[Direction],
+        start: u32,
+        is_end: impl Fn(&str) -> bool,
+    ) -> Result<Orbit, AocError> {
+        // the step each `(node, instruction)` state was first reached at
+        let mut seen = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+
+        for step in 0_u64.. {
+            let instruction = step as usize % directions.len();
+            if let Some(&pre_period) = seen.get(&(node, instruction)) {
+                let split = hits.partition_point(|&hit| hit < pre_period);
+                let cycle_hits = hits.split_off(split);
+                return Ok(Orbit {
+                    pre_period,
+                    period: step - pre_period,
+                    transient_hits: hits,
+                    cycle_hits,
+                });
+            }
+            seen.insert((node, instruction), step);
+
+            if is_end(self.names.resolve(node)) {
+                hits.push(step);
+            }
+            node = self.step(node, directions[instruction])?;
+        }
+        unreachable!("there are finitely many states")
+    }
+
+    /// Steps it takes to walk from `start` to `end`, an error rather than a
+    /// hang when the walk goes round in circles without getting there.
+    pub fn steps_between(
+        &self,
+        directions: &[Direction],
+        start: &str,
+        end: &str,
+    ) -> Result<u64, AocError> {
+        self.id(end)?;
+        let orbit = self.orbit(directions, self.id(start)?, |name| name == end)?;
+        orbit
+            .transient_hits
+            .first()
+            .or(orbit.cycle_hits.first())
+            .copied()
+            .ok_or_else(|| AocError::Unreachable {
+                start: start.to_string(),
+                end: end.to_string(),
+            })
+    }
+
+    /// Start nodes in green and end nodes in red, edges labelled with the
+    /// direction that takes them.
+    pub fn to_dot(&self) -> dot::Graph {
+        let mut graph = dot::Graph::directed();
+        for (id, name) in self.names.iter() {
+            match name.chars().last() {
+                Some('A') => graph.node(name, &[("style", "filled"), ("fillcolor", "lightgreen")]),
+                Some('Z') => graph.node(name, &[("style", "filled"), ("fillcolor", "salmon")]),
+                _ => graph.node(name, &[]),
+            };
+            if let Some((left, right)) = self.get(id) {
+                graph.edge(name, self.names.resolve(left), &[("label", "L")]);
+                graph.edge(name, self.names.resolve(right), &[("label", "R")]);
+            }
+        }
+        graph
+    }
+}
+
+/// The steps at which a walk is on an end node. Once past `pre_period`
+/// steps the walk repeats every `period` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orbit {
+    pub pre_period: u64,
+    pub period: u64,
+    /// Hits before the cycle starts, which never happen again.
+    pub transient_hits: Vec<u64>,
+    /// Hits during the first time round the cycle.
+    pub cycle_hits: Vec<u64>,
+}
+
+impl Orbit {
+    pub fn hits(&self, step: u128) -> bool {
+        let (pre_period, period) = (self.pre_period as u128, self.period as u128);
+        if step < pre_period {
+            self.transient_hits.binary_search(&(step as u64)).is_ok()
+        } else {
+            let step = pre_period + (step - pre_period) % period;
+            self.cycle_hits.binary_search(&(step as u64)).is_ok()
+        }
+    }
+}
+
+pub const SCAN_STEPS: u128 = 1 << 16;
+pub const MAX_COMBINATIONS: usize = 1 << 20;
+
+/// The first step at which every orbit hits an end node.
+///
+/// Any such step before the last cycle starts is a transient hit of some
+/// orbit, so those get checked directly. Past that, each orbit pins the step
+/// to one of its cycle hits modulo its period, and every choice of hits
+/// makes a system of congruences whose moduli needn't be coprime.
+///
+/// The choices multiply with every orbit, so the first [`SCAN_STEPS`] steps
+/// past the last cycle's start are walked before trying them, and more than
+/// [`MAX_COMBINATIONS`] of them is an error.
+pub fn first_simultaneous(orbits: &[Orbit]) -> Result<u128, AocError> {
+    if orbits.is_empty() {
+        return Err(AocError::NoGhosts);
+    }
+
+    if let Some(step) = orbits
+        .iter()
+        .flat_map(|orbit| &orbit.transient_hits)
+        .map(|&hit| hit as u128)
+        .filter(|&hit| orbits.iter().all(|orbit| orbit.hits(hit)))
+        .min()
+    {
+        return Ok(step);
+    }
+
+    let start = orbits
+        .iter()
+        .map(|orbit| orbit.pre_period as u128)
+        .max()
+        .unwrap_or(0);
+    if let Some(step) =
+        (start..start + SCAN_STEPS).find(|&step| orbits.iter().all(|orbit| orbit.hits(step)))
+    {
+        return Ok(step);
+    }
+
+    // every combination of the hits so far, as residues modulo one modulus
+    let mut residues = vec![0_u128];
+    let mut modulus = 1_u128;
+    for orbit in orbits {
+        let period = orbit.period as u128;
+        let mut combined = Vec::new();
+        let mut combined_modulus = modulus;
+        for &residue in &residues {
+            for &hit in &orbit.cycle_hits {
+                if let Some((r, m)) = crt((residue, modulus), (hit as u128 % period, period))? {
+                    combined.push(r);
+                    combined_modulus = m;
+                }
+            }
+        }
+        combined.sort();
+        combined.dedup();
+        if combined.len() > MAX_COMBINATIONS {
+            return Err(AocError::TooManyCombinations);
+        }
+        residues = combined;
+        modulus = combined_modulus;
+    }
+
+    // the first step on or past every cycle's start with one of the residues
+    let steps = residues
+        .into_iter()
+        .map(|residue| {
+            let offset = start % modulus;
+            let behind = match residue.checked_sub(offset) {
+                Some(behind) => behind,
+                None => modulus - (offset - residue),
+            };
+            start.checked_add(behind).ok_or(AocError::TooManySteps)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    steps.into_iter().min().ok_or(AocError::NeverSimultaneous)
+}
+
+/// The `x` in `0..lcm(m, n)` with `x ≡ a (mod m)` and `x ≡ b (mod n)`,
+/// along with `lcm(m, n)`, if there's one.
+fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Result<Option<(u128, u128)>, AocError> {
+    let g = gcd(m, n);
+    let difference = (b + n - a % n) % n;
+    if difference % g != 0 {
+        return Ok(None);
+    }
+    let lcm = (m / g).checked_mul(n).ok_or(AocError::TooManySteps)?;
+
+    // x = a + m k, with m k ≡ b - a (mod n), so k ≡ (b - a) / g * (m / g)⁻¹
+    let n_g = n / g;
+    let k = if n_g == 1 {
+        0
+    } else {
+        (difference / g % n_g) * mod_inverse(m / g % n_g, n_g) % n_g
+    };
+    Ok(Some(((a % m + m * k) % lcm, lcm)))
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+/// Inverse of `a` modulo `m`, which must be coprime.
+fn mod_inverse(a: u128, m: u128) -> u128 {
+    let (mut old_r, mut r) = (a as i128, m as i128);
+    let (mut old_s, mut s) = (1_i128, 0_i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    old_s.rem_euclid(m as i128) as u128
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<Direction>, Graph), AocError> {
+    all_consuming(delimited(
+        multispace0,
+        tuple((parse_directions, preceded(multispace0, parse_graph))),
+        multispace0,
+    ))(input)
+    .map(|(_, parsed)| parsed)
+    .map_err(|e| AocError::ParseError(e.to_string()))
+}
+
+fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
+    many1(alt((
+        value(Direction::Left, char('L')),
+        value(Direction::Right, char('R')),
+    )))(input)
+}
+
+fn parse_graph(input: &str) -> IResult<&str, Graph> {
+    fold_many0(
+        terminated(parse_graph_line, multispace0),
+        Graph::default,
+        |mut graph, (key, value)| {
+            graph.insert(key, value);
+            graph
+        },
+    )(input)
+}
+
+fn parse_graph_line(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    key_value(
+        alphanumeric1,
+        delimited(
+            char('('),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            char(')'),
+        ),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orbit(pre_period: u64, period: u64, transient_hits: &[u64], cycle_hits: &[u64]) -> Orbit {
+        Orbit {
+            pre_period,
+            period,
+            transient_hits: transient_hits.to_vec(),
+            cycle_hits: cycle_hits.to_vec(),
+        }
+    }
+
+    /// Steps every orbit until they all hit, to check against.
+    fn brute_force(orbits: &[Orbit], limit: u128) -> Option<u128> {
+        (0..limit).find(|&step| orbits.iter().all(|orbit| orbit.hits(step)))
+    }
+
+    #[test]
+    fn orbits_of_the_example() -> miette::Result<()> {
+        let (directions, graph) = parse_input(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        )?;
+        let is_end = |name: &str| name.ends_with('Z');
+
+        assert_eq!(
+            orbit(1, 2, &[], &[2]),
+            graph.orbit(&directions, graph.id("11A")?, is_end)?
+        );
+        assert_eq!(
+            orbit(1, 6, &[], &[3, 6]),
+            graph.orbit(&directions, graph.id("22A")?, is_end)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cycles_that_dont_start_at_zero() -> miette::Result<()> {
+        for orbits in [
+            // the lcm of the first hits would be 15, but they meet at 11
+            vec![orbit(2, 4, &[], &[3]), orbit(0, 6, &[], &[5])],
+            vec![orbit(3, 5, &[1], &[4, 6]), orbit(0, 7, &[], &[1, 5])],
+            vec![orbit(10, 4, &[2, 7], &[11]), orbit(1, 6, &[], &[3, 5])],
+            // only the transient hit lines up
+            vec![orbit(5, 2, &[3], &[6]), orbit(0, 3, &[], &[0])],
+        ] {
+            assert_eq!(
+                brute_force(&orbits, 1_000),
+                Some(first_simultaneous(&orbits)?),
+                "{orbits:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn many_ways_to_line_up() -> miette::Result<()> {
+        // ghosts on cycles of coprime lengths, every other node an end, so
+        // the hits combine in millions of ways
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+        let orbit_for = |period: u64, offset: u64| {
+            let hits = (0..period)
+                .filter(|i| (i + offset).is_multiple_of(2))
+                .map(|i| i + 1)
+                .collect::<Vec<_>>();
+            orbit(1, period, &[], &hits)
+        };
+
+        let orbits = primes.map(|p| orbit_for(p, 0));
+        assert_eq!(1, first_simultaneous(&orbits)?);
+
+        // a ghost that only hits past the steps that get walked leaves just
+        // the combinations to try
+        let late = [orbit(0, 1 << 17, &[], &[(1 << 17) - 1])];
+        let orbits = primes.map(|p| orbit_for(p, 1)).into_iter().chain(late);
+        assert!(matches!(
+            first_simultaneous(&orbits.collect::<Vec<_>>()),
+            Err(AocError::TooManyCombinations)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn cycles_that_line_up_past_u128() {
+        // 274177 × 67280421310721 × (2⁶⁴ - 1) = u128::MAX, and the residue
+        // of 1 sits behind the last cycle's start
+        let orbits = [
+            orbit(0, 274_177, &[], &[1]),
+            orbit(0, u64::MAX, &[], &[1]),
+            orbit(1_000, 67_280_421_310_721, &[], &[67_280_421_310_722]),
+        ];
+        assert!(matches!(
+            first_simultaneous(&orbits),
+            Err(AocError::TooManySteps)
+        ));
+    }
+
+    #[test]
+    fn no_ghosts() {
+        assert!(matches!(first_simultaneous(&[]), Err(AocError::NoGhosts)));
+    }
+
+    #[test]
+    fn never_simultaneous() {
+        for orbits in [
+            vec![orbit(0, 4, &[], &[1]), orbit(0, 6, &[], &[2])],
+            vec![orbit(0, 2, &[], &[]), orbit(0, 1, &[], &[0])],
+        ] {
+            assert_eq!(None, brute_force(&orbits, 1_000));
+            assert!(matches!(
+                first_simultaneous(&orbits),
+                Err(AocError::NeverSimultaneous)
+            ));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let (directions, graph) = parse_input(input)?;
+    graph.steps_between(&directions, "AAA", "ZZZ")
+}
+
+#[cfg(test)]
+mod day_08_part1 {
+    use super::*;
+
+    #[test]
+    fn example1() -> miette::Result<()> {
+        let input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+        let output = process(input)?;
+        assert_eq!(output, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn example2() -> miette::Result<()> {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let output = process(input)?;
+        assert_eq!(output, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn zzz_out_of_reach() {
+        let input = "L
+
+AAA = (BBB, ZZZ)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert!(matches!(process(input), Err(AocError::Unreachable { .. })));
+    }
+
+    #[test]
+    fn node_map() -> miette::Result<()> {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let (_, graph) = parse_input(input)?;
+        let output = graph.to_dot().to_string();
+        assert!(output.contains(r#""AAA" [style="filled", fillcolor="lightgreen"]"#));
+        assert!(output.contains(r#""ZZZ" [style="filled", fillcolor="salmon"]"#));
+        assert!(output.contains(r#""BBB" -> "ZZZ" [label="R"]"#));
+        assert_eq!(6, output.matches(" -> ").count());
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 22357);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{custom_error::AocError, *};
+
+pub fn process(input: &str) -> miette::Result<u128, AocError> {
+    let (directions, graph) = parse_input(input)?;
+
+    // every ghost walks into a cycle, though not necessarily one that starts
+    // at its start node or hits an end node only once
+    let orbits = graph
+        .names
+        .iter()
+        .filter(|(_, name)| name.ends_with('A'))
+        .map(|(start, _)| graph.orbit(&directions, start, |name| name.ends_with('Z')))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    first_simultaneous(&orbits)
+}
+
+#[cfg(test)]
+mod day_08_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        let output = process(input)?;
+        assert_eq!(output, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn no_start_nodes() {
+        let input = "L
+
+BBB = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert!(matches!(process(input), Err(AocError::NoGhosts)));
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 10371555451871);
+        Ok(())
+    }
+}
@@ -1,3 +1,17 @@
+use nom::{
+    character::complete::{self, line_ending, multispace0, space1},
+    combinator::{all_consuming, map},
+    multi::separated_list1,
+    sequence::delimited,
+};
+use num::{BigInt, BigRational, One, Zero};
+
+use crate::custom_error::AocError;
+
+pub mod custom_error;
+pub mod part1;
+pub mod part2;
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Reading {
     values: Vec<i64>,
@@ -8,78 +22,185 @@ impl Reading {
         Reading { values }
     }
 
-    fn get_interpolations(&self) -> Vec<Vec<i64>> {
-        let mut interpolations: Vec<Vec<i64>> = vec![];
-
-        let mut current = self
+    /// The polynomial of lowest degree through every value, taking the first
+    /// one to be at `x = 0`.
+    ///
+    /// Only the first entry of each row of the difference table is kept, which
+    /// is all Newton's forward difference formula needs. A row of zeros has to
+    /// turn up before the table runs out, otherwise nothing shows the values
+    /// aren't just the start of something else. So a lone `7` is an error
+    /// now, where the old in-place interpolation read it as the constant 7.
+    pub fn polynomial(&self) -> Result<Polynomial, AocError> {
+        let mut row = self
             .values
-            .windows(2)
-            .map(|w| {
-                let (a, b) = (w[0], w[1]);
-                b - a
-            })
-            .collect::<Vec<i64>>();
-
-        loop {
-            interpolations.push(current.clone());
-            current = current
-                .windows(2)
-                .map(|w| {
-                    let (a, b) = (w[0], w[1]);
-                    b - a
-                })
-                .collect::<Vec<i64>>();
-
-            if current.iter().all(|n| n == &0) {
-                current.push(0);
-                interpolations.push(current.clone());
-                break;
+            .iter()
+            .map(|&v| BigInt::from(v))
+            .collect::<Vec<_>>();
+        let mut differences = Vec::new();
+
+        while !row.iter().all(Zero::is_zero) {
+            if row.len() == 1 {
+                return Err(AocError::NotPolynomial(self.values.clone()));
             }
+            differences.push(row[0].clone());
+            row = row.windows(2).map(|w| &w[1] - &w[0]).collect();
+        }
+
+        Ok(Polynomial { differences })
+    }
+
+    /// The value `steps` past the last one.
+    pub fn extrapolate_forward(&self, steps: usize) -> Result<BigInt, AocError> {
+        let x = self.values.len() as i64 - 1 + steps as i64;
+        Ok(self.polynomial()?.value_at(x))
+    }
+
+    /// The value `steps` before the first one.
+    pub fn extrapolate_backward(&self, steps: usize) -> Result<BigInt, AocError> {
+        Ok(self.polynomial()?.value_at(-(steps as i64)))
+    }
+}
+
+/// A polynomial in Newton form, `sum(Δᵏ C(x, k))` over its forward
+/// differences `Δᵏ` at `x = 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    differences: Vec<BigInt>,
+}
+
+impl Polynomial {
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// Exact anywhere, as `C(x, k)` is an integer even for negative `x`.
+    pub fn value_at(&self, x: i64) -> BigInt {
+        let x = BigInt::from(x);
+        let mut binomial = BigInt::one();
+        let mut value = BigInt::zero();
+
+        for (k, difference) in self.differences.iter().enumerate() {
+            value += difference * &binomial;
+            // C(x, k + 1) = C(x, k) (x - k) / (k + 1), which divides exactly
+            binomial = binomial * (&x - k) / (k + 1);
         }
-        interpolations
+        value
     }
 
-    pub fn interpolate_next(&mut self) -> i64 {
-        let mut interpolations = self.get_interpolations();
-        interpolations.reverse();
-
-        let mut prev: i64 = 0;
-        for (idx, interpolation) in interpolations.iter_mut().enumerate() {
-            match idx {
-                0 => {
-                    interpolation.push(prev);
-                }
-                _ => {
-                    prev = prev + interpolation.last().unwrap();
-                    interpolation.push(prev);
-                }
+    /// Coefficients in the power basis, the constant term first.
+    pub fn coefficients(&self) -> Vec<BigRational> {
+        let mut coefficients = vec![BigRational::zero(); self.differences.len()];
+        // x (x - 1) ... (x - k + 1), the constant term first
+        let mut falling = vec![BigInt::one()];
+        let mut factorial = BigInt::one();
+
+        for (k, difference) in self.differences.iter().enumerate() {
+            for (coefficient, term) in coefficients.iter_mut().zip(&falling) {
+                *coefficient += BigRational::new(difference * term, factorial.clone());
+            }
+
+            let mut next = vec![BigInt::zero(); falling.len() + 1];
+            for (i, term) in falling.iter().enumerate() {
+                next[i + 1] += term;
+                next[i] -= term * k;
             }
+            falling = next;
+            factorial *= k + 1;
         }
+        coefficients
+    }
+}
 
-        let next = prev + self.values.last().unwrap();
-        self.values.push(next);
-        next
+pub fn parse_readings(input: &str) -> Result<Vec<Reading>, AocError> {
+    all_consuming(delimited(
+        multispace0,
+        separated_list1(
+            line_ending,
+            map(separated_list1(space1, complete::i64), Reading::new),
+        ),
+        multispace0,
+    ))(input)
+    .map(|(_, readings)| readings)
+    .map_err(|e: nom::Err<nom::error::Error<&str>>| AocError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn polynomial(coefficients: &[i64], x: i64) -> i64 {
+        coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
     }
 
-    pub fn interpolate_prev(&mut self) -> i64 {
-        let mut interpolations = self.get_interpolations();
-        interpolations.reverse();
-
-        let mut prev: i64 = 0;
-        for (idx, interpolation) in interpolations.iter_mut().enumerate() {
-            match idx {
-                0 => {
-                    interpolation.insert(0, prev);
-                }
-                _ => {
-                    prev = interpolation.first().unwrap() - prev;
-                    interpolation.insert(0, prev);
-                }
+    proptest! {
+        #[test]
+        fn extrapolates_polynomials(
+            coefficients in prop::collection::vec(-20i64..20, 1..6),
+            extra in 2usize..8,
+        ) {
+            // one more reading than the degree is enough to pin the polynomial
+            // down, plus at least one to find the differences
+            let len = coefficients.len() + extra;
+            let values = (0..len as i64).map(|x| polynomial(&coefficients, x)).collect();
+            let reading = Reading::new(values);
+
+            prop_assert_eq!(
+                reading.extrapolate_forward(1)?,
+                polynomial(&coefficients, len as i64).into()
+            );
+            prop_assert_eq!(
+                reading.extrapolate_backward(3)?,
+                polynomial(&coefficients, -3).into()
+            );
+
+            let mut expected = coefficients
+                .iter()
+                .map(|&c| BigRational::from_integer(c.into()))
+                .collect::<Vec<_>>();
+            while expected.last().is_some_and(Zero::is_zero) {
+                expected.pop();
             }
+            prop_assert_eq!(reading.polynomial()?.coefficients(), expected);
         }
+    }
+
+    #[test]
+    fn rational_coefficients() -> miette::Result<()> {
+        // triangular numbers, x (x + 1) / 2
+        let fitted = Reading::new(vec![0, 1, 3, 6, 10]).polynomial()?;
+        let half = BigRational::new(1.into(), 2.into());
+
+        assert_eq!(Some(2), fitted.degree());
+        assert_eq!(
+            vec![BigRational::zero(), half.clone(), half],
+            fitted.coefficients()
+        );
+        assert_eq!(BigInt::from(5050), fitted.value_at(100));
+        Ok(())
+    }
 
-        let next = self.values.first().unwrap() - prev;
-        self.values.insert(0, next);
-        next
+    #[test]
+    fn past_i64() -> miette::Result<()> {
+        let reading = Reading::new((0..30_i64).map(|x| x.pow(12)).collect());
+
+        assert_eq!(Some(12), reading.polynomial()?.degree());
+        assert_eq!(BigInt::from(49).pow(12), reading.extrapolate_forward(20)?);
+        assert_eq!(BigInt::from(-70).pow(12), reading.extrapolate_backward(70)?);
+        Ok(())
+    }
+
+    #[test]
+    fn not_polynomial() -> miette::Result<()> {
+        for values in [vec![1, 2, 4, 8, 16], vec![7], vec![0, 0, 1]] {
+            assert!(matches!(
+                Reading::new(values).polynomial(),
+                Err(AocError::NotPolynomial(_))
+            ));
+        }
+        assert_eq!(None, Reading::new(vec![0, 0]).polynomial()?.degree());
+        assert_eq!(Some(0), Reading::new(vec![7, 7]).polynomial()?.degree());
+        Ok(())
     }
 }
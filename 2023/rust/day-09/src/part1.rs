@@ -0,0 +1,40 @@
+use crate::{custom_error::AocError, *};
+use num::BigInt;
+
+pub fn process(input: &str) -> miette::Result<BigInt, AocError> {
+    parse_readings(input)?
+        .iter()
+        .map(|reading| reading.extrapolate_forward(1))
+        .sum()
+}
+
+#[cfg(test)]
+mod day_09_part1 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+        let output = process(input)?;
+        assert_eq!(output, 114.into());
+        Ok(())
+    }
+
+    #[test]
+    fn single_value_reading() {
+        // one value can't show it's polynomial, so this no longer reads as 7
+        let input = "0 3 6 9 12 15
+7";
+        assert!(matches!(process(input), Err(AocError::NotPolynomial(_))));
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 1637452029.into());
+        Ok(())
+    }
+}
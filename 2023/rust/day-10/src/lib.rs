@@ -1,6 +1,12 @@
+pub use aoc_common::direction::Direction;
 use glam::IVec2;
 use nom_locate::LocatedSpan;
 
+pub mod custom_error;
+
+pub mod part1;
+pub mod part2;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum PipeType {
     Vertical,
@@ -146,14 +152,6 @@ impl PipeType {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
 #[derive(Debug)]
 pub struct PipeInfo<'a> {
     pub span: SpanIVec2<'a>,
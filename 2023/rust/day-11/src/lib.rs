@@ -1,6 +1,11 @@
 use glam::IVec2;
 use nom_locate::LocatedSpan;
 
+pub mod custom_error;
+
+pub mod part1;
+pub mod part2;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum SpaceType {
     Galaxy,
@@ -65,9 +65,9 @@ impl DFA {
         DFA { states }
     }
 
-    fn count(&self, pattern: &String) -> usize {
+    fn count(&self, pattern: &String) -> u64 {
         let mut curr = HashMap::new();
-        curr.insert(&self.states[0], 1usize);
+        curr.insert(&self.states[0], 1u64);
 
         for c in pattern.chars() {
             let mut next = HashMap::with_capacity(self.states.len());
@@ -126,8 +126,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
             let (pattern, damaged_pattern) = record;
             let dfa = DFA::new(damaged_pattern);
             debug!(?pattern, ?damaged_pattern, ?dfa);
-            let count = dfa.count(pattern);
-            count as u64
+            dfa.count(pattern)
         })
         .sum::<u64>();
 
@@ -1,5 +1,10 @@
 use std::fmt;
 
+pub mod custom_error;
+
+pub mod part1;
+pub mod part2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Condition {
     Operational,
@@ -97,3 +102,38 @@ impl fmt::Display for SpringsLine {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn condition() -> impl Strategy<Value = Condition> {
+        prop_oneof![
+            Just(Condition::Operational),
+            Just(Condition::Damaged),
+            Just(Condition::Unknown),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn condition_round_trip(condition in condition()) {
+            let line = SpringsLine::from_string(condition.to_string());
+            prop_assert_eq!(line.conditions, vec![condition]);
+        }
+
+        #[test]
+        fn springs_line_round_trip(conditions in prop::collection::vec(condition(), 0..40)) {
+            let line = SpringsLine::new(conditions);
+            prop_assert_eq!(SpringsLine::from_string(line.to_string()), line);
+        }
+
+        #[test]
+        fn damaged_groups_add_up(conditions in prop::collection::vec(condition(), 0..40)) {
+            let line = SpringsLine::new(conditions);
+            let damaged = line.to_string().matches('#').count() as u64;
+            prop_assert_eq!(line.count_damaged_group_lengths().iter().sum::<u64>(), damaged);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{custom_error::AocError, *};
+
+use itertools::Itertools;
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::{self, multispace0, space1},
+    combinator::all_consuming,
+    multi::{many1, separated_list1},
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+
+fn parse_spring_condition_records(input: &str) -> IResult<&str, Vec<(SpringsLine, Vec<u64>)>> {
+    let (input, output) = all_consuming(many1(terminated(
+        separated_pair(
+            is_a("#.?").map(|conditions| {
+                SpringsLine::from_string(std::iter::repeat(conditions).take(5).join("?"))
+            }),
+            space1,
+            separated_list1(tag(","), complete::u64)
+                .map(|groups| std::iter::repeat(groups).take(5).flatten().collect()),
+        ),
+        multispace0,
+    )))(input)?;
+
+    Ok((input, output))
+}
+
+fn calculate_arrangements(springs_line: &SpringsLine, groups: &Vec<u64>) -> u64 {
+    let missing_conditions = springs_line.count_missing();
+
+    // println!(
+    //     "==> processing '{}', which has groups {:?} and {} missing spring conditions",
+    //     springs_line, groups, missing_conditions
+    // );
+
+    let permutations = itertools::repeat_n(
+        [Condition::Damaged, Condition::Operational].iter(),
+        missing_conditions,
+    )
+    .multi_cartesian_product();
+
+    permutations
+        .filter(|permutation| {
+            // println!("permutation: {:?}", permutation);
+            let candidate = SpringsLine::from_missing_permutation(&springs_line, permutation);
+            let candidate_groups = candidate.count_damaged_group_lengths();
+            let is_valid = candidate_groups == *groups;
+            // println!(
+            //     "candidate: '{}' with groups {:?}, valid={}",
+            //     candidate, candidate_groups, is_valid
+            // );
+            is_valid
+        })
+        .count() as u64
+}
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let (_, records) = parse_spring_condition_records(input).expect("should parse a space grid");
+
+    Ok(records
+        .iter()
+        .map(|(conditions, damaged_springs)| {
+            let valid_arrangements = calculate_arrangements(conditions, damaged_springs);
+            // println!(
+            //     "'{}' has {} valid arrangements",
+            //     conditions, valid_arrangements
+            // );
+            valid_arrangements
+        })
+        .sum())
+}
+
+#[cfg(test)]
+mod day_12_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+        let output = process(input)?;
+        assert_eq!(output, 21);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 7191);
+        Ok(())
+    }
+}
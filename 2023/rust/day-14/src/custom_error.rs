@@ -0,0 +1,13 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("export failed: {0}")]
+    #[diagnostic(code(aoc::export_error))]
+    ExportError(String),
+}
@@ -0,0 +1,4 @@
+pub mod custom_error;
+
+pub mod part1;
+pub mod part2;
@@ -0,0 +1,424 @@
+use crate::custom_error::AocError;
+#[cfg(feature = "export")]
+use aoc_common::frame::{Cell, Color, Frame};
+#[cfg(feature = "export")]
+use glam::IVec2;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+use itertools::Itertools;
+
+const TOTAL_CYCLES: u64 = 1_000_000_000;
+
+fn move_rocks_north(rocks: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+    // Transpose the rows into columns
+    let mut columns_iter_collection = rocks.iter().map(|row| row.iter()).collect::<Vec<_>>();
+    let colums = std::iter::from_fn(move || {
+        let mut items = vec![];
+        for iter in &mut columns_iter_collection {
+            match iter.next() {
+                Some(item) => items.push(item.clone()),
+                None => return None,
+            }
+        }
+        Some(items)
+    })
+    .collect::<Vec<Vec<char>>>();
+
+    // Move the rocks
+    let colums: Vec<Vec<char>> = colums
+        .iter()
+        .map(|col| {
+            let mut intervals: BTreeMap<Option<usize>, usize> = BTreeMap::new();
+            let mut interval_start = None;
+            intervals.insert(interval_start, 0);
+
+            for (idx, rock) in col.iter().enumerate() {
+                match rock {
+                    '#' => {
+                        interval_start = Some(idx);
+                        intervals.insert(interval_start, 0);
+                    }
+                    'O' => {
+                        intervals.entry(interval_start).and_modify(|val| *val += 1);
+                    }
+                    '.' => {}
+                    _ => unreachable!(),
+                }
+            }
+            (col.len(), intervals)
+        })
+        .map(|(col_len, intervals)| {
+            let mut result = vec![];
+            let mut idx = 0;
+            for (start, rock_count) in intervals {
+                match start {
+                    None => {
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = rock_count;
+                    }
+                    Some(start) => {
+                        result.append(vec!['.'; start - idx].as_mut());
+                        result.push('#');
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = start + rock_count + 1;
+                    }
+                }
+            }
+            result.append(vec!['.'; col_len - idx].as_mut());
+            result
+        })
+        .collect();
+
+    // Transpose the columns into rows
+    let mut rows_iter_collection = colums.iter().map(|col| col.iter()).collect::<Vec<_>>();
+    let rows = std::iter::from_fn(move || {
+        let mut items = vec![];
+        for iter in &mut rows_iter_collection {
+            match iter.next() {
+                Some(item) => items.push(item.clone()),
+                None => return None,
+            }
+        }
+        Some(items)
+    })
+    .collect::<Vec<Vec<char>>>();
+
+    rows
+}
+
+fn move_rocks_south(rocks: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+    // Transpose the rows into reversed columns
+    let mut columns_iter_collection = rocks
+        .iter()
+        .map(|row| row.iter().rev())
+        .rev()
+        .collect::<Vec<_>>();
+    let colums = std::iter::from_fn(move || {
+        let mut items = vec![];
+        for iter in &mut columns_iter_collection {
+            match iter.next() {
+                Some(item) => items.push(item.clone()),
+                None => return None,
+            }
+        }
+        Some(items)
+    })
+    .collect::<Vec<Vec<char>>>();
+
+    // Move the rocks
+    let colums: Vec<Vec<char>> = colums
+        .iter()
+        .map(|col| {
+            let mut intervals: BTreeMap<Option<usize>, usize> = BTreeMap::new();
+            let mut interval_start = None;
+            intervals.insert(interval_start, 0);
+
+            for (idx, rock) in col.iter().enumerate() {
+                match rock {
+                    '#' => {
+                        interval_start = Some(idx);
+                        intervals.insert(interval_start, 0);
+                    }
+                    'O' => {
+                        intervals.entry(interval_start).and_modify(|val| *val += 1);
+                    }
+                    '.' => {}
+                    _ => unreachable!(),
+                }
+            }
+            (col.len(), intervals)
+        })
+        .map(|(col_len, intervals)| {
+            let mut result = vec![];
+            let mut idx = 0;
+            for (start, rock_count) in intervals {
+                match start {
+                    None => {
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = rock_count;
+                    }
+                    Some(start) => {
+                        result.append(vec!['.'; start - idx].as_mut());
+                        result.push('#');
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = start + rock_count + 1;
+                    }
+                }
+            }
+            result.append(vec!['.'; col_len - idx].as_mut());
+            result
+        })
+        .collect();
+
+    // Transpose the columns into reversed rows
+    let mut rows_iter_collection = colums
+        .iter()
+        .map(|col| col.iter().rev())
+        .rev()
+        .collect::<Vec<_>>();
+    let rows = std::iter::from_fn(move || {
+        let mut items = vec![];
+        for iter in &mut rows_iter_collection {
+            match iter.next() {
+                Some(item) => items.push(item.clone()),
+                None => return None,
+            }
+        }
+        Some(items)
+    })
+    .collect::<Vec<Vec<char>>>();
+
+    rows
+}
+
+fn move_rocks_west(rocks: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+    // Clone the rocks
+    let rows = rocks
+        .iter()
+        .map(|row| row.iter().cloned().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Move the rocks
+    let rows: Vec<Vec<char>> = rows
+        .iter()
+        .map(|col| {
+            let mut intervals: BTreeMap<Option<usize>, usize> = BTreeMap::new();
+            let mut interval_start = None;
+            intervals.insert(interval_start, 0);
+
+            for (idx, rock) in col.iter().enumerate() {
+                match rock {
+                    '#' => {
+                        interval_start = Some(idx);
+                        intervals.insert(interval_start, 0);
+                    }
+                    'O' => {
+                        intervals.entry(interval_start).and_modify(|val| *val += 1);
+                    }
+                    '.' => {}
+                    _ => unreachable!(),
+                }
+            }
+            (col.len(), intervals)
+        })
+        .map(|(col_len, intervals)| {
+            let mut result = vec![];
+            let mut idx = 0;
+            for (start, rock_count) in intervals {
+                match start {
+                    None => {
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = rock_count;
+                    }
+                    Some(start) => {
+                        result.append(vec!['.'; start - idx].as_mut());
+                        result.push('#');
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = start + rock_count + 1;
+                    }
+                }
+            }
+            result.append(vec!['.'; col_len - idx].as_mut());
+            result
+        })
+        .collect();
+
+    // Clone the rocks
+    rows.iter()
+        .map(|row| row.iter().cloned().collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+}
+
+fn move_rocks_east(rocks: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+    // Reverse the rows
+    let rows = rocks
+        .iter()
+        .map(|row| row.iter().cloned().rev().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Move the rocks
+    let rows: Vec<Vec<char>> = rows
+        .iter()
+        .map(|col| {
+            let mut intervals: BTreeMap<Option<usize>, usize> = BTreeMap::new();
+            let mut interval_start = None;
+            intervals.insert(interval_start, 0);
+
+            for (idx, rock) in col.iter().enumerate() {
+                match rock {
+                    '#' => {
+                        interval_start = Some(idx);
+                        intervals.insert(interval_start, 0);
+                    }
+                    'O' => {
+                        intervals.entry(interval_start).and_modify(|val| *val += 1);
+                    }
+                    '.' => {}
+                    _ => unreachable!(),
+                }
+            }
+            (col.len(), intervals)
+        })
+        .map(|(col_len, intervals)| {
+            let mut result = vec![];
+            let mut idx = 0;
+            for (start, rock_count) in intervals {
+                match start {
+                    None => {
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = rock_count;
+                    }
+                    Some(start) => {
+                        result.append(vec!['.'; start - idx].as_mut());
+                        result.push('#');
+                        result.append(vec!['O'; rock_count].as_mut());
+                        idx = start + rock_count + 1;
+                    }
+                }
+            }
+            result.append(vec!['.'; col_len - idx].as_mut());
+            result
+        })
+        .collect();
+
+    // Reverse the rows
+    rows.iter()
+        .map(|row| row.iter().cloned().rev().collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+}
+
+fn compute_load_north_beam(rocks: Vec<Vec<char>>) -> u64 {
+    let row_count = rocks.len();
+    rocks
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            (row.iter().filter(|rock| *rock == &'O').count() * (row_count - idx)) as u64
+        })
+        .sum()
+}
+
+fn hash_rocks(rocks: &Vec<Vec<char>>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(
+        rocks
+            .iter()
+            .map(|row| row.iter().join(""))
+            .join("")
+            .as_bytes(),
+    );
+    let sha256digest = hasher.finalize();
+    format!("{:x}", sha256digest)
+}
+
+#[cfg(feature = "export")]
+struct RocksFrame<'a> {
+    rocks: &'a [Vec<char>],
+    cycle: u64,
+}
+
+#[cfg(feature = "export")]
+impl Frame for RocksFrame<'_> {
+    fn size(&self) -> IVec2 {
+        IVec2::new(self.rocks[0].len() as i32, self.rocks.len() as i32)
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        match self.rocks[position.y as usize][position.x as usize] {
+            'O' => Cell::new('O', Color::Yellow),
+            '#' => Cell::new('#', Color::DarkGrey),
+            rock => rock.into(),
+        }
+    }
+
+    fn caption(&self) -> String {
+        format!("cycle {}", self.cycle)
+    }
+}
+
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let mut rocks = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut cache = HashMap::new();
+    let mut end_cycle = None;
+
+    #[cfg(feature = "export")]
+    let mut exporter = aoc_common::export::Exporter::from_env()
+        .map_err(|e| AocError::ExportError(e.to_string()))?;
+
+    for cycle in 0..TOTAL_CYCLES {
+        for tilt in [
+            move_rocks_north,
+            move_rocks_west,
+            move_rocks_south,
+            move_rocks_east,
+        ] {
+            rocks = tilt(&rocks);
+
+            #[cfg(feature = "export")]
+            if let Some(exporter) = &mut exporter {
+                let frame = RocksFrame {
+                    rocks: &rocks,
+                    cycle: cycle + 1,
+                };
+                exporter
+                    .push("day-14-part2", &frame)
+                    .map_err(|e| AocError::ExportError(e.to_string()))?;
+            }
+        }
+
+        let hash = hash_rocks(&rocks);
+        match cache.get(&hash) {
+            Some(past_cycle) => {
+                let period = cycle - past_cycle;
+
+                if end_cycle.is_none() {
+                    let extra_cycles =
+                        (TOTAL_CYCLES - past_cycle) - (TOTAL_CYCLES - past_cycle) / period * period;
+                    end_cycle = Some(cycle + extra_cycles - 1);
+                }
+            }
+            None => {
+                cache.insert(hash, cycle);
+            }
+        }
+        if end_cycle.is_some_and(|end_cycle| cycle == end_cycle) {
+            break;
+        }
+    }
+    Ok(compute_load_north_beam(rocks))
+}
+
+#[cfg(test)]
+mod day_14_part2 {
+    use super::*;
+
+    #[test]
+    fn example() -> miette::Result<()> {
+        let input = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+        let output = process(input)?;
+        assert_eq!(output, 64);
+        Ok(())
+    }
+
+    #[test]
+    fn input1() -> miette::Result<()> {
+        let input = include_str!("../inputs/input1.txt");
+        let output = process(input)?;
+        assert_eq!(output, 100876);
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 use crate::custom_error::AocError;
 
+/// The HASH algorithm, reduced modulo 256 after every character as the
+/// puzzle describes. Reducing only at the end would overflow the `u64` on
+/// steps longer than about a dozen characters.
 fn hash(input: &str) -> u8 {
     input
         .trim()
         .chars()
-        .fold(0u64, |acc, c| (acc + c as u64) * 17u64) as u8
+        .fold(0u64, |acc, c| (acc + c as u64) * 17u64 % 256) as u8
 }
 
 #[tracing::instrument(skip(input))]
@@ -24,6 +27,22 @@ mod day_15_part1 {
         Ok(())
     }
 
+    #[test]
+    fn long_steps() {
+        assert_eq!(52, hash("HASH"));
+        // used to overflow before being reduced to a byte
+        assert_eq!(148, hash(&format!("{}=3", "qp".repeat(20))));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn hash_one_char_at_a_time(step in "[a-z]{0,100}[=-][0-9]?", c in proptest::char::range('!', '~')) {
+            let extended = format!("{step}{c}");
+            let expected = (hash(&step) as u32 + c as u32) * 17 % 256;
+            proptest::prop_assert_eq!(hash(&extended) as u32, expected);
+        }
+    }
+
     #[test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
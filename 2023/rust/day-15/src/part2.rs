@@ -4,11 +4,12 @@ use crate::custom_error::AocError;
 use tracing::{debug, info};
 
 use nom::{
-    bytes::complete::{is_a, tag},
+    branch::alt,
+    bytes::complete::tag,
     character::complete::{self, alpha1, multispace0},
-    combinator::{all_consuming, opt},
+    combinator::all_consuming,
     multi::separated_list1,
-    sequence::{terminated, tuple},
+    sequence::{preceded, terminated},
     IResult, Parser,
 };
 
@@ -49,28 +50,37 @@ impl LensBox {
     }
 }
 
+/// The HASH algorithm, reduced modulo 256 after every character as the
+/// puzzle describes. Reducing only at the end would overflow the `u64` on
+/// steps longer than about a dozen characters.
 fn hash(input: &str) -> u8 {
     input
         .trim()
         .chars()
-        .fold(0u64, |acc, c| (acc + c as u64) * 17u64) as u8
+        .fold(0u64, |acc, c| (acc + c as u64) * 17u64 % 256) as u8
 }
 
-fn parse_operations(input: &str) -> IResult<&str, Vec<Operation>> {
+fn parse_operations(input: &str) -> IResult<&str, Vec<Operation<'_>>> {
     let (input, output) = all_consuming(terminated(
         separated_list1(
             tag(","),
-            tuple((alpha1, is_a("=-"), opt(complete::u8))).map(|(label, op_type, focal_length)| {
-                match op_type {
-                    "=" => Operation::Upsert {
+            alpha1.and(alt((
+                preceded(tag("="), complete::u8).map(Some),
+                tag("-").map(|_| None),
+            ))),
+        )
+        .map(|operations| {
+            operations
+                .into_iter()
+                .map(|(label, focal_length)| match focal_length {
+                    Some(focal_length) => Operation::Upsert {
                         label,
-                        focal_length: focal_length.unwrap(),
+                        focal_length,
                     },
-                    "-" => Operation::Remove { label },
-                    _ => panic!("unexpected operation type"),
-                }
-            }),
-        ),
+                    None => Operation::Remove { label },
+                })
+                .collect()
+        }),
         multispace0,
     ))(input)?;
 
@@ -79,7 +89,8 @@ fn parse_operations(input: &str) -> IResult<&str, Vec<Operation>> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, init_sequence) = parse_operations(input).expect("a valid init sequence parse");
+    let (_, init_sequence) =
+        parse_operations(input).map_err(|e| AocError::ParseError(e.to_string()))?;
     let mut boxes: BTreeMap<u8, LensBox> = BTreeMap::new();
 
     for op in init_sequence {
@@ -147,6 +158,42 @@ mod day_15_part2 {
         Ok(())
     }
 
+    #[test]
+    fn malformed_steps() {
+        for input in ["ab=", "ab=-", "ab=256", "ab", "ab-,"] {
+            assert!(
+                matches!(process(input), Err(AocError::ParseError(_))),
+                "{input:?}"
+            );
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn upsert_keeps_insertion_order(
+            upserts in proptest::collection::vec(("[a-e]{1,2}", 1u8..10), 0..30),
+        ) {
+            let mut lens_box = LensBox::new();
+            for (label, focal_length) in upserts.iter() {
+                lens_box.upsert(label, *focal_length);
+            }
+
+            let mut first_seen: Vec<&str> = vec![];
+            for (label, _) in upserts.iter() {
+                if !first_seen.contains(&label.as_str()) {
+                    first_seen.push(label);
+                }
+            }
+            let labels = lens_box.lenses.iter().map(|lens| lens.label.as_str()).collect::<Vec<_>>();
+            proptest::prop_assert_eq!(&labels, &first_seen);
+
+            for lens in lens_box.lenses.iter() {
+                let (_, last) = upserts.iter().rev().find(|(label, _)| *label == lens.label).unwrap();
+                proptest::prop_assert_eq!(lens.focal_length, *last);
+            }
+        }
+    }
+
     #[test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
@@ -1,22 +1,16 @@
 use crate::custom_error::AocError;
 use crate::nom_locate_utils::*;
 
+use aoc_common::direction::{Direction, DirectionSet};
+#[cfg(any(test, feature = "visualize"))]
+use aoc_common::frame::{Cell, Color, Frame};
 use glam::IVec2;
-use itertools::Itertools;
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::multispace0, combinator::all_consuming,
     multi::many1, sequence::terminated, IResult, Parser,
 };
-use std::collections::{HashMap, HashSet};
-use tracing::{debug, info};
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+use std::collections::HashMap;
+use tracing::debug;
 
 #[derive(Debug, Eq, PartialEq)]
 enum TileType {
@@ -33,7 +27,7 @@ struct TileInfo<'a> {
     pub space_type: TileType,
 }
 
-fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, TileType>> {
+fn parse_grid(input: Span<'_>) -> IResult<Span<'_>, HashMap<IVec2, TileType>> {
     let (input, output) = all_consuming(many1(terminated(
         alt((
             tag(".").map(with_xy).map(|span| TileInfo {
@@ -69,54 +63,66 @@ fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, TileType>> {
     ))
 }
 
-#[allow(dead_code)]
-fn display_light_bounces(
-    input: &str,
-    grid: &HashMap<IVec2, TileType>,
-    energized: &HashMap<IVec2, HashSet<Direction>>,
-) {
-    let rows = input.lines().count();
-    let cols = input.lines().next().unwrap().chars().count();
-    let mut energized_grid = vec![vec!['.'; cols]; rows];
-    for (pos, tile_type) in grid.iter() {
-        let c = match energized.get(pos) {
-            Some(directions) if grid.get(pos).unwrap() == &TileType::Empty => {
-                if directions.len() > 1 {
-                    directions.len().to_string().chars().next().unwrap()
-                } else {
-                    match directions.iter().next().unwrap() {
-                        Direction::Up => '^',
-                        Direction::Down => 'v',
-                        Direction::Left => '<',
-                        Direction::Right => '>',
-                    }
+#[cfg(any(test, feature = "visualize"))]
+struct LightFrame<'a> {
+    grid: &'a HashMap<IVec2, TileType>,
+    energized: &'a HashMap<IVec2, DirectionSet>,
+    size: IVec2,
+}
+
+#[cfg(any(test, feature = "visualize"))]
+impl<'a> LightFrame<'a> {
+    fn new(
+        grid: &'a HashMap<IVec2, TileType>,
+        energized: &'a HashMap<IVec2, DirectionSet>,
+    ) -> Self {
+        LightFrame {
+            grid,
+            energized,
+            size: grid.keys().copied().fold(IVec2::ZERO, IVec2::max) + IVec2::ONE,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "visualize"))]
+impl Frame for LightFrame<'_> {
+    fn size(&self) -> IVec2 {
+        self.size
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        let directions = self.energized.get(&position);
+        let glyph = match self.grid.get(&position) {
+            Some(TileType::Empty) => match directions {
+                Some(directions) if directions.len() > 1 => {
+                    char::from_digit(directions.len() as u32, 10).unwrap()
                 }
-            }
-            Some(_) | None => match tile_type {
-                TileType::Empty => '.',
-                TileType::ForwardMirror => '/',
-                TileType::BackwardMirror => '\\',
-                TileType::HorizontalSplitter => '-',
-                TileType::VerticalSplitter => '|',
+                Some(directions) => directions.iter().next().unwrap().glyph(),
+                None => '.',
             },
+            Some(TileType::ForwardMirror) => '/',
+            Some(TileType::BackwardMirror) => '\\',
+            Some(TileType::HorizontalSplitter) => '-',
+            Some(TileType::VerticalSplitter) => '|',
+            None => ' ',
         };
-        energized_grid[pos.y as usize][pos.x as usize] = c
+
+        match directions {
+            Some(_) => Cell::new(glyph, Color::Yellow),
+            None => Cell::new(glyph, Color::DarkGrey),
+        }
     }
 
-    info!(
-        "Light bounces\n{}",
-        energized_grid
-            .iter()
-            .map(|row| row.iter().join(""))
-            .join("\n")
-    );
+    fn caption(&self) -> String {
+        format!("{} tiles energized", self.energized.len())
+    }
 }
 
 fn energized_tiles(
     start: (IVec2, Direction),
     grid: &HashMap<IVec2, TileType>,
-) -> HashMap<IVec2, HashSet<Direction>> {
-    let mut energized: HashMap<IVec2, HashSet<Direction>> = HashMap::new();
+) -> HashMap<IVec2, DirectionSet> {
+    let mut energized: HashMap<IVec2, DirectionSet> = HashMap::new();
     let mut stack = vec![start];
 
     while let Some((tile_pos, direction)) = stack.pop() {
@@ -126,57 +132,27 @@ fn energized_tiles(
         };
         debug!(tile = ?tile_type, pos = %tile_pos, direction = ?direction, "checking");
 
-        match energized.get_mut(&tile_pos) {
-            Some(directions) => {
-                if directions.contains(&direction) {
-                    continue;
-                }
-                directions.insert(direction.clone());
-            }
-            None => {
-                let mut directions = HashSet::new();
-                directions.insert(direction.clone());
-                energized.insert(tile_pos, directions);
-            }
+        if !energized.entry(tile_pos).or_default().insert(direction) {
+            continue;
         }
 
-        let up = tile_pos + IVec2::new(0, -1);
-        let down = tile_pos + IVec2::new(0, 1);
-        let left = tile_pos + IVec2::new(-1, 0);
-        let right = tile_pos + IVec2::new(1, 0);
-
-        match (tile_type, direction) {
-            (TileType::Empty, Direction::Up) => stack.push((up, Direction::Up)),
-            (TileType::Empty, Direction::Down) => stack.push((down, Direction::Down)),
-            (TileType::Empty, Direction::Left) => stack.push((left, Direction::Left)),
-            (TileType::Empty, Direction::Right) => stack.push((right, Direction::Right)),
-
-            (TileType::ForwardMirror, Direction::Up) => stack.push((right, Direction::Right)),
-            (TileType::ForwardMirror, Direction::Down) => stack.push((left, Direction::Left)),
-            (TileType::ForwardMirror, Direction::Left) => stack.push((down, Direction::Down)),
-            (TileType::ForwardMirror, Direction::Right) => stack.push((up, Direction::Up)),
-
-            (TileType::BackwardMirror, Direction::Up) => stack.push((left, Direction::Left)),
-            (TileType::BackwardMirror, Direction::Down) => stack.push((right, Direction::Right)),
-            (TileType::BackwardMirror, Direction::Left) => stack.push((up, Direction::Up)),
-            (TileType::BackwardMirror, Direction::Right) => stack.push((down, Direction::Down)),
-
-            (TileType::HorizontalSplitter, Direction::Up | Direction::Down) => {
-                stack.push((left, Direction::Left));
-                stack.push((right, Direction::Right));
-            }
-            (TileType::HorizontalSplitter, Direction::Left) => stack.push((left, Direction::Left)),
-            (TileType::HorizontalSplitter, Direction::Right) => {
-                stack.push((right, Direction::Right))
-            }
-
-            (TileType::VerticalSplitter, Direction::Up) => stack.push((up, Direction::Up)),
-            (TileType::VerticalSplitter, Direction::Down) => stack.push((down, Direction::Down)),
-            (TileType::VerticalSplitter, Direction::Left | Direction::Right) => {
-                stack.push((up, Direction::Up));
-                stack.push((down, Direction::Down));
-            }
-        }
+        let vertical = matches!(direction, Direction::North | Direction::South);
+        let next_directions = match tile_type {
+            TileType::Empty => vec![direction],
+            TileType::ForwardMirror if vertical => vec![direction.turn_right()],
+            TileType::ForwardMirror => vec![direction.turn_left()],
+            TileType::BackwardMirror if vertical => vec![direction.turn_left()],
+            TileType::BackwardMirror => vec![direction.turn_right()],
+            TileType::HorizontalSplitter if vertical => vec![Direction::West, Direction::East],
+            TileType::VerticalSplitter if !vertical => vec![Direction::North, Direction::South],
+            TileType::HorizontalSplitter | TileType::VerticalSplitter => vec![direction],
+        };
+
+        stack.extend(
+            next_directions
+                .into_iter()
+                .map(|direction| (tile_pos + direction, direction)),
+        );
     }
 
     energized
@@ -184,17 +160,18 @@ fn energized_tiles(
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let (_, grid) =
+        parse_grid(Span::new(input)).map_err(|e| AocError::ParseError(e.to_string()))?;
     let rows = input.lines().count();
-    let cols = input.lines().next().unwrap().chars().count();
-    let (_, grid) = parse_grid(Span::new(input)).expect("a valid grid parse");
+    let cols = input.lines().next().map_or(0, |line| line.chars().count());
 
     let mut start_tiles = vec![];
     start_tiles.append(
         &mut (0..rows)
             .map(|y| {
                 vec![
-                    (IVec2::new(0, y as i32), Direction::Right),
-                    (IVec2::new(cols as i32 - 1, y as i32), Direction::Left),
+                    (IVec2::new(0, y as i32), Direction::East),
+                    (IVec2::new(cols as i32 - 1, y as i32), Direction::West),
                 ]
             })
             .flatten()
@@ -204,23 +181,28 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
         &mut (0..cols)
             .map(|x| {
                 vec![
-                    (IVec2::new(x as i32, 0), Direction::Down),
-                    (IVec2::new(x as i32, rows as i32 - 1), Direction::Up),
+                    (IVec2::new(x as i32, 0), Direction::South),
+                    (IVec2::new(x as i32, rows as i32 - 1), Direction::North),
                 ]
             })
             .flatten()
             .collect::<Vec<(IVec2, Direction)>>(),
     );
 
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+
     let best_start = start_tiles
         .into_iter()
         .map(|tile| {
             debug!(tile = ?tile, "getting energized tiles with");
             let energized = energized_tiles(tile, &grid);
+            #[cfg(feature = "visualize")]
+            player.show(&LightFrame::new(&grid, &energized));
             energized.len() as u64
         })
         .max()
-        .unwrap();
+        .unwrap_or(0);
 
     Ok(best_start)
 }
@@ -242,6 +224,12 @@ mod day_16_part2 {
 .|....-|.\
 ..//.|...."#;
         assert_eq!(51, process(input)?);
+
+        // the best start, from above the fourth column
+        let (_, grid) = parse_grid(Span::new(input)).expect("a valid grid parse");
+        let energized = energized_tiles((IVec2::new(3, 0), Direction::South), &grid);
+        let frame = LightFrame::new(&grid, &energized);
+        insta::assert_snapshot!(format!("{}\n{}", frame.caption(), frame.render_plain()));
         Ok(())
     }
 
@@ -6,4 +6,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("there's no way for the crucible to reach the bottom right block")]
+    #[diagnostic(code(aoc::no_path))]
+    NoPath,
 }
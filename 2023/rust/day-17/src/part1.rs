@@ -26,7 +26,7 @@ impl Block {
     }
 }
 
-fn a_num(input: Span) -> IResult<Span, (IVec2, u64)> {
+fn a_num(input: Span<'_>) -> IResult<Span<'_>, (IVec2, u64)> {
     let (input, pos) = position(input)?;
     let (input, num) = one_of("0123456789")(input)?;
 
@@ -36,7 +36,7 @@ fn a_num(input: Span) -> IResult<Span, (IVec2, u64)> {
     Ok((input, (IVec2::new(x, y), num.to_digit(10).unwrap().into())))
 }
 
-fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, u64>> {
+fn parse_grid(input: Span<'_>) -> IResult<Span<'_>, HashMap<IVec2, u64>> {
     let (input, output) = all_consuming(terminated(
         separated_list1(line_ending, many1(a_num)),
         multispace0,
@@ -53,11 +53,12 @@ fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, u64>> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let row_count = input.lines().count() as i32;
-    let col_count = input.lines().next().unwrap().chars().count() as i32;
-    let (_, grid) = parse_grid(Span::new(input)).expect("a valid grid parse");
+    let (_, grid) =
+        parse_grid(Span::new(input)).map_err(|e| AocError::ParseError(e.to_string()))?;
 
-    let goal = IVec2::new(col_count as i32 - 1, row_count as i32 - 1);
+    // the bottom right corner, though rows may come up short of it
+    let goal = grid.keys().copied().fold(IVec2::ZERO, IVec2::max);
+    let (col_count, row_count) = (goal.x + 1, goal.y + 1);
     let result: (Vec<Block>, u64) = dijkstra(
         &Block::new(IVec2::splat(0), VecDeque::from([IVec2::splat(0)])),
         |block| {
@@ -107,15 +108,16 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
                 }
                 Some(Block::new(candidate_pos, prevs))
             })
-            .map(|block| {
-                let cost = *grid.get(&block.pos).unwrap();
-                (block, cost)
+            .filter_map(|block| {
+                // rows that come up short leave holes to steer around
+                let cost = *grid.get(&block.pos)?;
+                Some((block, cost))
             })
             .collect::<Vec<(Block, u64)>>()
         },
         |block| block.pos == goal,
     )
-    .expect("should have a valid path");
+    .ok_or(AocError::NoPath)?;
 
     Ok(result.1)
 }
@@ -143,6 +145,14 @@ mod day_17_part1 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_or_blocked_grids() {
+        assert!(matches!(process("12a"), Err(AocError::ParseError(_))));
+        assert!(matches!(process(""), Err(AocError::ParseError(_))));
+        // the bottom right block sits past where the last row ends
+        assert!(matches!(process("11111\n1"), Err(AocError::NoPath)));
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
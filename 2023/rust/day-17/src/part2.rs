@@ -27,7 +27,7 @@ impl Block {
     }
 }
 
-fn a_num(input: Span) -> IResult<Span, (IVec2, u64)> {
+fn a_num(input: Span<'_>) -> IResult<Span<'_>, (IVec2, u64)> {
     let (input, pos) = position(input)?;
     let (input, num) = one_of("0123456789")(input)?;
 
@@ -37,7 +37,7 @@ fn a_num(input: Span) -> IResult<Span, (IVec2, u64)> {
     Ok((input, (IVec2::new(x, y), num.to_digit(10).unwrap().into())))
 }
 
-fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, u64>> {
+fn parse_grid(input: Span<'_>) -> IResult<Span<'_>, HashMap<IVec2, u64>> {
     let (input, output) = all_consuming(terminated(
         separated_list1(line_ending, many1(a_num)),
         multispace0,
@@ -54,11 +54,12 @@ fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, u64>> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let row_count = input.lines().count() as i32;
-    let col_count = input.lines().next().unwrap().chars().count() as i32;
-    let (_, grid) = parse_grid(Span::new(input)).expect("a valid grid parse");
+    let (_, grid) =
+        parse_grid(Span::new(input)).map_err(|e| AocError::ParseError(e.to_string()))?;
 
-    let goal = IVec2::new(col_count as i32 - 1, row_count as i32 - 1);
+    // the bottom right corner, though rows may come up short of it
+    let goal = grid.keys().copied().fold(IVec2::ZERO, IVec2::max);
+    let (col_count, row_count) = (goal.x + 1, goal.y + 1);
     let result: (Vec<Block>, u64) = dijkstra(
         &Block::new(IVec2::splat(0), VecDeque::from([IVec2::splat(0)])),
         |block| {
@@ -68,10 +69,10 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
                 .tuple_windows()
                 .map(|(a, b)| *a - *b)
                 .collect();
-            let last_diff = diffs.get(0);
             let maybe_first_diff_count = diffs.iter().dedup_with_count().next();
-            let options = if let Some(diff_count) = maybe_first_diff_count {
-                let num_consecutive_straight_diffs = diff_count.0;
+            let options = if let Some((num_consecutive_straight_diffs, &last_diff)) =
+                maybe_first_diff_count
+            {
                 let must_turn = num_consecutive_straight_diffs == 10;
                 let must_go_straight = num_consecutive_straight_diffs < 4;
 
@@ -83,11 +84,11 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
                         IVec2::new(0, 1),
                     ]
                     .into_iter()
-                    .filter(|option| option != last_diff.unwrap())
+                    .filter(|option| *option != last_diff)
                     .map(|option| block.pos + option)
                     .collect::<Vec<IVec2>>()
                 } else if must_go_straight {
-                    vec![block.pos + *last_diff.unwrap()]
+                    vec![block.pos + last_diff]
                 } else {
                     vec![
                         block.pos + IVec2::new(-1, 0),
@@ -130,9 +131,10 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
                     }
                     Some(Block::new(candidate_pos, prevs))
                 })
-                .map(|block| {
-                    let cost = *grid.get(&block.pos).unwrap();
-                    (block, cost)
+                .filter_map(|block| {
+                    // rows that come up short leave holes to steer around
+                    let cost = *grid.get(&block.pos)?;
+                    Some((block, cost))
                 })
                 .collect::<Vec<(Block, u64)>>()
         },
@@ -148,7 +150,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
             maybe_first_diff_count.is_some_and(|(count, _)| count >= 4) && block.pos == goal
         },
     )
-    .expect("should have a valid path");
+    .ok_or(AocError::NoPath)?;
 
     Ok(result.1)
 }
@@ -187,6 +189,14 @@ mod day_17_part2 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_or_blocked_grids() {
+        assert!(matches!(process("12a"), Err(AocError::ParseError(_))));
+        assert!(matches!(process(""), Err(AocError::ParseError(_))));
+        // the bottom right block sits past where the last row ends
+        assert!(matches!(process("11111\n1"), Err(AocError::NoPath)));
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
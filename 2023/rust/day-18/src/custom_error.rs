@@ -6,4 +6,16 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("the trench doesn't end where it started")]
+    #[diagnostic(code(aoc::open_trench))]
+    OpenTrench,
+
+    #[error("the lagoon holds more lava than a u64 counts")]
+    #[diagnostic(code(aoc::lagoon_too_big))]
+    LagoonTooBig,
 }
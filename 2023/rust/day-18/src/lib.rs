@@ -1,4 +1,90 @@
+use glam::I64Vec2;
+
+use crate::custom_error::AocError;
+
 pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+
+#[derive(Debug)]
+pub struct DigInstruction {
+    pub direction: I64Vec2,
+    pub count: u32,
+}
+
+/// Cubic metres of lava the lagoon holds once the trench along `dig_plan`
+/// and everything inside it is dug out.
+///
+/// The shoelace formula gives the area inside the trench's centre line, and
+/// the trench adds half a block along its length plus a block for the
+/// corners. Works in `i128` with checked arithmetic, as a plan's metres can
+/// add up to more than the corners' coordinates multiplied fit in `i64`.
+pub fn lagoon_size(dig_plan: &[DigInstruction]) -> Result<u64, AocError> {
+    let (mut x, mut y) = (0_i128, 0_i128);
+    let (mut twice_area, mut perimeter) = (0_i128, 0_i128);
+
+    for instruction in dig_plan {
+        let count = i128::from(instruction.count);
+        let next_x = x
+            .checked_add(i128::from(instruction.direction.x) * count)
+            .ok_or(AocError::LagoonTooBig)?;
+        let next_y = y
+            .checked_add(i128::from(instruction.direction.y) * count)
+            .ok_or(AocError::LagoonTooBig)?;
+        twice_area = x
+            .checked_mul(next_y)
+            .zip(y.checked_mul(next_x))
+            .and_then(|(a, b)| a.checked_sub(b))
+            .and_then(|term| twice_area.checked_add(term))
+            .ok_or(AocError::LagoonTooBig)?;
+        perimeter = perimeter.checked_add(count).ok_or(AocError::LagoonTooBig)?;
+        (x, y) = (next_x, next_y);
+    }
+
+    if (x, y) != (0, 0) {
+        return Err(AocError::OpenTrench);
+    }
+    (twice_area.unsigned_abs() / 2 + perimeter.unsigned_abs() / 2 + 1)
+        .try_into()
+        .map_err(|_| AocError::LagoonTooBig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dig(steps: &[(I64Vec2, u32)]) -> Vec<DigInstruction> {
+        steps
+            .iter()
+            .map(|&(direction, count)| DigInstruction { direction, count })
+            .collect()
+    }
+
+    #[test]
+    fn square() -> miette::Result<()> {
+        let plan = dig(&[
+            (I64Vec2::X, 2),
+            (I64Vec2::NEG_Y, 2),
+            (I64Vec2::NEG_X, 2),
+            (I64Vec2::Y, 2),
+        ]);
+        assert_eq!(9, lagoon_size(&plan)?);
+        Ok(())
+    }
+
+    #[test]
+    fn open_or_huge_trenches() {
+        let open = dig(&[(I64Vec2::X, 2), (I64Vec2::Y, 2)]);
+        assert!(matches!(lagoon_size(&open), Err(AocError::OpenTrench)));
+
+        // a square u32::MAX metres a side holds more than a u64 counts
+        let huge = dig(&[
+            (I64Vec2::X, u32::MAX),
+            (I64Vec2::NEG_Y, u32::MAX),
+            (I64Vec2::NEG_X, u32::MAX),
+            (I64Vec2::Y, u32::MAX),
+        ]);
+        assert!(matches!(lagoon_size(&huge), Err(AocError::LagoonTooBig)));
+    }
+}
@@ -1,8 +1,7 @@
-use crate::custom_error::AocError;
-use tracing::{debug, info};
+use crate::{custom_error::AocError, lagoon_size, DigInstruction};
+use tracing::info;
 
 use glam::I64Vec2;
-use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::take_until,
@@ -12,12 +11,6 @@ use nom::{
     IResult, Parser,
 };
 
-#[derive(Debug)]
-struct DigInstruction {
-    direction: I64Vec2,
-    count: i64,
-}
-
 fn dig_instruction(input: &str) -> IResult<&str, DigInstruction> {
     let (input, direction) = alt((
         complete::char('R').map(|_| I64Vec2::X),
@@ -26,7 +19,7 @@ fn dig_instruction(input: &str) -> IResult<&str, DigInstruction> {
         complete::char('D').map(|_| I64Vec2::NEG_Y),
     ))(input)?;
 
-    let (input, count) = delimited(space1, complete::i64, space1)(input)?;
+    let (input, count) = delimited(space1, complete::u32, space1)(input)?;
     let (input, _) = terminated(take_until(")"), complete::char(')'))(input)?;
 
     Ok((input, DigInstruction { direction, count }))
@@ -38,46 +31,12 @@ fn parse_dig_plan(input: &str) -> IResult<&str, Vec<DigInstruction>> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, dig_plan) = parse_dig_plan(input).expect("a valid dig plan");
-
-    let vertices = dig_plan
-        .iter()
-        .scan(I64Vec2::ZERO, |state, instruction| {
-            *state += instruction.direction * instruction.count;
-            Some(*state)
-        })
-        .collect_vec();
-
-    let perimeter_len = vertices
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| {
-            let distance = (*a - *b).abs();
-            distance.x + distance.y
-        })
-        .sum::<i64>()
-        + {
-            // wraparound from the last vertex to the first
-            let last = vertices.last().unwrap();
-            let first = vertices.first().unwrap();
-            let distance = (*first - *last).abs();
-            distance.x + distance.y
-        };
-    debug!(?perimeter_len);
-
-    let area = (vertices
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| a.x * b.y - a.y * b.x)
-        .sum::<i64>()
-        / 2)
-    .abs()
-        + perimeter_len / 2
-        + 1;
+    let (_, dig_plan) = parse_dig_plan(input).map_err(|e| AocError::ParseError(e.to_string()))?;
 
+    let area = lagoon_size(&dig_plan)?;
     info!(?area);
 
-    Ok(area as u64)
+    Ok(area)
 }
 
 #[cfg(test)]
@@ -1,35 +1,29 @@
-use crate::custom_error::AocError;
-use tracing::{debug, info};
+use crate::{custom_error::AocError, lagoon_size, DigInstruction};
+use tracing::info;
 
 use glam::I64Vec2;
-use itertools::Itertools;
 use nom::{
-    bytes::complete::{take, take_until},
+    branch::alt,
+    bytes::complete::{take_until, take_while_m_n},
     character::complete::{self},
+    combinator::map_res,
     multi::many1,
     sequence::terminated,
-    IResult,
+    IResult, Parser,
 };
 
-#[derive(Debug)]
-struct DigInstruction {
-    direction: I64Vec2,
-    count: i64,
-}
-
 fn dig_instruction(input: &str) -> IResult<&str, DigInstruction> {
     let (input, _) = terminated(take_until("#"), complete::char('#'))(input)?;
-    let (input, hex) = take(5usize)(input)?;
-    let (input, direction) = take(1usize)(input)?;
-
-    let count = i64::from_str_radix(hex, 16).expect("a valid hex number");
-    let direction = match i64::from_str_radix(direction, 16).expect("a valid hex number") {
-        0 => I64Vec2::X,
-        1 => I64Vec2::NEG_Y,
-        2 => I64Vec2::NEG_X,
-        3 => I64Vec2::Y,
-        _ => panic!("invalid direction"),
-    };
+    let (input, count) = map_res(
+        take_while_m_n(5, 5, |c: char| c.is_ascii_hexdigit()),
+        |hex| u32::from_str_radix(hex, 16),
+    )(input)?;
+    let (input, direction) = alt((
+        complete::char('0').map(|_| I64Vec2::X),
+        complete::char('1').map(|_| I64Vec2::NEG_Y),
+        complete::char('2').map(|_| I64Vec2::NEG_X),
+        complete::char('3').map(|_| I64Vec2::Y),
+    ))(input)?;
 
     Ok((input, DigInstruction { direction, count }))
 }
@@ -40,46 +34,12 @@ fn parse_dig_plan(input: &str) -> IResult<&str, Vec<DigInstruction>> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, dig_plan) = parse_dig_plan(input).expect("a valid dig plan");
-
-    let vertices = dig_plan
-        .iter()
-        .scan(I64Vec2::ZERO, |state, instruction| {
-            *state += instruction.direction * instruction.count;
-            Some(*state)
-        })
-        .collect_vec();
-
-    let perimeter_len = vertices
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| {
-            let distance = (*a - *b).abs();
-            distance.x + distance.y
-        })
-        .sum::<i64>()
-        + {
-            // wraparound from the last vertex to the first
-            let last = vertices.last().unwrap();
-            let first = vertices.first().unwrap();
-            let distance = (*first - *last).abs();
-            distance.x + distance.y
-        };
-    debug!(?perimeter_len);
-
-    let area = (vertices
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| a.x * b.y - a.y * b.x)
-        .sum::<i64>()
-        / 2)
-    .abs()
-        + perimeter_len / 2
-        + 1;
+    let (_, dig_plan) = parse_dig_plan(input).map_err(|e| AocError::ParseError(e.to_string()))?;
 
+    let area = lagoon_size(&dig_plan)?;
     info!(?area);
 
-    Ok(area as u64)
+    Ok(area)
 }
 
 #[cfg(test)]
@@ -106,6 +66,16 @@ U 2 (#7a21e3)";
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_colors() {
+        for input in ["R 6 (#70c71)", "R 6 (#+0c710)", "R 6 (#70c714)"] {
+            assert!(
+                matches!(process(input), Err(AocError::ParseError(_))),
+                "{input:?}"
+            );
+        }
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
@@ -6,4 +6,16 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("there's no workflow named {0}")]
+    #[diagnostic(code(aoc::unknown_workflow))]
+    UnknownWorkflow(String),
+
+    #[error("workflow {0} sends parts back into itself")]
+    #[diagnostic(code(aoc::workflow_cycle))]
+    WorkflowCycle(String),
 }
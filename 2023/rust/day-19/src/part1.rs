@@ -6,7 +6,7 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alpha1, line_ending, multispace1},
-    combinator::opt,
+    combinator::{opt, verify},
     multi::{fold_many1, separated_list1},
     sequence::{delimited, separated_pair, terminated},
     IResult, Parser,
@@ -31,7 +31,7 @@ enum Rule<'a> {
     Test {
         attribute: &'a str,
         condition: Condition,
-        value: u64,
+        value: u32,
         target: Target<'a>,
     },
     Target(Target<'a>),
@@ -51,7 +51,7 @@ impl<'a> Rule<'a> {
                     "m" => part.m,
                     "a" => part.a,
                     "s" => part.s,
-                    _ => panic!("unknown attribute {}", attribute),
+                    _ => unreachable!("unknown attribute {}", attribute),
                 };
                 let ordering = match condition {
                     Condition::LessThan => Ordering::Less,
@@ -72,10 +72,10 @@ struct Workflow<'a> {
 
 #[derive(Debug, PartialEq, Eq, Default)]
 struct Part {
-    x: u64,
-    m: u64,
-    a: u64,
-    s: u64,
+    x: u32,
+    m: u32,
+    a: u32,
+    s: u32,
 }
 
 fn target(input: &str) -> IResult<&str, Target> {
@@ -86,13 +86,17 @@ fn target(input: &str) -> IResult<&str, Target> {
     ))(input)
 }
 
+fn attribute(input: &str) -> IResult<&str, &str> {
+    alt((tag("x"), tag("m"), tag("a"), tag("s")))(input)
+}
+
 fn rule_test(input: &str) -> IResult<&str, Rule> {
-    let (input, attribute) = alpha1(input)?;
+    let (input, attribute) = attribute(input)?;
     let (input, condition) = alt((
         tag("<").map(|_| Condition::LessThan),
         tag(">").map(|_| Condition::GreaterThan),
     ))(input)?;
-    let (input, value) = complete::u64(input)?;
+    let (input, value) = complete::u32(input)?;
     let (input, _) = complete::char(':')(input)?;
     let (input, target) = target(input)?;
     Ok((
@@ -108,11 +112,15 @@ fn rule_test(input: &str) -> IResult<&str, Rule> {
 
 fn workflow(input: &str) -> IResult<&str, Workflow> {
     let (input, id) = alpha1(input)?;
+    // the last rule has to send the parts that fail every test somewhere
     let (input, rules) = delimited(
         complete::char('{'),
-        separated_list1(
-            complete::char(','),
-            alt((rule_test, target.map(Rule::Target))),
+        verify(
+            separated_list1(
+                complete::char(','),
+                alt((rule_test, target.map(Rule::Target))),
+            ),
+            |rules: &[Rule]| matches!(rules.last(), Some(Rule::Target(_))),
         ),
         complete::char('}'),
     )(input)?;
@@ -129,7 +137,7 @@ fn part(input: &str) -> IResult<&str, Part> {
         complete::char('{'),
         fold_many1(
             terminated(
-                separated_pair(alpha1, complete::char('='), complete::u64),
+                separated_pair(attribute, complete::char('='), complete::u32),
                 opt(tag(",")),
             ),
             Part::default,
@@ -139,7 +147,7 @@ fn part(input: &str) -> IResult<&str, Part> {
                     "m" => part.m = count,
                     "a" => part.a = count,
                     "s" => part.s = count,
-                    _ => panic!("unknown attribute {}", next_attribute),
+                    _ => unreachable!("unknown attribute {}", next_attribute),
                 }
                 part
             },
@@ -161,38 +169,39 @@ fn parse(input: &str) -> IResult<&str, (Vec<Part>, HashMap<&str, Workflow>)> {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_input, (parts, workflows)) = parse(input).expect("should parse the puzzle input");
+    let (_input, (parts, workflows)) =
+        parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
 
-    let results = parts
-        .iter()
-        .filter_map(|part| {
-            let mut workflow_id = "in";
-            let last_target: Target = 'workflow_loop: loop {
-                let workflow = workflows.get(workflow_id).expect("should have a workflow");
+    let mut results = 0;
+    for part in parts.iter() {
+        let mut workflow_id = "in";
+        // a part can't go through more workflows than there are without
+        // going through one of them twice
+        let mut hops = 0;
+        let last_target: &Target = 'workflow_loop: loop {
+            let workflow = workflows
+                .get(workflow_id)
+                .ok_or_else(|| AocError::UnknownWorkflow(workflow_id.to_string()))?;
+            hops += 1;
+            if hops > workflows.len() {
+                return Err(AocError::WorkflowCycle(workflow_id.to_string()));
+            }
 
-                'rule_loop: for rule in workflow.rules.iter() {
-                    match rule.next_target(part) {
-                        Some(Target::Accepted) => {
-                            break 'workflow_loop Target::Accepted;
-                        }
-                        Some(Target::Rejected) => {
-                            break 'workflow_loop Target::Rejected;
-                        }
-                        Some(Target::Workflow(next_workflow_id)) => {
-                            workflow_id = next_workflow_id;
-                            break 'rule_loop;
-                        }
-                        None => {}
+            for rule in workflow.rules.iter() {
+                match rule.next_target(part) {
+                    Some(Target::Workflow(next_workflow_id)) => {
+                        workflow_id = next_workflow_id;
+                        break;
                     }
+                    Some(target) => break 'workflow_loop target,
+                    None => {}
                 }
-            };
-            match last_target {
-                Target::Workflow(_) => panic!("should not have a workflow as last target"),
-                Target::Accepted => Some(part.x + part.m + part.a + part.s),
-                Target::Rejected => None,
             }
-        })
-        .sum::<u64>();
+        };
+        if *last_target == Target::Accepted {
+            results += part.x as u64 + part.m as u64 + part.a as u64 + part.s as u64;
+        }
+    }
 
     Ok(results)
 }
@@ -264,6 +273,31 @@ hdj{m>838:A,pv}
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_input() {
+        let part = "\n\n{x=787,m=2655,a=1222,s=2876}";
+        assert!(matches!(
+            process(&format!("in{{x<10:A}}{part}")),
+            Err(AocError::ParseError(_))
+        ));
+        assert!(matches!(
+            process(&format!("in{{q<10:A,R}}{part}")),
+            Err(AocError::ParseError(_))
+        ));
+        assert!(matches!(
+            process("in{A}\n\n{x=99999999999,m=0,a=0,s=0}"),
+            Err(AocError::ParseError(_))
+        ));
+        assert!(matches!(
+            process(&format!("in{{px}}{part}")),
+            Err(AocError::UnknownWorkflow(id)) if id == "px"
+        ));
+        assert!(matches!(
+            process(&format!("in{{qq}}\nqq{{x>1:in,A}}{part}")),
+            Err(AocError::WorkflowCycle(_))
+        ));
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
@@ -4,6 +4,7 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alpha1, line_ending, multispace1},
+    combinator::verify,
     multi::separated_list1,
     sequence::delimited,
     IResult, Parser,
@@ -161,8 +162,12 @@ fn target(input: &str) -> IResult<&str, Target> {
     ))(input)
 }
 
+fn attribute(input: &str) -> IResult<&str, &str> {
+    alt((tag("x"), tag("m"), tag("a"), tag("s")))(input)
+}
+
 fn rule_test(input: &str) -> IResult<&str, Rule> {
-    let (input, attribute) = alpha1(input)?;
+    let (input, attribute) = attribute(input)?;
     let (input, condition) = alt((
         tag("<").map(|_| Condition::LessThan),
         tag(">").map(|_| Condition::GreaterThan),
@@ -183,11 +188,15 @@ fn rule_test(input: &str) -> IResult<&str, Rule> {
 
 fn workflow(input: &str) -> IResult<&str, Workflow> {
     let (input, id) = alpha1(input)?;
+    // the last rule has to send the parts that fail every test somewhere
     let (input, rules) = delimited(
         complete::char('{'),
-        separated_list1(
-            complete::char(','),
-            alt((rule_test, target.map(Rule::Target))),
+        verify(
+            separated_list1(
+                complete::char(','),
+                alt((rule_test, target.map(Rule::Target))),
+            ),
+            |rules: &[Rule]| matches!(rules.last(), Some(Rule::Target(_))),
         ),
         complete::char('}'),
     )(input)?;
@@ -205,45 +214,57 @@ fn parse(input: &str) -> IResult<&str, HashMap<&str, Workflow>> {
     Ok((input, workflows))
 }
 
-fn process_part(part: Part, workflows: &HashMap<&str, Workflow>, next_target: &Target) -> u64 {
+/// Number of ratings in a range, zero once a split leaves it empty.
+fn ratings(range: &RangeInclusive<u64>) -> u64 {
+    (range.end() + 1).saturating_sub(*range.start())
+}
+
+/// `depth` counts the workflows the part went through, there can't be more
+/// than there are workflows without going through one of them twice.
+fn process_part(
+    part: Part,
+    workflows: &HashMap<&str, Workflow>,
+    next_target: &Target,
+    depth: usize,
+) -> Result<u64, AocError> {
     match next_target {
         Target::Workflow(id) => {
             let current_workflow = workflows
                 .get(id)
-                .expect("should always return a valid workflow for a valid id");
+                .ok_or_else(|| AocError::UnknownWorkflow(id.to_string()))?;
+            if depth >= workflows.len() {
+                return Err(AocError::WorkflowCycle(id.to_string()));
+            }
             let mut current_part = part;
             let mut sum = 0;
 
             for rule in current_workflow.rules.iter() {
                 match rule.apply_to(&current_part) {
                     Apply::Split { pass, fails } => {
-                        sum += process_part(pass.0, workflows, pass.1);
+                        sum += process_part(pass.0, workflows, pass.1, depth + 1)?;
                         current_part = fails;
                     }
                     Apply::PassedTest(target) => {
-                        sum += process_part(current_part.clone(), workflows, target);
+                        sum += process_part(current_part.clone(), workflows, target, depth + 1)?;
                         break;
                     }
                     Apply::FailedTest => {}
                 }
             }
-            sum
+            Ok(sum)
         }
         Target::Accepted => {
-            (part.x.end() - part.x.start() + 1)
-                * (part.m.end() - part.m.start() + 1)
-                * (part.a.end() - part.a.start() + 1)
-                * (part.s.end() - part.s.start() + 1)
+            Ok(ratings(&part.x) * ratings(&part.m) * ratings(&part.a) * ratings(&part.s))
         }
-        Target::Rejected => 0,
+        Target::Rejected => Ok(0),
     }
 }
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_input, workflows) = parse(input).expect("should parse the puzzle input");
+    let (_input, workflows) = parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
 
-    let result = process_part(Part::default(), &workflows, &Target::Workflow("in"));
+    let result = process_part(Part::default(), &workflows, &Target::Workflow("in"), 0)?;
 
     Ok(result)
 }
@@ -301,6 +322,25 @@ hdj{m>838:A,pv}
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_input() {
+        assert!(matches!(
+            process("in{px}\n\n"),
+            Err(AocError::UnknownWorkflow(id)) if id == "px"
+        ));
+        assert!(matches!(
+            process("in{qq}\nqq{x>1:in,A}\n\n"),
+            Err(AocError::WorkflowCycle(_))
+        ));
+    }
+
+    #[test_log::test]
+    fn empty_split() -> miette::Result<()> {
+        assert_eq!(0, process("in{x>4000:A,R}\n\n")?);
+        assert_eq!(4000u64.pow(4), process("in{x<1:R,A}\n\n")?);
+        Ok(())
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
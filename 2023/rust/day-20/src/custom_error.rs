@@ -6,4 +6,28 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("there's no {0} module")]
+    #[diagnostic(code(aoc::missing_module))]
+    MissingModule(&'static str),
+
+    #[error("{0} isn't fed by a conjunction module")]
+    #[diagnostic(code(aoc::unsupported_network))]
+    UnsupportedNetwork(&'static str),
+
+    #[error("a button push sent more than {0} pulses without settling")]
+    #[diagnostic(code(aoc::unsettled_pulses))]
+    UnsettledPulses(usize),
+
+    #[error("{0} didn't get a low pulse within {1} button pushes")]
+    #[diagnostic(code(aoc::too_many_pushes))]
+    TooManyPushes(&'static str, usize),
+
+    #[error("the answer doesn't fit in 64 bits")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
 }
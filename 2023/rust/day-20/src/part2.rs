@@ -1,6 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
 use crate::custom_error::AocError;
+use aoc_common::interner::Interner;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -17,46 +18,49 @@ enum State {
     Off,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Signal {
     Low,
     High,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 struct Pulse {
-    src: String,
-    dst: String,
+    src: u32,
+    dst: u32,
     signal: Signal,
 }
 
 #[derive(Debug, Clone)]
 enum ModuleKind {
     FlipFlop { state: State },
-    Conjunction { state: HashMap<String, Signal> },
+    Conjunction { state: HashMap<u32, Signal> },
     Broadcast,
     // Button,
 }
 
 #[derive(Debug, Clone)]
-struct Module<'a> {
-    id: &'a str,
+struct Module {
+    id: u32,
     kind: ModuleKind,
-    destination_modules: Vec<&'a str>,
+    destination_modules: Vec<u32>,
 }
 
-impl Module<'_> {
+impl Module {
+    fn pulses(&self, signal: Signal) -> Vec<Pulse> {
+        self.destination_modules
+            .iter()
+            .map(|dst| Pulse {
+                src: self.id,
+                dst: *dst,
+                signal,
+            })
+            .collect()
+    }
+
     fn send(&mut self, pulse: Pulse) -> Vec<Pulse> {
         match self.kind {
-            ModuleKind::Broadcast => self
-                .destination_modules
-                .iter()
-                .map(|dst| Pulse {
-                    src: String::from(self.id),
-                    dst: String::from(*dst),
-                    signal: pulse.signal.clone(),
-                })
-                .collect(),
+            ModuleKind::Broadcast => self.pulses(pulse.signal),
             ModuleKind::FlipFlop { ref mut state } => match pulse.signal {
                 Signal::High => {
                     vec![]
@@ -64,31 +68,17 @@ impl Module<'_> {
                 Signal::Low => match state {
                     State::Off => {
                         *state = State::On;
-                        self.destination_modules
-                            .iter()
-                            .map(|dst| Pulse {
-                                src: String::from(self.id),
-                                dst: String::from(*dst),
-                                signal: Signal::High,
-                            })
-                            .collect()
+                        self.pulses(Signal::High)
                     }
                     State::On => {
                         *state = State::Off;
-                        self.destination_modules
-                            .iter()
-                            .map(|dst| Pulse {
-                                src: String::from(self.id),
-                                dst: String::from(*dst),
-                                signal: Signal::Low,
-                            })
-                            .collect()
+                        self.pulses(Signal::Low)
                     }
                 },
             },
             ModuleKind::Conjunction { ref mut state } => {
                 state.entry(pulse.src).and_modify(|src| {
-                    *src = pulse.signal.clone();
+                    *src = pulse.signal;
                 });
 
                 let signal = match state.values().all(|signal| match signal {
@@ -99,21 +89,24 @@ impl Module<'_> {
                     false => Signal::High,
                 };
 
-                self.destination_modules
-                    .iter()
-                    .map(|dst| Pulse {
-                        src: String::from(self.id),
-                        dst: String::from(*dst),
-                        signal: signal.clone(),
-                    })
-                    .collect()
+                self.pulses(signal)
             }
         }
     }
 }
 
-fn parse_modules(input: &str) -> IResult<&str, HashMap<&str, Module>> {
-    let (input, modules) = separated_list0(
+/// Pulses a single button push may send. The puzzle's networks settle after
+/// a few hundred, a network looping back on itself never does.
+const MAX_PULSES: usize = 1 << 16;
+
+/// Button pushes before giving up on a conjunction input ever getting a low
+/// pulse. The puzzle's inputs cycle every four thousand pushes or so.
+const MAX_PUSHES: usize = 1 << 16;
+
+type ParsedModule<'a> = ((&'a str, ModuleKind), Vec<&'a str>);
+
+fn parse_modules(input: &str) -> IResult<&str, Vec<ParsedModule<'_>>> {
+    separated_list0(
         line_ending,
         separated_pair(
             alt((
@@ -132,56 +125,62 @@ fn parse_modules(input: &str) -> IResult<&str, HashMap<&str, Module>> {
             tag(" -> "),
             separated_list1(tag(", "), alpha1),
         ),
-    )(input)?;
+    )(input)
+}
 
-    Ok((
-        input,
-        modules
+/// Modules are indexed by their interned id. Destinations that aren't
+/// declared (like `output` or `rx`) get an id but no module.
+fn build_modules(parsed: Vec<ParsedModule<'_>>) -> (Interner<u32>, Vec<Option<Module>>) {
+    let mut names = Interner::new();
+    let mut modules = vec![];
+
+    for ((name, kind), destinations) in parsed {
+        let id = names.intern(name);
+        let destination_modules = destinations
             .into_iter()
-            .map(|((id, kind), destination_modules)| {
-                (
-                    id,
-                    Module {
-                        id,
-                        kind,
-                        destination_modules,
-                    },
-                )
-            })
-            .collect(),
-    ))
+            .map(|dst| names.intern(dst))
+            .collect();
+
+        modules.resize(names.len(), None);
+        modules[id as usize] = Some(Module {
+            id,
+            kind,
+            destination_modules,
+        });
+    }
+    modules.resize(names.len(), None);
+
+    (names, modules)
 }
 
-fn update_conjunction_inputs(modules: &mut HashMap<&str, Module>) {
-    let conjunction_ids = modules
-        .iter()
-        .filter_map(|(id, module)| match module.kind {
-            ModuleKind::Conjunction { .. } => Some(*id),
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-
-    let conjunction_inputs = modules.iter().fold(
-        HashMap::<&str, Vec<&str>>::new(),
-        |mut acc, (id, module)| {
-            for c in conjunction_ids.iter() {
-                if module.destination_modules.contains(c) {
-                    acc.entry(c)
-                        .and_modify(|item| {
-                            item.push(id);
+fn update_conjunction_inputs(modules: &mut [Option<Module>]) {
+    let conjunction_inputs =
+        modules
+            .iter()
+            .flatten()
+            .fold(HashMap::<u32, Vec<u32>>::new(), |mut acc, module| {
+                for dst in module.destination_modules.iter() {
+                    if matches!(
+                        modules[*dst as usize],
+                        Some(Module {
+                            kind: ModuleKind::Conjunction { .. },
+                            ..
                         })
-                        .or_insert(vec![id]);
+                    ) {
+                        acc.entry(*dst).or_default().push(module.id);
+                    }
                 }
-            }
-            acc
-        },
-    );
+                acc
+            });
 
     conjunction_inputs.into_iter().for_each(|(id, inputs)| {
-        let conjunction = modules.get_mut(id).unwrap();
-        if let ModuleKind::Conjunction { ref mut state } = conjunction.kind {
+        if let Some(Module {
+            kind: ModuleKind::Conjunction { ref mut state },
+            ..
+        }) = modules[id as usize]
+        {
             for input in inputs {
-                state.insert(String::from(input), Signal::Low);
+                state.insert(input, Signal::Low);
             }
         }
     });
@@ -189,76 +188,99 @@ fn update_conjunction_inputs(modules: &mut HashMap<&str, Module>) {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, mut modules) = parse_modules(input).expect("should parse modules");
+    let (_, parsed) = parse_modules(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    let (mut names, mut modules) = build_modules(parsed);
     update_conjunction_inputs(&mut modules);
 
+    let button = names.intern("button");
+    let broadcaster = names
+        .get("broadcaster")
+        .ok_or(AocError::MissingModule("broadcaster"))?;
+
     const FINAL_DST: &str = "rx";
+    let final_dst = names
+        .get(FINAL_DST)
+        .ok_or(AocError::MissingModule(FINAL_DST))?;
     let final_conjunction = modules
         .iter()
-        .filter_map(|(id, module)| {
+        .flatten()
+        .filter_map(|module| {
             module
                 .destination_modules
-                .contains(&FINAL_DST)
-                .then_some(*id)
+                .contains(&final_dst)
+                .then_some(module.id)
         })
-        .collect::<Vec<&str>>();
-    let mut final_conjunction_inputs = modules
-        .get(final_conjunction.first().unwrap())
+        .collect::<Vec<u32>>();
+    let mut final_conjunction_inputs = final_conjunction
+        .first()
+        .and_then(|id| modules[*id as usize].as_ref())
         .and_then(|module| {
             if let ModuleKind::Conjunction { ref state } = module.kind {
-                Some(state.keys().cloned().collect::<Vec<_>>())
+                Some(state.keys().copied().collect::<Vec<_>>())
             } else {
                 None
             }
         })
-        .unwrap();
+        .filter(|inputs| !inputs.is_empty())
+        .ok_or(AocError::UnsupportedNetwork(FINAL_DST))?;
     let final_conjunction_inputs_len = final_conjunction_inputs.len();
 
-    let mut lcms: Vec<usize> = vec![];
-    for button_push in 0.. {
+    let mut lcms: Vec<u64> = vec![];
+    for button_push in 0..=MAX_PUSHES {
         // debug!("pushing the button for the {} time", button_push + 1);
 
         if lcms.len() == final_conjunction_inputs_len {
             break;
         }
+        if button_push == MAX_PUSHES {
+            return Err(AocError::TooManyPushes(FINAL_DST, MAX_PUSHES));
+        }
 
         let mut pulse_bus = VecDeque::new();
         pulse_bus.push_back(Pulse {
-            src: String::from("button"),
-            dst: String::from("broadcaster"),
+            src: button,
+            dst: broadcaster,
             signal: Signal::Low,
         });
 
+        let mut pulses = 0;
         while let Some(pulse) = pulse_bus.pop_front() {
-            debug!("{} -{:?}-> {}", pulse.src, pulse.signal, pulse.dst);
+            pulses += 1;
+            if pulses > MAX_PULSES {
+                return Err(AocError::UnsettledPulses(MAX_PULSES));
+            }
+            debug!(
+                "{} -{:?}-> {}",
+                names.resolve(pulse.src),
+                pulse.signal,
+                names.resolve(pulse.dst)
+            );
 
             if final_conjunction_inputs.contains(&pulse.dst) && pulse.signal == Signal::Low {
                 debug!(?final_conjunction_inputs);
                 final_conjunction_inputs.retain(|id| id != &pulse.dst);
-                lcms.push(button_push + 1);
+                lcms.push(button_push as u64 + 1);
             }
 
             let output = modules
-                .get_mut(pulse.dst.as_str())
-                .map(|module| module.send(pulse.clone()))
-                .unwrap_or(vec![]);
+                .get_mut(pulse.dst as usize)
+                .and_then(Option::as_mut)
+                .map(|module| module.send(pulse))
+                .unwrap_or_default();
             pulse_bus.extend(output);
         }
     }
 
-    Ok(lcm(&lcms) as u64)
+    lcm(&lcms).ok_or(AocError::Overflow)
 }
 
-fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
+fn lcm(nums: &[u64]) -> Option<u64> {
+    nums.iter().try_fold(1, |lcm, &num| {
+        (lcm / gcd_of_two_numbers(lcm, num)).checked_mul(num)
+    })
 }
 
-fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
+fn gcd_of_two_numbers(a: u64, b: u64) -> u64 {
     if b == 0 {
         return a;
     }
@@ -269,6 +291,23 @@ fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
 mod day_20_part2 {
     use super::*;
 
+    #[test_log::test]
+    fn malformed_input() {
+        assert!(matches!(
+            process("broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a"),
+            Err(AocError::MissingModule("rx"))
+        ));
+        assert!(matches!(
+            process("broadcaster -> a\n%a -> rx"),
+            Err(AocError::UnsupportedNetwork("rx"))
+        ));
+        // nothing ever sends a pulse to `b`
+        assert!(matches!(
+            process("broadcaster -> a\n%a -> con\n&b -> con\n&con -> rx"),
+            Err(AocError::TooManyPushes("rx", MAX_PUSHES))
+        ));
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
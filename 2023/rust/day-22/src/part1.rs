@@ -1,12 +1,10 @@
 use crate::custom_error::AocError;
+use aoc_common::{dot::Graph, parsers::ivec3};
 use glam::IVec3;
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    bytes::complete::tag, character::complete::line_ending, multi::separated_list1,
+    sequence::separated_pair, IResult,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -39,16 +37,6 @@ impl Brick {
     }
 }
 
-fn ivec3(input: &str) -> IResult<&str, IVec3> {
-    let (input, a) = complete::i32(input)?;
-    let (input, _) = tag(",")(input)?;
-    let (input, b) = complete::i32(input)?;
-    let (input, _) = tag(",")(input)?;
-    let (input, c) = complete::i32(input)?;
-
-    Ok((input, IVec3::from([a, b, c])))
-}
-
 fn parse_bricks(input: &str) -> IResult<&str, Vec<Brick>> {
     let (input, bricks) =
         separated_list1(line_ending, separated_pair(ivec3, tag("~"), ivec3))(input)?;
@@ -67,6 +55,7 @@ fn parse_bricks(input: &str) -> IResult<&str, Vec<Brick>> {
     ))
 }
 
+#[tracing::instrument(skip_all)]
 fn settle_bricks(bricks: &mut Vec<Brick>) -> Vec<Brick> {
     bricks
         .iter_mut()
@@ -115,6 +104,47 @@ fn settle_bricks(bricks: &mut Vec<Brick>) -> Vec<Brick> {
         .0
 }
 
+fn is_removable(bricks: &[Brick], brick: &Brick) -> bool {
+    brick
+        .supports
+        .iter()
+        .all(|i| bricks[*i].supported_by.len() > 1)
+}
+
+/// Which settled brick rests on which, with the ground at the bottom and
+/// the bricks that can be disintegrated safely in green.
+pub fn dot(input: &str) -> miette::Result<Graph, AocError> {
+    let (_, mut bricks) = parse_bricks(input).expect("should parse bricks");
+    let bricks = settle_bricks(&mut bricks);
+
+    let mut graph = Graph::directed();
+    graph.attribute("rankdir", "BT");
+    for (i, brick) in bricks.iter().enumerate() {
+        let label = format!(
+            "{i}\n{},{},{}~{},{},{}",
+            brick.start.x, brick.start.y, brick.start.z, brick.end.x, brick.end.y, brick.end.z
+        );
+        let color = match is_removable(&bricks, brick) {
+            true => "lightgreen",
+            false => "lightgrey",
+        };
+        graph.node(
+            &i.to_string(),
+            &[
+                ("shape", "box"),
+                ("label", &label),
+                ("style", "filled"),
+                ("fillcolor", color),
+            ],
+        );
+        for supported in brick.supports.iter() {
+            graph.edge(&i.to_string(), &supported.to_string(), &[]);
+        }
+    }
+
+    Ok(graph)
+}
+
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
     let (_, mut bricks) = parse_bricks(input).expect("should parse bricks");
@@ -122,12 +152,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
     let removable = bricks
         .iter()
-        .filter(|brick| {
-            brick
-                .supports
-                .iter()
-                .all(|i| bricks[*i].supported_by.len() > 1)
-        })
+        .filter(|brick| is_removable(&bricks, brick))
         .count();
 
     Ok(removable as u64)
@@ -150,6 +175,23 @@ mod day_22_part1 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn support_graph() -> miette::Result<()> {
+        let input = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+        let graph = dot(input)?.to_string();
+        assert_eq!(9, graph.matches(" -> ").count());
+        assert_eq!(5, graph.matches("lightgreen").count());
+        assert!(graph.contains(r#""0" -> "1";"#));
+        assert!(graph.contains(r#""5" -> "6";"#));
+        Ok(())
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
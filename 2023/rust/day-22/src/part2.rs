@@ -1,13 +1,12 @@
 use crate::custom_error::AocError;
+use aoc_common::parsers::ivec3;
 use glam::IVec3;
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    bytes::complete::tag, character::complete::line_ending, multi::separated_list1,
+    sequence::separated_pair, IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Brick {
@@ -39,16 +38,6 @@ impl Brick {
     }
 }
 
-fn ivec3(input: &str) -> IResult<&str, IVec3> {
-    let (input, a) = complete::i32(input)?;
-    let (input, _) = tag(",")(input)?;
-    let (input, b) = complete::i32(input)?;
-    let (input, _) = tag(",")(input)?;
-    let (input, c) = complete::i32(input)?;
-
-    Ok((input, IVec3::from([a, b, c])))
-}
-
 fn parse_bricks(input: &str) -> IResult<&str, Vec<Brick>> {
     let (input, bricks) =
         separated_list1(line_ending, separated_pair(ivec3, tag("~"), ivec3))(input)?;
@@ -67,6 +56,7 @@ fn parse_bricks(input: &str) -> IResult<&str, Vec<Brick>> {
     ))
 }
 
+#[tracing::instrument(skip_all)]
 fn settle_bricks(bricks: &mut Vec<Brick>) -> Vec<Brick> {
     bricks
         .iter_mut()
@@ -122,6 +112,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
     let mut sum = 0;
 
+    let _chain_reactions = info_span!("chain_reactions").entered();
     for i in (0..bricks.len()).rev() {
         if bricks[i].supports.is_empty() {
             continue;
@@ -1,6 +1,7 @@
 use crate::custom_error::AocError;
 use crate::nom_locate_utils::*;
 
+use aoc_common::direction::Direction;
 use glam::IVec2;
 use nom::{
     branch::alt,
@@ -14,26 +15,6 @@ use petgraph::{algo, prelude::*};
 use std::collections::HashMap;
 use tracing::debug;
 
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn next(&self, from: &IVec2) -> IVec2 {
-        *from
-            + match self {
-                Direction::Up => IVec2::NEG_Y,
-                Direction::Down => IVec2::Y,
-                Direction::Left => IVec2::NEG_X,
-                Direction::Right => IVec2::X,
-            }
-    }
-}
-
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum MapTile {
     Ground,
@@ -52,19 +33,19 @@ fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, MapTile>> {
         alt((
             tag("^").map(with_xy).map(|span| MapTileInfo {
                 span,
-                tile_type: MapTile::Slope(Direction::Up),
+                tile_type: MapTile::Slope(Direction::North),
             }),
             tag("v").map(with_xy).map(|span| MapTileInfo {
                 span,
-                tile_type: MapTile::Slope(Direction::Down),
+                tile_type: MapTile::Slope(Direction::South),
             }),
             tag(">").map(with_xy).map(|span| MapTileInfo {
                 span,
-                tile_type: MapTile::Slope(Direction::Right),
+                tile_type: MapTile::Slope(Direction::East),
             }),
             tag("<").map(with_xy).map(|span| MapTileInfo {
                 span,
-                tile_type: MapTile::Slope(Direction::Left),
+                tile_type: MapTile::Slope(Direction::West),
             }),
             tag(".").map(with_xy).map(|span| MapTileInfo {
                 span,
@@ -108,15 +89,8 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
     grid.iter()
         .flat_map(|(pos, _tile)| {
-            vec![
-                Direction::Up,
-                Direction::Down,
-                Direction::Left,
-                Direction::Right,
-            ]
-            .into_iter()
-            .filter_map(|dir| {
-                let next_pos = dir.next(pos);
+            Direction::CARDINAL.into_iter().filter_map(|dir| {
+                let next_pos = *pos + dir;
                 grid.contains_key(&next_pos)
                     .then(|| (node_idx_map[pos], node_idx_map[&next_pos], 1))
             })
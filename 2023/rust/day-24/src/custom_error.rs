@@ -6,4 +6,8 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("the hailstones don't pin down a single trajectory for the rock")]
+    #[diagnostic(code(aoc::no_unique_trajectory))]
+    NoUniqueTrajectory,
 }
@@ -1,4 +1,5 @@
 pub mod custom_error;
+mod linalg;
 
 pub mod part1;
 pub mod part2;
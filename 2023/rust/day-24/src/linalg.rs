@@ -0,0 +1,64 @@
+use ndarray::{Array1, Array2};
+
+/// Solves `a x = b`, `None` when `a` is singular.
+#[cfg(feature = "linalg")]
+pub fn solve(a: Array2<f64>, b: Array1<f64>) -> Option<Array1<f64>> {
+    use ndarray_linalg::Solve;
+    a.solve_into(b).ok()
+}
+
+/// Solves `a x = b`, `None` when `a` is singular.
+///
+/// Gaussian elimination with partial pivoting, the same LU factorization
+/// LAPACK does without needing a BLAS toolchain to build.
+#[cfg(not(feature = "linalg"))]
+pub fn solve(mut a: Array2<f64>, mut b: Array1<f64>) -> Option<Array1<f64>> {
+    let n = b.len();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[[i, col]].abs().total_cmp(&a[[j, col]].abs()))?;
+        if a[[pivot, col]] == 0.0 {
+            return None;
+        }
+        if pivot != col {
+            for k in col..n {
+                a.swap([col, k], [pivot, k]);
+            }
+            b.swap(col, pivot);
+        }
+
+        for row in col + 1..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            for k in col..n {
+                a[[row, k]] -= factor * a[[col, k]];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = Array1::zeros(n);
+    for row in (0..n).rev() {
+        let known: f64 = (row + 1..n).map(|k| a[[row, k]] * x[k]).sum();
+        x[row] = (b[row] - known) / a[[row, row]];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test_log::test]
+    fn solves_with_pivoting() {
+        // the first pivot is zero, so the rows have to be swapped
+        let a = array![[0., 2., 1.], [1., 1., 1.], [2., 1., 3.]];
+        let b = array![5., 4., 7.];
+        let x = solve(a, b).expect("should have a solution");
+        for (x, expected) in x.iter().zip([1., 2., 1.]) {
+            assert!((x - expected).abs() < 1e-9);
+        }
+
+        assert_eq!(None, solve(array![[1., 2.], [2., 4.]], array![1., 2.]));
+    }
+}
@@ -1,14 +1,15 @@
 use std::ops::RangeInclusive;
 
 use crate::custom_error::AocError;
+use crate::linalg::solve;
+use aoc_common::parsers::i64vec3;
 use glam::{DVec2, I64Vec3, Vec3Swizzles};
 use itertools::Itertools;
-use ndarray_linalg::error::LinalgError;
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
+    character::complete::{line_ending, space1},
     multi::separated_list1,
-    sequence::{delimited, separated_pair, terminated},
+    sequence::{delimited, separated_pair},
     IResult, Parser,
 };
 use tracing::debug;
@@ -25,9 +26,8 @@ impl Hailstone {
     }
 
     // https://math.stackexchange.com/questions/406864/intersection-of-two-lines-in-vector-form
-    fn solve_intersection_xy(&self, other: &Hailstone) -> Result<(f64, f64, DVec2), LinalgError> {
+    fn solve_intersection_xy(&self, other: &Hailstone) -> Option<(f64, f64, DVec2)> {
         use ndarray::prelude::*;
-        use ndarray_linalg::Solve;
         let a: Array2<f64> = array![
             [self.direction.x as f64, -other.direction.x as f64],
             [self.direction.y as f64, -other.direction.y as f64],
@@ -38,24 +38,14 @@ impl Hailstone {
             (other.starting_position.y - self.starting_position.y) as f64,
         ];
 
-        let x = a.solve_into(b)?;
+        let x = solve(a, b)?;
         let self_scalar = x[0];
         let other_scalar = x[1];
 
-        Ok((self_scalar, other_scalar, self.at_xy(self_scalar)))
+        Some((self_scalar, other_scalar, self.at_xy(self_scalar)))
     }
 }
 
-fn i64vec3(input: &str) -> IResult<&str, I64Vec3> {
-    let (input, a) = complete::i64(input)?;
-    let (input, _) = terminated(tag(","), space1)(input)?;
-    let (input, b) = complete::i64(input)?;
-    let (input, _) = terminated(tag(","), space1)(input)?;
-    let (input, c) = complete::i64(input)?;
-
-    Ok((input, I64Vec3::from([a, b, c])))
-}
-
 fn parse_hailstones(input: &str) -> IResult<&str, Vec<Hailstone>> {
     let (input, hailstones) = separated_list1(
         line_ending,
@@ -81,7 +71,6 @@ pub fn process(input: &str, bounds: RangeInclusive<f64>) -> miette::Result<u64,
         .filter_map(|(hail_one, hail_two)| {
             hail_one
                 .solve_intersection_xy(&hail_two)
-                .ok()
                 .map(|intersection| ((hail_one, hail_two), intersection))
         })
         .filter(|(_hails, (hail_one_scalar, hail_two_scalar, coord))| {
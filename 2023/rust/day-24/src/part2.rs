@@ -1,11 +1,12 @@
 use crate::custom_error::AocError;
+use crate::linalg::solve;
+use aoc_common::parsers::i64vec3;
 use ndarray::prelude::*;
-use ndarray_linalg::Inverse;
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
+    character::complete::{line_ending, space1},
     multi::separated_list1,
-    sequence::{delimited, separated_pair, terminated},
+    sequence::{delimited, separated_pair},
     IResult, Parser,
 };
 use tracing::debug;
@@ -17,20 +18,9 @@ struct DHailstone {
 }
 
 fn array_xyz(input: &str) -> IResult<&str, Array1<f64>> {
-    let (input, a) = complete::i64(input)?;
-    let (input, _) = terminated(tag(","), space1)(input)?;
-    let (input, b) = complete::i64(input)?;
-    let (input, _) = terminated(tag(","), space1)(input)?;
-    let (input, c) = complete::i64(input)?;
-
-    Ok((
-        input,
-        array![
-            a as f64,
-            b as f64,
-            c as f64,
-        ],
-    ))
+    i64vec3
+        .map(|v| array![v.x as f64, v.y as f64, v.z as f64])
+        .parse(input)
 }
 
 fn parse_hailstones(input: &str) -> IResult<&str, Vec<DHailstone>> {
@@ -94,11 +84,8 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
             + cross_matrix(&hailstones[2].starting_position)),
     );
     debug!(?a);
-    
-    let a_inv = a.inv().unwrap();
-    debug!(?a_inv);
 
-    let result = a_inv.dot(&b);
+    let result = solve(a, b).ok_or(AocError::NoUniqueTrajectory)?;
     debug!("{:?}", result);
 
     Ok(result.slice(s![0..3]).map(|x| x.round() as u64).sum())
@@ -117,4 +104,13 @@ mod day_24_part2 {
         assert_eq!(47, process(input)?);
         Ok(())
     }
+
+    #[test_log::test]
+    fn parallel_hailstones() {
+        // any rock moving alongside them hits them all
+        let input = "0, 0, 0 @ 1, 1, 1
+5, 0, 0 @ 1, 1, 1
+0, 7, 0 @ 1, 1, 1";
+        assert!(matches!(process(input), Err(AocError::NoUniqueTrajectory)));
+    }
 }
@@ -6,4 +6,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse the puzzle input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("a wiring of {0} components can't be cut in two")]
+    #[diagnostic(code(aoc::no_cut))]
+    NoCut(usize),
 }
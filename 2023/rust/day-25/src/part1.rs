@@ -1,31 +1,26 @@
 use crate::custom_error::AocError;
+use aoc_common::{dot::Graph, parsers::adjacency};
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space1},
+    character::complete::{alpha1, line_ending},
     multi::separated_list1,
-    sequence::separated_pair,
     IResult,
 };
 use petgraph::prelude::*;
 use rustworkx_core::connectivity::stoer_wagner_min_cut;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::debug;
 
 fn parse_wiring_diagram(input: &str) -> IResult<&str, Vec<(&str, Vec<&str>)>> {
-    let (input, output) = separated_list1(
-        line_ending,
-        separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1)),
-    )(input)?;
+    let (input, output) = separated_list1(line_ending, adjacency(alpha1, alpha1))(input)?;
 
     Ok((input, output))
 }
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, wires) = parse_wiring_diagram(input).expect("should parse wiring diagram");
-    debug!(?wires);
-
+/// The wiring as a graph, along with the nodes on one side of its minimum cut.
+fn split_wiring<'a>(
+    wires: &[(&'a str, Vec<&'a str>)],
+) -> Result<(UnGraph<&'a str, u32>, Vec<NodeIndex>), AocError> {
     let nodes = wires
         .iter()
         .flat_map(|(key, values)| {
@@ -40,7 +35,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
     let node_idx_map: HashMap<&str, NodeIndex> = nodes
         .iter()
-        .map(|node| (*node, graph.add_node(&node)))
+        .map(|node| (*node, graph.add_node(node)))
         .collect();
 
     for (key, values) in wires.iter() {
@@ -51,7 +46,47 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
     let min: rustworkx_core::Result<Option<(usize, Vec<_>)>> =
         stoer_wagner_min_cut(&graph, |_| Ok(1));
-    let (_cut_size, nodes_in_partition) = min.unwrap().unwrap();
+    let (_cut_size, nodes_in_partition) = min
+        .ok()
+        .flatten()
+        .ok_or(AocError::NoCut(graph.node_count()))?;
+
+    Ok((graph, nodes_in_partition))
+}
+
+/// The wiring diagram with both halves coloured and the cut wires in red.
+pub fn dot(input: &str) -> miette::Result<Graph, AocError> {
+    let (_, wires) =
+        parse_wiring_diagram(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    let (graph, nodes_in_partition) = split_wiring(&wires)?;
+    let partition = nodes_in_partition.into_iter().collect::<HashSet<_>>();
+
+    let mut dot = Graph::undirected();
+    for node in graph.node_indices() {
+        let color = match partition.contains(&node) {
+            true => "lightblue",
+            false => "lightgreen",
+        };
+        dot.node(graph[node], &[("style", "filled"), ("fillcolor", color)]);
+    }
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        match partition.contains(&a) == partition.contains(&b) {
+            true => dot.edge(graph[a], graph[b], &[]),
+            false => dot.edge(graph[a], graph[b], &[("color", "red"), ("penwidth", "3")]),
+        };
+    }
+
+    Ok(dot)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let (_, wires) =
+        parse_wiring_diagram(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    debug!(?wires);
+
+    let (graph, nodes_in_partition) = split_wiring(&wires)?;
     let total_nodes = graph.node_count();
 
     Ok(((total_nodes - nodes_in_partition.len()) * nodes_in_partition.len()) as u64)
@@ -80,6 +115,48 @@ frs: qnr lhk lsr";
         Ok(())
     }
 
+    #[test_log::test]
+    fn cut_wires() -> miette::Result<()> {
+        let input = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+        let graph = dot(input)?.to_string();
+        let cut = graph
+            .lines()
+            .filter(|line| line.contains("color=\"red\""))
+            .map(str::trim)
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                r#""cmg" -- "bvb" [color="red", penwidth="3"];"#,
+                r#""jqt" -- "nvd" [color="red", penwidth="3"];"#,
+                r#""pzl" -- "hfx" [color="red", penwidth="3"];"#,
+            ],
+            cut
+        );
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn bad_wiring() {
+        for input in ["", "12: 34", ": abc"] {
+            assert!(matches!(process(input), Err(AocError::ParseError(_))));
+            assert!(matches!(dot(input), Err(AocError::ParseError(_))));
+        }
+        assert!(matches!(process("abc: abc"), Err(AocError::NoCut(1))));
+    }
+
     #[test_log::test]
     fn input1() -> miette::Result<()> {
         let input = include_str!("../inputs/input1.txt");
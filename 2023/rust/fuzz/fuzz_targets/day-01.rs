@@ -0,0 +1,6 @@
+use day_01::{Calibration, DIGITS, ENGLISH};
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    let _ = Calibration::new(&DIGITS).sum(data);
+    let _ = Calibration::new(&ENGLISH).sum(data);
+});
@@ -0,0 +1,13 @@
+use day_02::{parse_games, CubeSet, GameIndex, PALETTE};
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(games) = parse_games(input) {
+            let bag = CubeSet::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+            let _ = GameIndex::new(&games).possible_with(&bag).count();
+            for game in &games {
+                let _ = game.minimum_set().power(&PALETTE);
+            }
+        }
+    }
+});
@@ -0,0 +1,10 @@
+use day_03::EngineSchematic;
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(engine_schematic) = EngineSchematic::new(input) {
+            let _ = engine_schematic.part_numbers();
+            let _ = engine_schematic.gears();
+        }
+    }
+});
@@ -0,0 +1,10 @@
+use day_04::{copies, parse_cards, Card};
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(cards) = parse_cards(input) {
+            let _ = cards.iter().map(Card::matches).count();
+            let _ = copies(&cards);
+        }
+    }
+});
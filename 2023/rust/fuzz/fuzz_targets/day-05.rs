@@ -0,0 +1,29 @@
+use day_05::Almanac;
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(almanac) = Almanac::new_from_aoc_input(input) {
+            if let Ok(seed_to_location) = almanac.map_between("seed", "location") {
+                let _ = almanac
+                    .seeds
+                    .iter()
+                    .map(|&seed| seed_to_location.get(seed))
+                    .min();
+            }
+            for &seed in &almanac.seeds {
+                if let Ok(location) = almanac.lookup("seed", "location", seed) {
+                    let _ = almanac.reverse_lookup("location", "seed", location);
+                }
+            }
+        }
+        if let Ok(almanac) = Almanac::new_from_aoc_input_v2(input) {
+            if let Ok(seed_to_location) = almanac.map_between("seed", "location") {
+                let _ = almanac
+                    .seeds_v2
+                    .iter()
+                    .flat_map(|range| seed_to_location.map_range(range.clone()))
+                    .count();
+            }
+        }
+    }
+});
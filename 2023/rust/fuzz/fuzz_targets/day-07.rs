@@ -0,0 +1,14 @@
+use day_07::{parse_hands, total_winnings, HandRules};
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for rules in [
+            HandRules::camel_cards(),
+            HandRules::camel_cards_with_jokers(),
+        ] {
+            if let Ok(hands) = parse_hands(input, &rules) {
+                let _ = total_winnings(hands);
+            }
+        }
+    }
+});
@@ -0,0 +1,19 @@
+use day_08::{first_simultaneous, parse_input};
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok((directions, graph)) = parse_input(input) {
+            let _ = graph.steps_between(&directions, "AAA", "ZZZ");
+
+            let orbits = graph
+                .names
+                .iter()
+                .filter(|(_, name)| name.ends_with('A'))
+                .map(|(start, _)| graph.orbit(&directions, start, |name| name.ends_with('Z')))
+                .collect::<Result<Vec<_>, _>>();
+            if let Ok(orbits) = orbits {
+                let _ = first_simultaneous(&orbits);
+            }
+        }
+    }
+});
@@ -0,0 +1,12 @@
+use day_09::parse_readings;
+
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(readings) = parse_readings(input) {
+            for reading in &readings {
+                let _ = reading.extrapolate_forward(1);
+                let _ = reading.extrapolate_backward(1);
+            }
+        }
+    }
+});
@@ -0,0 +1,6 @@
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = day_20::part1::process(input);
+        let _ = day_20::part2::process(input);
+    }
+});
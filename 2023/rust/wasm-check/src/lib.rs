@@ -0,0 +1,2 @@
+//! Holds the test in `tests/answers.rs` checking every day's parts print the
+//! same answers compiled to `wasm32-wasip1` as they do natively.
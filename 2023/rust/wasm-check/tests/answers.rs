@@ -0,0 +1,97 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const TARGET: &str = "wasm32-wasip1";
+
+/// Left out unless asked for: day-12 brute forces part 2 for hours, where
+/// day-12-dfa solves the same puzzle in moments, day-17's part 2 search
+/// needs more than 5 GiB and day-23's walks every path for the best part of
+/// an hour.
+const SLOW_DAYS: [&str; 3] = ["day-12", "day-17", "day-23"];
+
+fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the crate should sit in the workspace")
+}
+
+/// The days in `AOC_WASM_DAYS`, separated by spaces or commas, or else all
+/// but the slow ones.
+fn days() -> Vec<String> {
+    let chosen = std::env::var("AOC_WASM_DAYS").unwrap_or_default();
+    let chosen = chosen
+        .split([' ', ','])
+        .filter(|day| !day.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if !chosen.is_empty() {
+        return chosen;
+    }
+    let mut days = fs::read_dir(workspace())
+        .expect("the workspace should be readable")
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("day-") && !SLOW_DAYS.contains(&name.as_str()))
+        .collect::<Vec<_>>();
+    days.sort();
+    days
+}
+
+/// The day's binaries, `part1` and usually `part2`.
+fn parts(day: &str) -> Vec<String> {
+    let mut parts = fs::read_dir(workspace().join(day).join("src/bin"))
+        .unwrap_or_else(|e| panic!("{day} should have binaries: {e}"))
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "rs").then(|| path.file_stem()?.to_str().map(str::to_string))?
+        })
+        .collect::<Vec<_>>();
+    parts.sort();
+    parts
+}
+
+fn stdout(command: &mut Command) -> String {
+    let output = command
+        .output()
+        .unwrap_or_else(|e| panic!("{command:?} didn't start: {e}"));
+    assert!(
+        output.status.success(),
+        "{command:?} failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("the answer should be UTF-8")
+}
+
+/// Builds both ways into a target directory of their own, as the workspace's
+/// is locked by the `cargo test` running this.
+#[test]
+#[cfg_attr(
+    not(feature = "wasm"),
+    ignore = "needs wasmtime and the wasm32-wasip1 target, run with `--features wasm`"
+)]
+fn answers_match_native() {
+    let target_dir = workspace().join("target").join("wasm-check");
+    let cargo = |subcommand: &str, day: &str| {
+        let mut command = Command::new(env!("CARGO"));
+        command
+            .current_dir(workspace())
+            .env_remove("RUST_LOG")
+            .args([subcommand, "--quiet", "--release", "--package", day])
+            .arg("--target-dir")
+            .arg(&target_dir);
+        command
+    };
+    let wasmtime = std::env::var_os("WASMTIME").unwrap_or("wasmtime".into());
+    let wasm_dir: PathBuf = target_dir.join(TARGET).join("release");
+
+    for day in days() {
+        stdout(cargo("build", &day).args(["--bins", "--target", TARGET]));
+        for part in parts(&day) {
+            let native = stdout(cargo("run", &day).args(["--bin", &part]));
+            let wasm = stdout(Command::new(&wasmtime).arg(wasm_dir.join(format!("{part}.wasm"))));
+            assert_eq!(native, wasm, "{day} {part}");
+        }
+    }
+}
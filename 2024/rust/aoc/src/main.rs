@@ -0,0 +1,115 @@
+//! Runs the 2024 days from one binary: `aoc run --all` solves every part in
+//! parallel, `aoc run <day> [<part>]` just those, `aoc list` shows which
+//! inputs are there and `aoc tui` browses them, running parts and showing
+//! their logs. Build with `--features visualize` to watch the days that draw
+//! themselves from the TUI.
+
+use std::process::ExitCode;
+
+use aoc_common::runner::{answer, Part, Registry};
+use glam::IVec2;
+
+static REGISTRY: Registry = Registry {
+    year: 2024,
+    workspace: concat!(env!("CARGO_MANIFEST_DIR"), "/.."),
+    input: "inputs/input.txt",
+    parts: &[
+        Part::new("day-01", 1, |input| answer(day_01::part1::process(input))).answer("1830467"),
+        Part::new("day-01", 2, |input| answer(day_01::part2::process(input))).answer("26674158"),
+        Part::new("day-02", 1, |input| answer(day_02::part1::process(input))).answer("202"),
+        Part::new("day-02", 2, |input| answer(day_02::part2::process(input))).answer("271"),
+        Part::new("day-03", 1, |input| answer(day_03::part1::process(input))).answer("173785482"),
+        Part::new("day-03", 2, |input| answer(day_03::part2::process(input))).answer("83158140"),
+        Part::new("day-04", 1, |input| answer(day_04::part1::process(input))).answer("2493"),
+        Part::new("day-04", 2, |input| answer(day_04::part2::process(input))).answer("1890"),
+        Part::new("day-05", 1, |input| answer(day_05::part1::process(input))).answer("4662"),
+        Part::new("day-05", 2, |input| answer(day_05::part2::process(input))).answer("5900"),
+        Part::new("day-06", 1, |input| answer(day_06::part1::process(input))).answer("4722"),
+        Part::new("day-06", 2, |input| answer(day_06::part2::process(input))).answer("1602"),
+        Part::new("day-07", 1, |input| answer(day_07::part1::process(input)))
+            .answer("850435817339"),
+        Part::new("day-07", 2, |input| answer(day_07::part2::process(input)))
+            .answer("104824810233437"),
+        Part::new("day-08", 1, |input| answer(day_08::part1::process(input))).answer("289"),
+        Part::new("day-08", 2, |input| answer(day_08::part2::process(input))).answer("1030"),
+        Part::new("day-09", 1, |input| answer(day_09::part1::process(input)))
+            .answer("6330095022244")
+            .visualize(),
+        Part::new("day-09", 2, |input| answer(day_09::part2::process(input)))
+            .answer("6359491814941")
+            .visualize(),
+        Part::new("day-10", 1, |input| answer(day_10::part1::process(input))).answer("659"),
+        Part::new("day-10", 2, |input| answer(day_10::part2::process(input))).answer("1463"),
+        Part::new("day-11", 1, |input| answer(day_11::part1::process(input))).answer("189167"),
+        Part::new("day-11", 2, |input| answer(day_11::part2::process(input)))
+            .answer("225253278506288"),
+        Part::new("day-12", 1, |input| answer(day_12::part1::process(input))).answer("1377008"),
+        Part::new("day-12", 2, |input| answer(day_12::part2::process(input))).answer("815788"),
+        Part::new("day-13", 1, |input| answer(day_13::part1::process(input))).answer("33481"),
+        Part::new("day-13", 2, |input| answer(day_13::part2::process(input)))
+            .answer("92572057880885"),
+        Part::new("day-14", 1, |input| {
+            answer(day_14::part1::process(input, IVec2::new(101, 103)))
+        })
+        .answer("229069152")
+        .visualize(),
+        Part::new("day-14", 2, |input| {
+            answer(day_14::part2::process(input, IVec2::new(101, 103)))
+        })
+        .answer("7383")
+        .visualize(),
+        Part::new("day-15", 1, |input| answer(day_15::part1::process(input)))
+            .answer("1437174")
+            .visualize(),
+        Part::new("day-15", 2, |input| answer(day_15::part2::process(input)))
+            .answer("1437468")
+            .visualize(),
+        Part::new("day-16", 1, |input| answer(day_16::part1::process(input))).answer("90440"),
+        Part::new("day-16", 2, |input| answer(day_16::part2::process(input))).answer("479"),
+        Part::new("day-17", 1, |input| answer(day_17::part1::process(input)))
+            .answer("1,5,0,5,2,0,1,3,5"),
+        Part::new("day-17", 2, |input| answer(day_17::part2::process(input)))
+            .answer("236581108670061"),
+        Part::new("day-18", 1, |input| {
+            answer(day_18::part1::process(input, IVec2::splat(70), 1024))
+        })
+        .answer("334")
+        .visualize(),
+        Part::new("day-18", 2, |input| {
+            answer(day_18::part2::process(input, IVec2::splat(70)))
+        })
+        .answer("20,12")
+        .visualize(),
+        Part::new("day-19", 1, |input| answer(day_19::part1::process(input))).answer("226"),
+        Part::new("day-19", 2, |input| answer(day_19::part2::process(input)))
+            .answer("601201576113503"),
+        Part::new("day-20", 1, |input| {
+            answer(day_20::part1::process(input, 100))
+        })
+        .answer("1365"),
+        Part::new("day-20", 2, |input| {
+            answer(day_20::part2::process(input, 100))
+        })
+        .answer("986082"),
+        Part::new("day-21", 1, |input| answer(day_21::part1::process(input))).answer("94284"),
+        Part::new("day-21", 2, |input| answer(day_21::part2::process(input)))
+            .answer("116821732384052"),
+        Part::new("day-22", 1, |input| answer(day_22::part1::process(input))).answer("16039090236"),
+        Part::new("day-22", 2, |input| answer(day_22::part2::process(input))).answer("1808"),
+        Part::new("day-23", 1, |input| answer(day_23::part1::process(input))).answer("1437"),
+        Part::new("day-23", 2, |input| {
+            answer(day_23::part2::process(input, 12))
+        })
+        .answer("da,do,gx,ly,mb,ns,nt,pz,sc,si,tp,ul,vl"),
+        Part::new("day-24", 1, |input| answer(day_24::part1::process(input)))
+            .answer("51837135476040"),
+        Part::new("day-24", 2, |input| answer(day_24::part2::process(input)))
+            .input("inputs/working-input.txt"),
+        Part::new("day-25", 1, |input| answer(day_25::part1::process(input))).answer("3090"),
+    ],
+    visualize: cfg!(feature = "visualize"),
+};
+
+fn main() -> ExitCode {
+    aoc_common::runner::main(&REGISTRY)
+}
@@ -1,7 +1,7 @@
 use crate::custom_error::AocError;
+use aoc_common::parsers::number;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
+    character::complete::{line_ending, space1},
     multi::separated_list0,
     sequence::separated_pair,
     IResult,
@@ -10,7 +10,7 @@ use nom::{
 fn parse(input: &str) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
     let (input, output) = separated_list0(
         line_ending,
-        separated_pair(complete::u64, tag("   "), complete::u64),
+        separated_pair(number::<u64>, space1, number::<u64>),
     )(input)?;
 
     Ok((input, output.into_iter().unzip()))
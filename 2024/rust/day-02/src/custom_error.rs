@@ -6,4 +6,8 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("parse failed: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
 }
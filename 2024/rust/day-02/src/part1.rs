@@ -1,7 +1,9 @@
 use crate::custom_error::AocError;
+use aoc_common::parsers::numbers;
 use itertools::Itertools;
 use nom::{
-    character::complete::{self, line_ending, multispace0, space1},
+    character::complete::{line_ending, multispace0},
+    combinator::all_consuming,
     multi::separated_list0,
     sequence::terminated,
     IResult,
@@ -16,10 +18,10 @@ enum Order {
 }
 
 fn parse(input: &str) -> IResult<&str, Vec<Vec<u64>>> {
-    terminated(
-        separated_list0(line_ending, separated_list0(space1, complete::u64)),
+    all_consuming(terminated(
+        separated_list0(line_ending, numbers),
         multispace0,
-    )(input)
+    ))(input)
 }
 
 fn is_report_safe(report: &Vec<u64>) -> bool {
@@ -36,7 +38,10 @@ fn is_report_safe(report: &Vec<u64>) -> bool {
 
     debug!(?levels);
 
-    let initial_order = levels.first().unwrap().0;
+    // a single level has nothing to be unsafe with
+    let Some(&(initial_order, _)) = levels.first() else {
+        return true;
+    };
     for (order, diff) in levels {
         match (order, diff) {
             (Order::Flat, _) => return false,
@@ -53,7 +58,7 @@ fn is_report_safe(report: &Vec<u64>) -> bool {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, reports) = parse(input).expect("should parse input");
+    let (_, reports) = parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
     debug!(?reports);
 
     let safe_reports = reports
@@ -79,6 +84,12 @@ mod day_02_part1 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn single_level_reports() -> miette::Result<()> {
+        assert_eq!(2, process("7\n1 2\n3 3")?);
+        Ok(())
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
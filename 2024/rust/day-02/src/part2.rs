@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
 use crate::custom_error::AocError;
+use aoc_common::parsers::numbers;
 use itertools::Itertools;
 use nom::{
-    character::complete::{self, line_ending, multispace0, space1},
+    character::complete::{line_ending, multispace0},
+    combinator::all_consuming,
     multi::separated_list0,
     sequence::terminated,
     IResult,
@@ -18,10 +20,10 @@ enum Order {
 }
 
 fn parse(input: &str) -> IResult<&str, Vec<Vec<u64>>> {
-    terminated(
-        separated_list0(line_ending, separated_list0(space1, complete::u64)),
+    all_consuming(terminated(
+        separated_list0(line_ending, numbers),
         multispace0,
-    )(input)
+    ))(input)
 }
 
 fn is_report_safe(report: &Vec<u64>) -> bool {
@@ -99,7 +101,7 @@ fn _is_report_safe(report: &Vec<u64>, recursion_level: usize) -> bool {
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, reports) = parse(input).expect("should parse input");
+    let (_, reports) = parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
     debug!(?reports);
 
     let safe_reports = reports
@@ -134,6 +136,16 @@ mod day_02_part2 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn malformed_reports() {
+        for input in ["1 2 x", "18446744073709551616 1"] {
+            assert!(
+                matches!(process(input), Err(AocError::ParseError(_))),
+                "{input:?}"
+            );
+        }
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
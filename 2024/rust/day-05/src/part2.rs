@@ -1,29 +1,34 @@
 use std::{cmp::Ordering, collections::HashMap};
 
 use crate::custom_error::AocError;
+use aoc_common::parsers::blank_line;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
-    multi::{fold_many1, separated_list1},
-    sequence::{separated_pair, terminated},
+    multi::separated_list1,
+    sequence::separated_pair,
     IResult,
 };
 use tracing::{debug, info};
 
 fn rules(input: &str) -> IResult<&str, HashMap<u32, Vec<u32>>> {
-    fold_many1(
-        terminated(
-            separated_pair(complete::u32, tag("|"), complete::u32),
-            line_ending,
+    let (input, rules) = separated_list1(
+        line_ending,
+        separated_pair(complete::u32, tag("|"), complete::u32),
+    )(input)?;
+
+    Ok((
+        input,
+        rules.into_iter().fold(
+            HashMap::default(),
+            |mut acc: HashMap<u32, Vec<u32>>, (page, after)| {
+                acc.entry(page)
+                    .and_modify(|afters| afters.push(after))
+                    .or_insert(vec![after]);
+                acc
+            },
         ),
-        HashMap::default,
-        |mut acc: HashMap<u32, Vec<u32>>, (page, after)| {
-            acc.entry(page)
-                .and_modify(|afters| afters.push(after))
-                .or_insert(vec![after]);
-            acc
-        },
-    )(input)
+    ))
 }
 
 fn updates(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
@@ -31,10 +36,7 @@ fn updates(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
 }
 
 fn parse(input: &str) -> IResult<&str, (HashMap<u32, Vec<u32>>, Vec<Vec<u32>>)> {
-    let (input, rules) = terminated(rules, line_ending)(input)?;
-    let (input, updates) = updates(input)?;
-
-    Ok((input, (rules, updates)))
+    separated_pair(rules, blank_line, updates)(input)
 }
 
 #[tracing::instrument(skip(input))]
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::direction::{Direction, DirectionSet};
 use glam::IVec2;
 use itertools::Itertools;
 use miette::miette;
@@ -21,15 +22,10 @@ fn token(input: Span) -> IResult<Span, (IVec2, char)> {
     Ok((input, (IVec2::new(x as i32 - 1, y as i32 - 1), token)))
 }
 
-fn parse(input: Span) -> IResult<Span, ((IVec2, char), HashMap<IVec2, char>)> {
+fn parse(input: Span) -> IResult<Span, (Option<(IVec2, char)>, HashMap<IVec2, char>)> {
     let (input, tokens) = separated_list1(line_ending, many1(token))(input)?;
 
-    let guard = tokens
-        .iter()
-        .flatten()
-        .find(|(_, c)| c == &'^')
-        .cloned()
-        .expect("should have a guard token");
+    let guard = tokens.iter().flatten().find(|(_, c)| c == &'^').cloned();
 
     let obstacles = tokens
         .into_iter()
@@ -40,38 +36,11 @@ fn parse(input: Span) -> IResult<Span, ((IVec2, char), HashMap<IVec2, char>)> {
     Ok((input, (guard, obstacles)))
 }
 
-#[derive(Debug)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-
-    fn next_pos(&self) -> IVec2 {
-        match self {
-            Direction::North => IVec2::NEG_Y,
-            Direction::East => IVec2::X,
-            Direction::South => IVec2::Y,
-            Direction::West => IVec2::NEG_X,
-        }
-    }
-}
-
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u32> {
-    let (_input, ((mut guard_pos, _), obstacles)) =
+    let (_input, (guard, obstacles)) =
         parse(Span::new(input)).map_err(|e| miette!("parse failed {}", e))?;
+    let (mut guard_pos, _) = guard.ok_or_else(|| miette!("there's no guard on the map"))?;
     debug!(?guard_pos, ?obstacles);
 
     let (x_min, x_max) = obstacles
@@ -79,20 +48,22 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .map(|(pos, _)| pos.x)
         .minmax()
         .into_option()
-        .unwrap();
+        .ok_or_else(|| miette!("there are no obstacles on the map"))?;
 
     let (y_min, y_max) = obstacles
         .iter()
         .map(|(pos, _)| pos.y)
         .minmax()
         .into_option()
-        .unwrap();
+        .ok_or_else(|| miette!("there are no obstacles on the map"))?;
 
     let mut guard_dir = Direction::North;
     let mut visited = HashSet::from([guard_pos]);
+    // a guard facing the same way somewhere twice never leaves
+    let mut headings = HashMap::from([(guard_pos, DirectionSet::from(guard_dir))]);
 
     while (x_min..=x_max).contains(&guard_pos.x) && (y_min..=y_max).contains(&guard_pos.y) {
-        let next_pos = guard_pos + guard_dir.next_pos();
+        let next_pos = guard_pos + guard_dir;
 
         if obstacles.get(&next_pos).is_some() {
             // if there's a wall in the next position, turn right
@@ -102,6 +73,10 @@ pub fn process(input: &str) -> miette::Result<u32> {
             guard_pos = next_pos;
             visited.insert(guard_pos);
         }
+
+        if !headings.entry(guard_pos).or_default().insert(guard_dir) {
+            return Err(miette!("the guard walks in a loop"));
+        }
     }
     debug!(?visited);
 
@@ -128,6 +103,13 @@ mod day_06_part1 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn guards_that_never_leave() {
+        for input in [".#.\n#^#\n.#.", ".#..\n.^.#\n#...\n..#.", "...\n.^.", "...\n.#."] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
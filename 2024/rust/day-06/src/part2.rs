@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::direction::{Direction, DirectionSet};
 use glam::IVec2;
 use itertools::Itertools;
 use miette::miette;
@@ -21,15 +22,10 @@ fn token(input: Span) -> IResult<Span, (IVec2, char)> {
     Ok((input, (IVec2::new(x as i32 - 1, y as i32 - 1), token)))
 }
 
-fn parse(input: Span) -> IResult<Span, ((IVec2, char), HashMap<IVec2, char>)> {
+fn parse(input: Span) -> IResult<Span, (Option<(IVec2, char)>, HashMap<IVec2, char>)> {
     let (input, tokens) = separated_list1(line_ending, many1(token))(input)?;
 
-    let guard = tokens
-        .iter()
-        .flatten()
-        .find(|(_, c)| c == &'^')
-        .cloned()
-        .expect("should have a guard token");
+    let guard = tokens.iter().flatten().find(|(_, c)| c == &'^').cloned();
 
     let obstacles = tokens
         .into_iter()
@@ -40,38 +36,11 @@ fn parse(input: Span) -> IResult<Span, ((IVec2, char), HashMap<IVec2, char>)> {
     Ok((input, (guard, obstacles)))
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-
-    fn next_pos(&self) -> IVec2 {
-        match self {
-            Direction::North => IVec2::NEG_Y,
-            Direction::East => IVec2::X,
-            Direction::South => IVec2::Y,
-            Direction::West => IVec2::NEG_X,
-        }
-    }
-}
-
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<u32> {
-    let (_input, ((mut guard_pos, _), obstacles)) =
+    let (_input, (guard, obstacles)) =
         parse(Span::new(input)).map_err(|e| miette!("parse failed {}", e))?;
+    let (mut guard_pos, _) = guard.ok_or_else(|| miette!("there's no guard on the map"))?;
     let initial_guard_pos = guard_pos.clone();
     debug!(?guard_pos, ?obstacles);
 
@@ -80,28 +49,40 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .map(|(pos, _)| pos.x)
         .minmax()
         .into_option()
-        .unwrap();
+        .ok_or_else(|| miette!("there are no obstacles on the map"))?;
 
     let (y_min, y_max) = obstacles
         .iter()
         .map(|(pos, _)| pos.y)
         .minmax()
         .into_option()
-        .unwrap();
+        .ok_or_else(|| miette!("there are no obstacles on the map"))?;
 
     let mut guard_dir = Direction::North;
     let mut visited = HashSet::from([guard_pos]);
+    // a guard facing the same way somewhere twice never leaves
+    let mut headings = HashMap::from([(guard_pos, DirectionSet::from(guard_dir))]);
 
     loop {
-        let next_pos = guard_pos + guard_dir.next_pos();
+        let next_pos = guard_pos + guard_dir;
 
         if obstacles.get(&next_pos).is_some() {
             // if there's a wall in the next position, turn right
             guard_dir = guard_dir.turn_right();
+            if !headings.entry(guard_pos).or_default().insert(guard_dir) {
+                return Err(miette!(
+                    "the guard walks in a loop without any new obstacle"
+                ));
+            }
         } else if (x_min..=x_max).contains(&next_pos.x) && (y_min..=y_max).contains(&next_pos.y) {
             // otherwise, if the next position is within bounds, continue in the same direction
             guard_pos = next_pos;
             visited.insert(guard_pos);
+            if !headings.entry(guard_pos).or_default().insert(guard_dir) {
+                return Err(miette!(
+                    "the guard walks in a loop without any new obstacle"
+                ));
+            }
         } else {
             break;
         }
@@ -117,18 +98,25 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .filter(|new_obstacle| {
             let mut guard_pos = initial_guard_pos.clone();
             let mut guard_dir = Direction::North;
-            let mut visited = HashSet::from([(guard_pos, guard_dir.clone())]);
+            let mut visited = HashMap::from([(guard_pos, DirectionSet::from(guard_dir))]);
 
             loop {
-                let next_pos = guard_pos + guard_dir.next_pos();
+                let next_pos = guard_pos + guard_dir;
 
                 if obstacles.get(&next_pos).is_some() || &&next_pos == new_obstacle {
-                    // if there's a wall in the next position, turn right
+                    // if there's a wall in the next position, turn right, which
+                    // loops too if the guard has faced that way here before
                     guard_dir = guard_dir.turn_right();
+                    if !visited.entry(guard_pos).or_default().insert(guard_dir) {
+                        break true;
+                    }
                     continue;
                 }
 
-                if visited.get(&(next_pos, guard_dir.clone())).is_some() {
+                if visited
+                    .get(&next_pos)
+                    .is_some_and(|directions| directions.contains(guard_dir))
+                {
                     // if we've already visited this position in this direction, we've found a loop
                     break true;
                 } else if (x_min..=x_max).contains(&next_pos.x)
@@ -136,7 +124,7 @@ pub fn process(input: &str) -> miette::Result<u32> {
                 {
                     // otherwise, if the next position is within bounds, continue in the same direction
                     guard_pos = next_pos;
-                    visited.insert((guard_pos, guard_dir.clone()));
+                    *visited.entry(guard_pos).or_default() |= guard_dir;
                     continue;
                 } else {
                     break false;
@@ -168,6 +156,13 @@ mod day_06_part2 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn guards_that_never_leave() {
+        for input in [".#.\n#^#\n.#.", ".#..\n.^.#\n#...\n..#.", "...\n.^.", "...\n.#."] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
@@ -1,21 +1,10 @@
+use aoc_common::parsers::{adjacency, number};
 use miette::miette;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
+use nom::{character::complete::line_ending, multi::separated_list1, IResult};
 use tracing::info;
 
 fn parse(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
-    separated_list1(
-        line_ending,
-        nom::sequence::separated_pair(
-            complete::u64,
-            tag(": "),
-            separated_list1(space1, complete::u64),
-        ),
-    )(input)
+    separated_list1(line_ending, adjacency(number, number))(input)
 }
 
 fn is_valid((test_value, equation): &(u64, Vec<u64>)) -> bool {
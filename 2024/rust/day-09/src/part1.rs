@@ -1,18 +1,49 @@
-use itertools::Itertools;
+use aoc_common::frame::{Cell, Color, Frame};
+use glam::IVec2;
 use tracing::debug;
 
-fn print_memory(memory: &[Option<u64>]) {
-    let memory = memory
-        .iter()
-        .map(|mem_block| {
-            if let Some(file_id) = mem_block {
-                format!("{}", file_id)
-            } else {
-                format!(".")
-            }
-        })
-        .join("");
-    debug!("{}", memory);
+struct MemoryFrame<'a> {
+    memory: &'a [Option<u64>],
+    width: usize,
+}
+
+impl<'a> MemoryFrame<'a> {
+    /// Lays the memory out in rows of `width` blocks.
+    fn new(memory: &'a [Option<u64>], width: usize) -> Self {
+        MemoryFrame { memory, width }
+    }
+}
+
+impl Frame for MemoryFrame<'_> {
+    fn size(&self) -> IVec2 {
+        IVec2::new(
+            self.width as i32,
+            self.memory.len().div_ceil(self.width) as i32,
+        )
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        const COLORS: [Color; 6] = [
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+        ];
+
+        match self
+            .memory
+            .get(position.y as usize * self.width + position.x as usize)
+        {
+            Some(Some(file_id)) => Cell::new(
+                char::from_digit((file_id % 10) as u32, 10).unwrap(),
+                COLORS[*file_id as usize % COLORS.len()],
+            ),
+            Some(None) => Cell::new('.', Color::DarkGrey),
+            None => ' '.into(),
+        }
+    }
 }
 
 #[tracing::instrument(skip(input))]
@@ -33,7 +64,13 @@ pub fn process(input: &str) -> miette::Result<u64> {
         .flatten()
         .collect::<Vec<_>>();
 
-    print_memory(&expanded_memory);
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+
+    debug!(
+        "{}",
+        MemoryFrame::new(&expanded_memory, expanded_memory.len()).render_plain()
+    );
 
     // move the right-most memory blocks to the left-most free space available
     let file_blocks = expanded_memory
@@ -50,8 +87,16 @@ pub fn process(input: &str) -> miette::Result<u64> {
             }
         }
         idx += 1;
+
+        #[cfg(feature = "visualize")]
+        if idx % 100 == 0 {
+            player.show(&MemoryFrame::new(&expanded_memory, 100));
+        }
     }
-    print_memory(&expanded_memory);
+    debug!(
+        "{}",
+        MemoryFrame::new(&expanded_memory, expanded_memory.len()).render_plain()
+    );
 
     // compute the file system checksum
     let result = expanded_memory
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc_common::direction::Direction;
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -11,8 +12,6 @@ use nom_locate::LocatedSpan;
 use tracing::debug;
 
 type Span<'a> = LocatedSpan<&'a str>;
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
-
 fn num_pos(input: Span) -> IResult<Span, (IVec2, u32)> {
     let x = input.get_column() as i32 - 1;
     let y = input.location_line() as i32 - 1;
@@ -27,9 +26,9 @@ fn parse(input: Span) -> IResult<Span, HashMap<IVec2, u32>> {
 
 fn search_trail(map: &HashMap<IVec2, u32>, trailhead: IVec2) -> HashMap<IVec2, u32> {
     let mut trail = HashMap::from([(trailhead, 0u32)]);
-    let mut stack: Vec<(IVec2, i32)> = DIRECTIONS
+    let mut stack: Vec<(IVec2, i32)> = Direction::CARDINAL
         .iter()
-        .map(|dir| (trailhead + dir, 0i32))
+        .map(|dir| (trailhead + *dir, 0i32))
         .collect();
 
     while let Some((pos, from_value)) = stack.pop() {
@@ -37,7 +36,7 @@ fn search_trail(map: &HashMap<IVec2, u32>, trailhead: IVec2) -> HashMap<IVec2, u
             let diff = *value as i32 - from_value;
             if diff == 1 {
                 trail.entry(pos).or_insert(*value);
-                for dir in DIRECTIONS.iter() {
+                for dir in Direction::CARDINAL {
                     if !trail.contains_key(&(pos + dir)) {
                         stack.push((pos + dir, *value as i32));
                     }
@@ -49,18 +48,19 @@ fn search_trail(map: &HashMap<IVec2, u32>, trailhead: IVec2) -> HashMap<IVec2, u
     trail
 }
 
-fn print_trail(trail: &HashMap<IVec2, u32>, height: usize, width: usize) {
+fn trail_to_string(trail: &HashMap<IVec2, u32>, height: usize, width: usize) -> String {
+    let mut output = String::with_capacity((width + 1) * height);
     for y in 0..height {
         for x in 0..width {
             let pos = IVec2::new(x as i32, y as i32);
-            if let Some(value) = trail.get(&pos) {
-                print!("{}", value);
-            } else {
-                print!(".");
+            match trail.get(&pos) {
+                Some(value) => output.push_str(&value.to_string()),
+                None => output.push('.'),
             }
         }
-        println!();
+        output.push('\n');
     }
+    output
 }
 
 #[tracing::instrument(skip(input))]
@@ -81,7 +81,11 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .iter()
         .map(|trailhead| {
             let trail = search_trail(&map, *trailhead);
-            print_trail(&trail, height, width);
+            debug!(
+                "trail from {}\n{}",
+                trailhead,
+                trail_to_string(&trail, height, width)
+            );
 
             let score = trail.iter().filter(|(_, value)| **value == 9u32).count() as u32;
             debug!("trail score: {}", score);
@@ -96,6 +100,7 @@ pub fn process(input: &str) -> miette::Result<u32> {
 #[cfg(test)]
 mod day_10_part1 {
     use super::*;
+    use itertools::Itertools;
 
     #[test_log::test]
     fn example() -> miette::Result<()> {
@@ -111,6 +116,31 @@ mod day_10_part1 {
         Ok(())
     }
 
+    #[test_log::test]
+    fn example_trails() {
+        let input = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+        let (_, map) = parse(Span::new(input)).unwrap();
+
+        let trails = map
+            .iter()
+            .filter(|(_, &v)| v == 0)
+            .map(|(&trailhead, _)| trailhead)
+            .sorted_by_key(|trailhead| (trailhead.y, trailhead.x))
+            .map(|trailhead| {
+                let trail = search_trail(&map, trailhead);
+                format!("{trailhead}\n{}", trail_to_string(&trail, 8, 8))
+            })
+            .join("\n");
+        insta::assert_snapshot!(trails);
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
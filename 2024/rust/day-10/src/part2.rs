@@ -1,5 +1,6 @@
 use std::{collections::HashMap, usize};
 
+use aoc_common::direction::Direction;
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -12,8 +13,6 @@ use pathfinding::prelude::count_paths;
 use tracing::debug;
 
 type Span<'a> = LocatedSpan<&'a str>;
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
-
 fn num_pos(input: Span) -> IResult<Span, (IVec2, u32)> {
     let x = input.get_column() as i32 - 1;
     let y = input.location_line() as i32 - 1;
@@ -28,9 +27,9 @@ fn parse(input: Span) -> IResult<Span, HashMap<IVec2, u32>> {
 
 fn search_trail(map: &HashMap<IVec2, u32>, trailhead: IVec2) -> HashMap<IVec2, u32> {
     let mut trail = HashMap::from([(trailhead, 0u32)]);
-    let mut stack: Vec<(IVec2, i32)> = DIRECTIONS
+    let mut stack: Vec<(IVec2, i32)> = Direction::CARDINAL
         .iter()
-        .map(|dir| (trailhead + dir, 0i32))
+        .map(|dir| (trailhead + *dir, 0i32))
         .collect();
 
     while let Some((pos, from_value)) = stack.pop() {
@@ -38,7 +37,7 @@ fn search_trail(map: &HashMap<IVec2, u32>, trailhead: IVec2) -> HashMap<IVec2, u
             let diff = *value as i32 - from_value;
             if diff == 1 {
                 trail.entry(pos).or_insert(*value);
-                for dir in DIRECTIONS.iter() {
+                for dir in Direction::CARDINAL {
                     if !trail.contains_key(&(pos + dir)) {
                         stack.push((pos + dir, *value as i32));
                     }
@@ -57,10 +56,10 @@ fn trail_rating(map: &HashMap<IVec2, u32>, trailhead: &IVec2, endings: &[IVec2])
             count_paths(
                 *trailhead,
                 |pos| {
-                    DIRECTIONS
+                    Direction::CARDINAL
                         .iter()
                         .zip(std::iter::repeat(*pos))
-                        .map(|(dir, pos)| (dir + pos, pos))
+                        .map(|(dir, pos)| (pos + *dir, pos))
                         .filter(|(new_pos, from_pos)| {
                             map.get(new_pos).is_some_and(|h| {
                                 let current_height = map.get(from_pos).unwrap();
@@ -76,18 +75,19 @@ fn trail_rating(map: &HashMap<IVec2, u32>, trailhead: &IVec2, endings: &[IVec2])
         .sum::<usize>() as u32
 }
 
-fn print_trail(trail: &HashMap<IVec2, u32>, height: usize, width: usize) {
+fn trail_to_string(trail: &HashMap<IVec2, u32>, height: usize, width: usize) -> String {
+    let mut output = String::with_capacity((width + 1) * height);
     for y in 0..height {
         for x in 0..width {
             let pos = IVec2::new(x as i32, y as i32);
-            if let Some(value) = trail.get(&pos) {
-                print!("{}", value);
-            } else {
-                print!(".");
+            match trail.get(&pos) {
+                Some(value) => output.push_str(&value.to_string()),
+                None => output.push('.'),
             }
         }
-        println!();
+        output.push('\n');
     }
+    output
 }
 
 #[tracing::instrument(skip(input))]
@@ -108,7 +108,11 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .iter()
         .map(|trailhead| {
             let trail = search_trail(&map, *trailhead);
-            print_trail(&trail, height, width);
+            debug!(
+                "trail from {}\n{}",
+                trailhead,
+                trail_to_string(&trail, height, width)
+            );
 
             let endings = trail
                 .iter()
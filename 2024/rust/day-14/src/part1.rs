@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
+use aoc_common::{
+    frame::{Cell, Color, Frame},
+    parsers::ivec2,
+};
 use glam::IVec2;
+use itertools::Itertools;
 use miette::miette;
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
+    character::complete::{line_ending, space1},
     multi::separated_list1,
     sequence::{preceded, separated_pair},
     IResult, Parser,
@@ -15,52 +22,81 @@ struct Robot {
     velocity: IVec2,
 }
 
-fn parse_ivec2(input: &str) -> IResult<&str, IVec2> {
-    let (input, (x, y)) = separated_pair(complete::i32, tag(","), complete::i32)(input)?;
-    Ok((input, IVec2::new(x, y)))
-}
-
 fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
     separated_list1(
         line_ending,
         separated_pair(
-            preceded(tag("p="), parse_ivec2),
+            preceded(tag("p="), ivec2),
             space1,
-            preceded(tag("v="), parse_ivec2),
+            preceded(tag("v="), ivec2),
         )
         .map(|(position, velocity)| Robot { position, velocity }),
     )(input)
 }
 
-fn robots_map_to_string(robots: &[Robot], map_size: &IVec2) -> String {
-    let mut str: String = String::from("\n");
-    for y in 0..map_size.y {
-        for x in 0..map_size.x {
-            match robots
-                .iter()
-                .filter(|Robot { position, .. }| position.x == x && position.y == y)
-                .count()
-            {
-                0 => str += ".",
-                n => str += &format!("{}", n),
-            }
+struct RobotsFrame {
+    robots: HashMap<IVec2, usize>,
+    map_size: IVec2,
+    seconds: u32,
+}
+
+impl RobotsFrame {
+    fn new(robots: &[Robot], map_size: IVec2, seconds: u32) -> Self {
+        RobotsFrame {
+            robots: robots.iter().map(|robot| robot.position).counts(),
+            map_size,
+            seconds,
         }
-        str += "\n"
     }
-    str
+}
+
+impl Frame for RobotsFrame {
+    fn size(&self) -> IVec2 {
+        self.map_size
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        match self.robots.get(&position) {
+            None => '.'.into(),
+            Some(n) => Cell::new(char::from_digit(*n as u32, 10).unwrap_or('+'), Color::Green),
+        }
+    }
+
+    fn caption(&self) -> String {
+        format!("after {}s", self.seconds)
+    }
 }
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str, map_size: IVec2) -> miette::Result<u32> {
     let (_input, mut robots) = parse(input).map_err(|e| miette!("parse failed: {}", e))?;
 
-    debug!("Starting map {}", robots_map_to_string(&robots, &map_size));
+    debug!(
+        "Starting map\n{}",
+        RobotsFrame::new(&robots, map_size, 0).render_plain()
+    );
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+    #[cfg(feature = "export")]
+    let mut exporter =
+        aoc_common::export::Exporter::from_env().map_err(|e| miette!("export failed: {}", e))?;
     for _i in 0..100 {
         for robot in robots.iter_mut() {
             robot.position = (robot.position + robot.velocity).rem_euclid(map_size);
         }
+        #[cfg(feature = "visualize")]
+        player.show(&RobotsFrame::new(&robots, map_size, _i + 1));
+        #[cfg(feature = "export")]
+        if let Some(exporter) = &mut exporter {
+            exporter
+                .push("day-14-part1", &RobotsFrame::new(&robots, map_size, _i + 1))
+                .map_err(|e| miette!("export failed: {}", e))?;
+        }
     }
-    debug!("After 100s {}", robots_map_to_string(&robots, &map_size));
+    debug!(
+        "After 100s\n{}",
+        RobotsFrame::new(&robots, map_size, 100).render_plain()
+    );
 
     let middles = map_size / 2;
     let quadrants = [
@@ -1,8 +1,12 @@
 use std::{
     collections::HashMap,
-    fmt::{self, Display, Write},
+    fmt::{self, Display},
 };
 
+use aoc_common::{
+    direction::Direction,
+    frame::{Cell, Color, Frame},
+};
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -40,26 +44,40 @@ impl Display for Tile {
     }
 }
 
-fn map_to_string(tiles: &HashMap<IVec2, Tile>) -> Result<String, fmt::Error> {
-    let map_size = IVec2::new(
-        tiles.keys().map(|pos| pos.x).max().unwrap(),
-        tiles.keys().map(|pos| pos.y).max().unwrap(),
-    );
-    let mut output = String::from("\n");
-    for y in 0..=map_size.y {
-        for x in 0..=map_size.x {
-            match tiles.get(&IVec2::new(x, y)) {
-                Some(tile) => {
-                    write!(&mut output, "{tile}")?;
-                }
-                None => {
-                    write!(&mut output, ".",)?;
-                }
-            }
+struct MapFrame<'a> {
+    tiles: &'a HashMap<IVec2, Tile>,
+    direction: Option<IVec2>,
+}
+
+impl<'a> MapFrame<'a> {
+    fn new(tiles: &'a HashMap<IVec2, Tile>, direction: Option<IVec2>) -> Self {
+        MapFrame { tiles, direction }
+    }
+}
+
+impl Frame for MapFrame<'_> {
+    fn size(&self) -> IVec2 {
+        IVec2::new(
+            self.tiles.keys().map(|pos| pos.x).max().unwrap(),
+            self.tiles.keys().map(|pos| pos.y).max().unwrap(),
+        ) + IVec2::ONE
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        match self.tiles.get(&position) {
+            Some(Tile::Wall) => Cell::new('#', Color::DarkGrey),
+            Some(Tile::Box) => Cell::new('O', Color::Yellow),
+            Some(Tile::Robot) => Cell::new('@', Color::Red),
+            None => '.'.into(),
+        }
+    }
+
+    fn caption(&self) -> String {
+        match self.direction.map(Direction::try_from) {
+            Some(Ok(direction)) => format!("moving {}", direction.glyph()),
+            _ => String::new(),
         }
-        writeln!(&mut output)?;
     }
-    Ok(output)
 }
 
 fn parse_tile(input: Span) -> IResult<Span, (IVec2, Tile)> {
@@ -96,14 +114,32 @@ fn parse(input: Span) -> IResult<Span, (HashMap<IVec2, Tile>, Vec<IVec2>)> {
     Ok((input, (hashmap, directions)))
 }
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<u32> {
+/// Moves the robot around, returning the warehouse once it's done.
+fn simulate(input: &str) -> miette::Result<HashMap<IVec2, Tile>> {
     let (_input, (mut map, directions)) =
         parse(Span::new(input)).map_err(|e| miette!("parse failed {}", e))?;
+    let robots = map.values().filter(|tile| tile == &&Tile::Robot).count();
+    if robots != 1 {
+        return Err(miette!("there should be one robot, found {}", robots));
+    }
 
-    debug!("Starting map {}", map_to_string(&map).unwrap());
+    debug!("Starting map\n{}", MapFrame::new(&map, None).render_plain());
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+    #[cfg(feature = "export")]
+    let mut exporter =
+        aoc_common::export::Exporter::from_env().map_err(|e| miette!("export failed: {}", e))?;
 
     for direction in directions {
+        #[cfg(feature = "visualize")]
+        player.show(&MapFrame::new(&map, Some(direction)));
+        #[cfg(feature = "export")]
+        if let Some(exporter) = &mut exporter {
+            exporter
+                .push("day-15-part1", &MapFrame::new(&map, Some(direction)))
+                .map_err(|e| miette!("export failed: {}", e))?;
+        }
+
         let robot_pos = map
             .iter()
             .find(|(_, tile)| tile == &&Tile::Robot)
@@ -120,9 +156,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
             map.insert(next_pos, robot);
 
             debug!(
-                "move={}, map after empty space {}",
+                "move={}, map after empty space\n{}",
                 direction,
-                map_to_string(&map).unwrap()
+                MapFrame::new(&map, Some(direction)).render_plain()
             );
             continue;
         };
@@ -130,9 +166,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
         match next {
             Tile::Wall => {
                 debug!(
-                    "move={}, map after wall {}",
+                    "move={}, map after wall\n{}",
                     direction,
-                    map_to_string(&map).unwrap()
+                    MapFrame::new(&map, Some(direction)).render_plain()
                 );
                 continue;
             }
@@ -169,9 +205,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
                     }
 
                     debug!(
-                        "move={}, map after box {}",
+                        "move={}, map after box\n{}",
                         direction,
-                        map_to_string(&map).unwrap()
+                        MapFrame::new(&map, Some(direction)).render_plain()
                     );
                     continue;
                 };
@@ -182,6 +218,23 @@ pub fn process(input: &str) -> miette::Result<u32> {
         }
     }
 
+    #[cfg(feature = "visualize")]
+    player.show(&MapFrame::new(&map, None));
+    #[cfg(feature = "export")]
+    if let Some(exporter) = &mut exporter {
+        exporter
+            .push("day-15-part1", &MapFrame::new(&map, None))
+            .and_then(|_| exporter.still("day-15-part1", &MapFrame::new(&map, None)))
+            .map_err(|e| miette!("export failed: {}", e))?;
+    }
+
+    Ok(map)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> miette::Result<u32> {
+    let map = simulate(input)?;
+
     let result: i32 = map
         .iter()
         .filter(|(_, tile)| tile == &&Tile::Box)
@@ -194,6 +247,8 @@ pub fn process(input: &str) -> miette::Result<u32> {
 #[cfg(test)]
 mod day_15_part1 {
     use super::*;
+    use crate::testing::{rendered, warehouses};
+    use proptest::prelude::*;
 
     #[test_log::test]
     fn small_example() -> miette::Result<()> {
@@ -208,6 +263,7 @@ mod day_15_part1 {
 
 <^^>>>vv<v>>v<<";
         assert_eq!(2028, process(input)?);
+        insta::assert_snapshot!(MapFrame::new(&simulate(input)?, None).render_plain());
         Ok(())
     }
 
@@ -235,6 +291,7 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         assert_eq!(10092, process(input)?);
+        insta::assert_snapshot!(MapFrame::new(&simulate(input)?, None).render_plain());
         Ok(())
     }
 
@@ -244,4 +301,21 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         assert_eq!(1437174, process(input)?);
         Ok(())
     }
+
+    #[test_log::test]
+    fn warehouses_without_one_robot() {
+        for input in ["#..#\n\n<", "#@@#\n\n<", "#@O@#\n\n>>"] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn tiles_round_trip(warehouse in warehouses(&[".", "#", "O", "@"])) {
+            let input = format!("{warehouse}\n\n<");
+
+            let (_, (tiles, _)) = parse(Span::new(&input)).unwrap();
+            prop_assert_eq!(rendered(&tiles, &warehouse), warehouse);
+        }
+    }
 }
@@ -1,8 +1,12 @@
 use std::{
     collections::{HashMap, HashSet},
-    fmt::{self, Display, Write},
+    fmt::{self, Display},
 };
 
+use aoc_common::{
+    direction::Direction,
+    frame::{Cell, Color, Frame},
+};
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -42,26 +46,41 @@ impl Display for Tile {
     }
 }
 
-fn map_to_string(tiles: &HashMap<IVec2, Tile>) -> Result<String, fmt::Error> {
-    let map_size = IVec2::new(
-        tiles.keys().map(|pos| pos.x).max().unwrap(),
-        tiles.keys().map(|pos| pos.y).max().unwrap(),
-    );
-    let mut output = String::from("\n");
-    for y in 0..=map_size.y {
-        for x in 0..=map_size.x {
-            match tiles.get(&IVec2::new(x, y)) {
-                Some(tile) => {
-                    write!(&mut output, "{tile}")?;
-                }
-                None => {
-                    write!(&mut output, ".",)?;
-                }
-            }
+struct MapFrame<'a> {
+    tiles: &'a HashMap<IVec2, Tile>,
+    direction: Option<IVec2>,
+}
+
+impl<'a> MapFrame<'a> {
+    fn new(tiles: &'a HashMap<IVec2, Tile>, direction: Option<IVec2>) -> Self {
+        MapFrame { tiles, direction }
+    }
+}
+
+impl Frame for MapFrame<'_> {
+    fn size(&self) -> IVec2 {
+        IVec2::new(
+            self.tiles.keys().map(|pos| pos.x).max().unwrap(),
+            self.tiles.keys().map(|pos| pos.y).max().unwrap(),
+        ) + IVec2::ONE
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        match self.tiles.get(&position) {
+            Some(Tile::Wall) => Cell::new('#', Color::DarkGrey),
+            Some(Tile::BoxLeft) => Cell::new('[', Color::Yellow),
+            Some(Tile::BoxRight) => Cell::new(']', Color::Yellow),
+            Some(Tile::Robot) => Cell::new('@', Color::Red),
+            None => '.'.into(),
+        }
+    }
+
+    fn caption(&self) -> String {
+        match self.direction.map(Direction::try_from) {
+            Some(Ok(direction)) => format!("moving {}", direction.glyph()),
+            _ => String::new(),
         }
-        writeln!(&mut output)?;
     }
-    Ok(output)
 }
 
 fn parse_tile(input: Span) -> IResult<Span, (IVec2, Tile)> {
@@ -195,8 +214,14 @@ fn horizontal(map: &mut HashMap<IVec2, Tile>, next_pos: IVec2, direction: IVec2,
     };
 }
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<u32> {
+/// Moves the robot around, returning the warehouse once it's done.
+fn simulate(input: &str) -> miette::Result<HashMap<IVec2, Tile>> {
+    // boxes only come in whole pairs when they're widened from `O`
+    if input.contains(['[', ']']) {
+        return Err(miette!(
+            "the warehouse should be given at its original width"
+        ));
+    }
     let new_input = input
         .chars()
         .map(|c| match c {
@@ -209,10 +234,28 @@ pub fn process(input: &str) -> miette::Result<u32> {
         .collect::<String>();
     let (_input, (mut map, directions)) =
         parse(Span::new(&new_input)).map_err(|e| miette!("parse failed {}", e))?;
+    let robots = map.values().filter(|tile| tile == &&Tile::Robot).count();
+    if robots != 1 {
+        return Err(miette!("there should be one robot, found {}", robots));
+    }
 
-    debug!("Starting map {}", map_to_string(&map).unwrap());
+    debug!("Starting map\n{}", MapFrame::new(&map, None).render_plain());
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+    #[cfg(feature = "export")]
+    let mut exporter =
+        aoc_common::export::Exporter::from_env().map_err(|e| miette!("export failed: {}", e))?;
 
     for direction in directions {
+        #[cfg(feature = "visualize")]
+        player.show(&MapFrame::new(&map, Some(direction)));
+        #[cfg(feature = "export")]
+        if let Some(exporter) = &mut exporter {
+            exporter
+                .push("day-15-part2", &MapFrame::new(&map, Some(direction)))
+                .map_err(|e| miette!("export failed: {}", e))?;
+        }
+
         let robot_pos = map
             .iter()
             .find(|(_, tile)| tile == &&Tile::Robot)
@@ -229,9 +272,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
             map.insert(next_pos, robot);
 
             debug!(
-                "move={}, map after empty space {}",
+                "move={}, map after empty space\n{}",
                 direction,
-                map_to_string(&map).unwrap()
+                MapFrame::new(&map, Some(direction)).render_plain()
             );
             continue;
         };
@@ -239,9 +282,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
         match next {
             Tile::Wall => {
                 debug!(
-                    "move={}, map after wall {}",
+                    "move={}, map after wall\n{}",
                     direction,
-                    map_to_string(&map).unwrap()
+                    MapFrame::new(&map, Some(direction)).render_plain()
                 );
                 continue;
             }
@@ -254,9 +297,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
                     horizontal(&mut map, next_pos, direction, robot_pos)
                 }
                 debug!(
-                    "move={}, map after box-left {}",
+                    "move={}, map after box-left\n{}",
                     direction,
-                    map_to_string(&map).unwrap()
+                    MapFrame::new(&map, Some(direction)).render_plain()
                 );
             }
             Tile::BoxRight => {
@@ -268,9 +311,9 @@ pub fn process(input: &str) -> miette::Result<u32> {
                     horizontal(&mut map, next_pos, direction, robot_pos)
                 }
                 debug!(
-                    "move={}, map after box-right {}",
+                    "move={}, map after box-right\n{}",
                     direction,
-                    map_to_string(&map).unwrap()
+                    MapFrame::new(&map, Some(direction)).render_plain()
                 );
             }
             Tile::Robot => {
@@ -279,6 +322,23 @@ pub fn process(input: &str) -> miette::Result<u32> {
         }
     }
 
+    #[cfg(feature = "visualize")]
+    player.show(&MapFrame::new(&map, None));
+    #[cfg(feature = "export")]
+    if let Some(exporter) = &mut exporter {
+        exporter
+            .push("day-15-part2", &MapFrame::new(&map, None))
+            .and_then(|_| exporter.still("day-15-part2", &MapFrame::new(&map, None)))
+            .map_err(|e| miette!("export failed: {}", e))?;
+    }
+
+    Ok(map)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> miette::Result<u32> {
+    let map = simulate(input)?;
+
     let result: i32 = map
         .iter()
         .filter(|(_, tile)| tile == &&Tile::BoxLeft)
@@ -291,6 +351,8 @@ pub fn process(input: &str) -> miette::Result<u32> {
 #[cfg(test)]
 mod day_15_part2 {
     use super::*;
+    use crate::testing::{rendered, warehouses};
+    use proptest::prelude::*;
 
     #[test_log::test]
     fn big_example() -> miette::Result<()> {
@@ -316,6 +378,7 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         assert_eq!(9021, process(input)?);
+        insta::assert_snapshot!(MapFrame::new(&simulate(input)?, None).render_plain());
         Ok(())
     }
 
@@ -325,4 +388,21 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         assert_eq!(1437468, process(input)?);
         Ok(())
     }
+
+    #[test_log::test]
+    fn warehouses_without_one_robot() {
+        for input in ["#[]@#\n\n<", "#..#\n\n<", "#@@#\n\n<", "#@O@#\n\n>>"] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn tiles_round_trip(warehouse in warehouses(&[".", "#", "[]", "@"])) {
+            let input = format!("{warehouse}\n\n<");
+
+            let (_, (tiles, _)) = parse(Span::new(&input)).unwrap();
+            prop_assert_eq!(rendered(&tiles, &warehouse), warehouse);
+        }
+    }
 }
@@ -0,0 +1,39 @@
+//! Test helpers shared by both parts.
+
+use std::{collections::HashMap, fmt::Display};
+
+use glam::IVec2;
+use itertools::Itertools;
+use proptest::prelude::*;
+
+/// Draws the tiles over a warehouse the size of `warehouse`, with `.` where
+/// there's nothing.
+pub fn rendered<T: Display>(tiles: &HashMap<IVec2, T>, warehouse: &str) -> String {
+    warehouse
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            (0..line.len())
+                .map(|x| {
+                    tiles
+                        .get(&IVec2::new(x as i32, y as i32))
+                        .map_or(".".to_string(), T::to_string)
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// Warehouses made of the given tiles, with walls on both ends of every row
+/// like the puzzle input.
+pub fn warehouses(tiles: &'static [&'static str]) -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop::collection::vec(prop::sample::select(tiles), 0..12),
+        1..8,
+    )
+    .prop_map(|rows| {
+        rows.iter()
+            .map(|row| format!("#{}#", row.concat()))
+            .join("\n")
+    })
+}
@@ -3,7 +3,7 @@ use miette::miette;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending, multispace1},
-    combinator::{all_consuming, opt},
+    combinator::{all_consuming, map_opt, opt},
     multi::separated_list1,
     sequence::delimited,
     IResult,
@@ -37,48 +37,58 @@ fn parse_register(input: &str) -> IResult<&str, Register> {
     Ok((input, Register { a, b, c }))
 }
 
+/// `None` unless the numbers pair up into opcodes and operands.
+fn program(numbers: Vec<i32>) -> Option<Vec<Instruction>> {
+    numbers
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [0, b] => Some(Instruction::Adv(b)),
+            [1, b] => Some(Instruction::Bxl(b)),
+            [2, b] => Some(Instruction::Bst(b)),
+            [3, b] => Some(Instruction::Jnz(b)),
+            [4, b] => Some(Instruction::Bxc(b)),
+            [5, b] => Some(Instruction::Out(b)),
+            [6, b] => Some(Instruction::Bdv(b)),
+            [7, b] => Some(Instruction::Cdv(b)),
+            _ => None,
+        })
+        .collect()
+}
+
 fn parse(input: &str) -> IResult<&str, (Register, Vec<Instruction>)> {
     let (input, register) = parse_register(input)?;
     let (input, _) = multispace1(input)?;
 
     let (input, _) = tag("Program: ")(input)?;
-    let (input, program) = separated_list1(tag(","), complete::i32)(input)?;
+    let (input, program) = map_opt(separated_list1(tag(","), complete::i32), program)(input)?;
     let (input, _) = opt(line_ending)(input)?;
 
-    let program = program
-        .into_iter()
-        .chunks(2)
-        .into_iter()
-        .map(|mut chunk| {
-            let (a, b) = (chunk.next().unwrap(), chunk.next().unwrap());
-            match a {
-                0 => Instruction::Adv(b),
-                1 => Instruction::Bxl(b),
-                2 => Instruction::Bst(b),
-                3 => Instruction::Jnz(b),
-                4 => Instruction::Bxc(b),
-                5 => Instruction::Out(b),
-                6 => Instruction::Bdv(b),
-                7 => Instruction::Cdv(b),
-                _ => panic!("invalid instruction"),
-            }
-        })
-        .collect();
-
     Ok((input, (register, program)))
 }
 
-fn combo_op_value(register: &Register, combo_op: i32) -> i32 {
+fn combo_op_value(register: &Register, combo_op: i32) -> miette::Result<i32> {
     match combo_op {
-        0..=3 => combo_op,
-        4 => register.a,
-        5 => register.b,
-        6 => register.c,
-        7 => unreachable!("7 is reserved"),
-        _ => panic!("combo operand out of range"),
+        0..=3 => Ok(combo_op),
+        4 => Ok(register.a),
+        5 => Ok(register.b),
+        6 => Ok(register.c),
+        7 => Err(miette!("combo operand 7 is reserved")),
+        _ => Err(miette!("combo operand {} out of range", combo_op)),
     }
 }
 
+/// `numerator / 2^exponent`, which is 0 once `2^exponent` doesn't fit.
+fn divide(numerator: i32, exponent: i32) -> miette::Result<i32> {
+    let exponent =
+        u32::try_from(exponent).map_err(|_| miette!("can't divide by 2^{}", exponent))?;
+    Ok(2i32
+        .checked_pow(exponent)
+        .map_or(0, |denominator| numerator / denominator))
+}
+
+/// Instructions to run before deciding the program never halts.
+const MAX_STEPS: usize = 1 << 20;
+
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String> {
     let (_input, (mut register, program)) =
@@ -87,12 +97,17 @@ pub fn process(input: &str) -> miette::Result<String> {
 
     let mut output: Vec<i32> = Vec::new();
     let mut i = 0;
+    let mut steps = 0;
 
     while i < program.len() {
+        steps += 1;
+        if steps > MAX_STEPS {
+            return Err(miette!("program didn't halt after {} steps", MAX_STEPS));
+        }
         debug!("register={:?}, i={}, program={:?}", register, i, program[i]);
         match program[i] {
             Instruction::Adv(combo_op) => {
-                register.a = register.a / 2i32.pow(combo_op_value(&register, combo_op) as u32);
+                register.a = divide(register.a, combo_op_value(&register, combo_op)?)?;
                 i += 1;
             }
             Instruction::Bxl(literal_op) => {
@@ -100,7 +115,7 @@ pub fn process(input: &str) -> miette::Result<String> {
                 i += 1;
             }
             Instruction::Bst(combo_op) => {
-                register.b = combo_op_value(&register, combo_op) % 8;
+                register.b = combo_op_value(&register, combo_op)? % 8;
                 i += 1;
             }
             Instruction::Jnz(literal_op) => match register.a {
@@ -116,15 +131,15 @@ pub fn process(input: &str) -> miette::Result<String> {
                 i += 1;
             }
             Instruction::Out(combo_op) => {
-                output.push(combo_op_value(&register, combo_op) % 8);
+                output.push(combo_op_value(&register, combo_op)? % 8);
                 i += 1;
             }
             Instruction::Bdv(combo_op) => {
-                register.b = register.a / 2i32.pow(combo_op_value(&register, combo_op) as u32);
+                register.b = divide(register.a, combo_op_value(&register, combo_op)?)?;
                 i += 1;
             }
             Instruction::Cdv(combo_op) => {
-                register.c = register.a / 2i32.pow(combo_op_value(&register, combo_op) as u32);
+                register.c = divide(register.a, combo_op_value(&register, combo_op)?)?;
                 i += 1;
             }
         }
@@ -138,6 +153,7 @@ pub fn process(input: &str) -> miette::Result<String> {
 #[cfg(test)]
 mod day_17_part1 {
     use super::*;
+    use rstest::rstest;
 
     #[test_log::test]
     fn small_example1() -> miette::Result<()> {
@@ -172,6 +188,28 @@ Program: 0,1,5,4,3,0";
         Ok(())
     }
 
+    #[rstest]
+    #[case::invalid_opcode("8,0")]
+    #[case::missing_operand("0,1,5")]
+    #[case::reserved_combo_operand("5,7")]
+    #[case::combo_operand_out_of_range("5,9")]
+    #[case::never_halts("3,0")]
+    fn malformed_program(#[case] program: &str) {
+        let input = format!("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {program}");
+        assert!(process(&input).is_err());
+    }
+
+    #[test_log::test]
+    fn huge_exponent() -> miette::Result<()> {
+        let input = "Register A: 5
+Register B: 40
+Register C: 0
+
+Program: 0,5,5,4";
+        assert_eq!("0", process(input)?);
+        Ok(())
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use miette::miette;
 use nom::{
     bytes::complete::tag,
@@ -37,27 +36,20 @@ fn parse_register(input: &str) -> IResult<&str, Register> {
     Ok((input, Register { a, b, c }))
 }
 
-fn program_from_nums(num_repr: &Vec<i64>) -> Vec<Instruction> {
-    if num_repr.len() % 2 != 0 {
-        return Vec::new();
-    }
+fn program_from_nums(num_repr: &[i64]) -> miette::Result<Vec<Instruction>> {
     num_repr
-        .into_iter()
         .chunks(2)
-        .into_iter()
-        .map(|mut chunk| {
-            let (a, b) = (chunk.next().unwrap(), chunk.next().unwrap());
-            match a {
-                0 => Instruction::Adv(*b),
-                1 => Instruction::Bxl(*b),
-                2 => Instruction::Bst(*b),
-                3 => Instruction::Jnz(*b),
-                4 => Instruction::Bxc(*b),
-                5 => Instruction::Out(*b),
-                6 => Instruction::Bdv(*b),
-                7 => Instruction::Cdv(*b),
-                _ => panic!("invalid instruction"),
-            }
+        .map(|chunk| match *chunk {
+            [0, b] => Ok(Instruction::Adv(b)),
+            [1, b] => Ok(Instruction::Bxl(b)),
+            [2, b] => Ok(Instruction::Bst(b)),
+            [3, b] => Ok(Instruction::Jnz(b)),
+            [4, b] => Ok(Instruction::Bxc(b)),
+            [5, b] => Ok(Instruction::Out(b)),
+            [6, b] => Ok(Instruction::Bdv(b)),
+            [7, b] => Ok(Instruction::Cdv(b)),
+            [a, _] => Err(miette!("invalid instruction {}", a)),
+            _ => Err(miette!("instruction {} is missing its operand", chunk[0])),
         })
         .collect()
 }
@@ -73,26 +65,43 @@ fn parse(input: &str) -> IResult<&str, (Register, Vec<i64>)> {
     Ok((input, (register, program)))
 }
 
-fn combo_op_value(register: &Register, combo_op: i64) -> i64 {
+fn combo_op_value(register: &Register, combo_op: i64) -> miette::Result<i64> {
     match combo_op {
-        0..=3 => combo_op,
-        4 => register.a,
-        5 => register.b,
-        6 => register.c,
-        7 => unreachable!("7 is reserved"),
-        _ => panic!("combo operand out of range"),
+        0..=3 => Ok(combo_op),
+        4 => Ok(register.a),
+        5 => Ok(register.b),
+        6 => Ok(register.c),
+        7 => Err(miette!("combo operand 7 is reserved")),
+        _ => Err(miette!("combo operand {} out of range", combo_op)),
     }
 }
 
-fn run_program(register: &mut Register, program: &Vec<Instruction>) -> Vec<i64> {
+/// `numerator / 2^exponent`, which is 0 once `2^exponent` doesn't fit.
+fn divide(numerator: i64, exponent: i64) -> miette::Result<i64> {
+    let exponent =
+        u32::try_from(exponent).map_err(|_| miette!("can't divide by 2^{}", exponent))?;
+    Ok(2i64
+        .checked_pow(exponent)
+        .map_or(0, |denominator| numerator / denominator))
+}
+
+/// Instructions to run before deciding the program never halts.
+const MAX_STEPS: usize = 1 << 16;
+
+fn run_program(register: &mut Register, program: &[Instruction]) -> miette::Result<Vec<i64>> {
     let mut output: Vec<i64> = Vec::new();
     let mut i = 0;
+    let mut steps = 0;
 
     while i < program.len() {
+        steps += 1;
+        if steps > MAX_STEPS {
+            return Err(miette!("program didn't halt after {} steps", MAX_STEPS));
+        }
         // debug!("register={:?}, i={}, program={:?}", register, i, program[i]);
         match program[i] {
             Instruction::Adv(combo_op) => {
-                register.a = register.a / 2i64.pow(combo_op_value(&register, combo_op) as u32);
+                register.a = divide(register.a, combo_op_value(register, combo_op)?)?;
                 i += 1;
             }
             Instruction::Bxl(literal_op) => {
@@ -100,7 +109,7 @@ fn run_program(register: &mut Register, program: &Vec<Instruction>) -> Vec<i64>
                 i += 1;
             }
             Instruction::Bst(combo_op) => {
-                register.b = combo_op_value(&register, combo_op) % 8;
+                register.b = combo_op_value(register, combo_op)? % 8;
                 i += 1;
             }
             Instruction::Jnz(literal_op) => match register.a {
@@ -116,15 +125,15 @@ fn run_program(register: &mut Register, program: &Vec<Instruction>) -> Vec<i64>
                 i += 1;
             }
             Instruction::Out(combo_op) => {
-                output.push(combo_op_value(&register, combo_op) % 8);
+                output.push(combo_op_value(register, combo_op)? % 8);
                 i += 1;
             }
             Instruction::Bdv(combo_op) => {
-                register.b = register.a / 2i64.pow(combo_op_value(&register, combo_op) as u32);
+                register.b = divide(register.a, combo_op_value(register, combo_op)?)?;
                 i += 1;
             }
             Instruction::Cdv(combo_op) => {
-                register.c = register.a / 2i64.pow(combo_op_value(&register, combo_op) as u32);
+                register.c = divide(register.a, combo_op_value(register, combo_op)?)?;
                 i += 1;
             }
         }
@@ -132,7 +141,7 @@ fn run_program(register: &mut Register, program: &Vec<Instruction>) -> Vec<i64>
 
     // debug!(?register, ?output);
 
-    output
+    Ok(output)
 }
 
 #[tracing::instrument(skip(input))]
@@ -140,15 +149,23 @@ pub fn process(input: &str) -> miette::Result<i64> {
     let (_input, (mut register, prog_nums)) =
         all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
 
-    let prog_inst = program_from_nums(&prog_nums);
+    let prog_inst = program_from_nums(&prog_nums)?;
     debug!(?register, ?prog_inst);
 
-    let mut pow;
-    register.a = 8i64.pow(prog_nums.len() as u32 - 1);
+    // the output has a digit for every octal digit of register a, so only
+    // values with as many octal digits as the program can reproduce it
+    let too_long = || miette!("no value of register A fits a program that long");
+    let digits = prog_nums.len() as u32;
+    let end = 8i64.checked_pow(digits).ok_or_else(too_long)?;
+    register.a = 8i64.checked_pow(digits - 1).ok_or_else(too_long)?;
 
+    let mut pow;
     loop {
+        if register.a >= end {
+            return Err(miette!("no value of register A outputs the program"));
+        }
         debug!("Trying with register.a={}", register.a);
-        let output = run_program(&mut register.clone(), &prog_inst);
+        let output = run_program(&mut register.clone(), &prog_inst)?;
         if output == prog_nums {
             debug!(?output, ?prog_nums);
             break;
@@ -156,14 +173,15 @@ pub fn process(input: &str) -> miette::Result<i64> {
 
         pow = register.a.ilog(8) as i64;
 
-        for (i, &v) in output[1..].iter().rev().enumerate() {
-            if prog_nums[prog_nums.len() - 1 - i] != v {
+        let matching = output.get(1..).unwrap_or_default().iter().rev();
+        for (&v, &expected) in matching.zip(prog_nums.iter().rev()) {
+            if expected != v {
                 break;
             }
             pow -= 1;
         }
 
-        register.a += 8i64.pow(pow as u32);
+        register.a += 8i64.pow(pow.max(0) as u32);
     }
 
     Ok(register.a)
@@ -184,6 +202,18 @@ Program: 0,3,5,4,3,0";
         Ok(())
     }
 
+    #[test_log::test]
+    fn no_quine() {
+        // part 1's example never outputs itself, the search used to overflow
+        let input = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+        assert!(process(input).is_err());
+        assert!(process("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5").is_err());
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
@@ -5,10 +5,14 @@ use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
+    #[cfg(not(feature = "profile"))]
     tracing_subscriber::fmt::init();
+    #[cfg(feature = "profile")]
+    let _profile = aoc_common::profile::init(tracing_subscriber::fmt::layer());
 
     let input = include_str!("../../inputs/input.txt");
-    let output = process(input, IVec2::splat(70), 1024).context("process part 1")?;
+    let output = aoc_common::memory::report(|| process(input, IVec2::splat(70), 1024))
+        .context("process part 1")?;
     println!("Output is {output}");
     Ok(())
 }
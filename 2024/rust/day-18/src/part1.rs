@@ -1,8 +1,9 @@
-use std::{
-    fmt::{self, Write},
-    ops::Not,
-};
+use std::{collections::HashSet, ops::Not};
 
+use aoc_common::{
+    direction::Direction,
+    frame::{Cell, Color, Frame},
+};
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -16,8 +17,6 @@ use nom::{
 use pathfinding::prelude::*;
 use tracing::debug;
 
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
-
 fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     let (input, bytes) = separated_list1(
         line_ending,
@@ -28,24 +27,40 @@ fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     Ok((input, bytes))
 }
 
-fn grid_to_string(tiles: &[IVec2], grid_size: IVec2) -> Result<String, fmt::Error> {
-    let map_size = grid_size;
-
-    let mut output = String::from("\n");
-    for y in 0..=map_size.y {
-        for x in 0..=map_size.x {
-            match tiles.contains(&IVec2::new(x, y)) {
-                true => {
-                    write!(&mut output, "#")?;
-                }
-                false => {
-                    write!(&mut output, ".",)?;
-                }
-            }
+struct BytesFrame {
+    bytes: HashSet<IVec2>,
+    path: HashSet<IVec2>,
+    grid_size: IVec2,
+}
+
+impl BytesFrame {
+    fn new(bytes: &[IVec2], path: &[IVec2], grid_size: IVec2) -> Self {
+        BytesFrame {
+            bytes: bytes.iter().copied().collect(),
+            path: path.iter().copied().collect(),
+            grid_size,
+        }
+    }
+}
+
+impl Frame for BytesFrame {
+    fn size(&self) -> IVec2 {
+        self.grid_size + IVec2::ONE
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        if self.bytes.contains(&position) {
+            Cell::new('#', Color::Red)
+        } else if self.path.contains(&position) {
+            Cell::new('O', Color::Green)
+        } else {
+            Cell::new('.', Color::DarkGrey)
         }
-        writeln!(&mut output)?;
     }
-    Ok(output)
+
+    fn caption(&self) -> String {
+        format!("{} bytes fallen", self.bytes.len())
+    }
 }
 
 #[tracing::instrument(skip(input))]
@@ -53,18 +68,36 @@ pub fn process(input: &str, grid_size: IVec2, ns: usize) -> miette::Result<u32>
     let (_input, falling_bytes) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
 
     debug!(
-        "falling_bytes: {}",
-        grid_to_string(&falling_bytes[0..ns], grid_size).unwrap()
+        "falling_bytes:\n{}",
+        BytesFrame::new(&falling_bytes[0..ns], &[], grid_size).render_plain()
     );
 
     let end = falling_bytes.len().min(ns);
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+    #[cfg(feature = "export")]
+    let mut exporter =
+        aoc_common::export::Exporter::from_env().map_err(|e| miette!("export failed: {}", e))?;
+    #[cfg(any(feature = "visualize", feature = "export"))]
+    for fallen in 0..=end {
+        let frame = BytesFrame::new(&falling_bytes[0..fallen], &[], grid_size);
+        #[cfg(feature = "visualize")]
+        player.show(&frame);
+        #[cfg(feature = "export")]
+        if let Some(exporter) = &mut exporter {
+            exporter
+                .push("day-18-part1", &frame)
+                .map_err(|e| miette!("export failed: {}", e))?;
+        }
+    }
+
     let result = dijkstra(
         &IVec2::ZERO,
         |position| {
-            DIRECTIONS
+            Direction::CARDINAL
                 .iter()
                 .filter_map(|direction| {
-                    let next_position = position + direction;
+                    let next_position = position + *direction;
                     if !(0..=grid_size.x).contains(&next_position.x)
                         || !(0..=grid_size.y).contains(&next_position.y)
                     {
@@ -83,6 +116,18 @@ pub fn process(input: &str, grid_size: IVec2, ns: usize) -> miette::Result<u32>
     )
     .expect("a valid path");
 
+    #[cfg(any(feature = "visualize", feature = "export"))]
+    let frame = BytesFrame::new(&falling_bytes[0..end], &result.0, grid_size);
+    #[cfg(feature = "visualize")]
+    player.show(&frame);
+    #[cfg(feature = "export")]
+    if let Some(exporter) = &mut exporter {
+        exporter
+            .push("day-18-part1", &frame)
+            .and_then(|_| exporter.still("day-18-part1", &frame))
+            .map_err(|e| miette!("export failed: {}", e))?;
+    }
+
     Ok(result.1 as u32)
 }
 
@@ -1,8 +1,9 @@
-use std::{
-    fmt::{self, Write},
-    ops::Not,
-};
+use std::{collections::HashSet, ops::Not};
 
+use aoc_common::{
+    direction::Direction,
+    frame::{Cell, Color, Frame},
+};
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -14,8 +15,7 @@ use nom::{
     IResult, Parser,
 };
 use pathfinding::prelude::*;
-
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+use tracing::debug;
 
 fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     let (input, bytes) = separated_list1(
@@ -27,41 +27,62 @@ fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
     Ok((input, bytes))
 }
 
-#[allow(dead_code)]
-fn grid_to_string(tiles: &[IVec2], grid_size: IVec2) -> Result<String, fmt::Error> {
-    let map_size = grid_size;
-
-    let mut output = String::from("\n");
-    for y in 0..=map_size.y {
-        for x in 0..=map_size.x {
-            match tiles.contains(&IVec2::new(x, y)) {
-                true => {
-                    write!(&mut output, "#")?;
-                }
-                false => {
-                    write!(&mut output, ".",)?;
-                }
-            }
+struct BytesFrame {
+    bytes: HashSet<IVec2>,
+    path: HashSet<IVec2>,
+    grid_size: IVec2,
+}
+
+impl BytesFrame {
+    fn new(bytes: &[IVec2], path: &[IVec2], grid_size: IVec2) -> Self {
+        BytesFrame {
+            bytes: bytes.iter().copied().collect(),
+            path: path.iter().copied().collect(),
+            grid_size,
         }
-        writeln!(&mut output)?;
     }
-    Ok(output)
+}
+
+impl Frame for BytesFrame {
+    fn size(&self) -> IVec2 {
+        self.grid_size + IVec2::ONE
+    }
+
+    fn cell(&self, position: IVec2) -> Cell {
+        if self.bytes.contains(&position) {
+            Cell::new('#', Color::Red)
+        } else if self.path.contains(&position) {
+            Cell::new('O', Color::Green)
+        } else {
+            Cell::new('.', Color::DarkGrey)
+        }
+    }
+
+    fn caption(&self) -> String {
+        format!("{} bytes fallen", self.bytes.len())
+    }
 }
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str, grid_size: IVec2) -> miette::Result<String> {
     let (_input, falling_bytes) = parse(input).map_err(|e| miette!("parse failed {}", e))?;
 
+    #[cfg(feature = "visualize")]
+    let mut player = aoc_common::terminal::Player::from_env();
+    #[cfg(feature = "export")]
+    let mut exporter =
+        aoc_common::export::Exporter::from_env().map_err(|e| miette!("export failed: {}", e))?;
+
     let mut ns = grid_size.x as usize; // heuristic
     let idx = loop {
         let end = falling_bytes.len().min(ns);
         let result = dijkstra(
             &IVec2::ZERO,
             |position| {
-                DIRECTIONS
+                Direction::CARDINAL
                     .iter()
                     .filter_map(|direction| {
-                        let next_position = position + direction;
+                        let next_position = position + *direction;
                         if !(0..=grid_size.x).contains(&next_position.x)
                             || !(0..=grid_size.y).contains(&next_position.y)
                         {
@@ -79,7 +100,28 @@ pub fn process(input: &str, grid_size: IVec2) -> miette::Result<String> {
             |&position| position == grid_size,
         );
 
+        let frame = BytesFrame::new(
+            &falling_bytes[0..end],
+            result.as_ref().map_or(&[], |(path, _)| path),
+            grid_size,
+        );
+        #[cfg(feature = "visualize")]
+        player.show(&frame);
+        #[cfg(feature = "export")]
+        if let Some(exporter) = &mut exporter {
+            exporter
+                .push("day-18-part2", &frame)
+                .map_err(|e| miette!("export failed: {}", e))?;
+        }
+
         if result.is_none() {
+            debug!("no path after {} bytes:\n{}", ns, frame.render_plain());
+            #[cfg(feature = "export")]
+            if let Some(exporter) = &exporter {
+                exporter
+                    .still("day-18-part2", &frame)
+                    .map_err(|e| miette!("export failed: {}", e))?;
+            }
             break ns;
         }
 
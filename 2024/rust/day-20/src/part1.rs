@@ -1,5 +1,6 @@
 use std::{collections::HashSet, ops::Not};
 
+use aoc_common::direction::Direction;
 use glam::IVec2;
 use miette::miette;
 use nom::{
@@ -61,8 +62,6 @@ fn parse(input: Span) -> IResult<Span, Maze> {
     ))
 }
 
-const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
-
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str, savings_threshold: i32) -> miette::Result<u32> {
     let (_input, Maze { start, end, walls }) =
@@ -74,10 +73,10 @@ pub fn process(input: &str, savings_threshold: i32) -> miette::Result<u32> {
     let path_wo_cheats = dijkstra(
         &start,
         |position| {
-            DIRECTIONS
+            Direction::CARDINAL
                 .iter()
                 .filter_map(|direction| {
-                    let next_position = position + direction;
+                    let next_position = position + *direction;
                     walls
                         .contains(&next_position)
                         .not()
@@ -94,7 +93,7 @@ pub fn process(input: &str, savings_threshold: i32) -> miette::Result<u32> {
     let result = walls
         .iter()
         .filter(|wall| {
-            DIRECTIONS
+            Direction::CARDINAL
                 .iter()
                 .filter(|direction| {
                     let next_position = **wall + **direction;
@@ -109,10 +108,10 @@ pub fn process(input: &str, savings_threshold: i32) -> miette::Result<u32> {
             dijkstra(
                 &start,
                 |position| {
-                    DIRECTIONS
+                    Direction::CARDINAL
                         .iter()
                         .filter_map(|direction| {
-                            let next_position = position + direction;
+                            let next_position = position + *direction;
                             (next_position == *wall || walls.contains(&next_position).not())
                                 .then_some((next_position, 1))
                         })
@@ -1,3 +1,4 @@
+use aoc_common::direction::Direction;
 use cached::proc_macro::cached;
 use glam::IVec2;
 use itertools::Itertools;
@@ -65,33 +66,6 @@ impl Keypad {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn to_char(&self) -> char {
-        match self {
-            Direction::Up => '^',
-            Direction::Down => 'v',
-            Direction::Left => '<',
-            Direction::Right => '>',
-        }
-    }
-    fn to_delta(&self) -> IVec2 {
-        match self {
-            Direction::Up => IVec2::NEG_Y,
-            Direction::Down => IVec2::Y,
-            Direction::Left => IVec2::NEG_X,
-            Direction::Right => IVec2::X,
-        }
-    }
-}
-
 fn parse(input: &str) -> IResult<&str, Vec<&str>> {
     let (input, codes) = separated_list1(line_ending, alphanumeric1)(input)?;
     let (input, _) = opt(line_ending)(input)?;
@@ -108,16 +82,16 @@ fn moves_to_key(start_pos: IVec2, c: char, keypad: &Keypad) -> Vec<String> {
 
     let mut moves = Vec::new();
     if delta.x > 0 {
-        moves.extend(std::iter::repeat(Direction::Right).take(delta.x as usize));
+        moves.extend(std::iter::repeat(Direction::East).take(delta.x as usize));
     }
     if delta.x < 0 {
-        moves.extend(std::iter::repeat(Direction::Left).take(-delta.x as usize));
+        moves.extend(std::iter::repeat(Direction::West).take(-delta.x as usize));
     }
     if delta.y > 0 {
-        moves.extend(std::iter::repeat(Direction::Down).take(delta.y as usize));
+        moves.extend(std::iter::repeat(Direction::South).take(delta.y as usize));
     }
     if delta.y < 0 {
-        moves.extend(std::iter::repeat(Direction::Up).take(-delta.y as usize));
+        moves.extend(std::iter::repeat(Direction::North).take(-delta.y as usize));
     }
 
     debug!("Moves required to reach key '{}': {:?}", c, moves);
@@ -129,11 +103,11 @@ fn moves_to_key(start_pos: IVec2, c: char, keypad: &Keypad) -> Vec<String> {
             let mut pos = start_pos;
             let mut moves = String::new();
             for m in move_perm.iter() {
-                pos += m.to_delta();
+                pos += **m;
                 if !keypad.is_pos_valid(&pos) {
                     return None;
                 }
-                moves.push(m.to_char());
+                moves.push(m.glyph());
             }
             moves.push('A');
             Some(moves)
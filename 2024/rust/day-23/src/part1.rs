@@ -1,3 +1,4 @@
+use aoc_common::interner::Interner;
 use itertools::Itertools;
 use miette::miette;
 use nom::{
@@ -8,7 +9,38 @@ use nom::{
     sequence::separated_pair,
     IResult,
 };
-use petgraph::prelude::UnGraphMap;
+
+/// Computers are indexed by their interned id, each holding its neighbours.
+#[derive(Debug, Default)]
+struct Lan {
+    names: Interner<u16>,
+    neighbors: Vec<Vec<u16>>,
+}
+
+impl Lan {
+    fn from_edges(edges: &[(&str, &str)]) -> Lan {
+        let mut lan = Lan::default();
+        for (a, b) in edges {
+            let [a, b] = [a, b].map(|name| lan.names.intern(name));
+            lan.neighbors.resize(lan.names.len(), vec![]);
+            lan.neighbors[a as usize].push(b);
+            lan.neighbors[b as usize].push(a);
+        }
+        lan
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = u16> + '_ {
+        self.names.iter().map(|(id, _)| id)
+    }
+
+    fn neighbors(&self, node: u16) -> impl Iterator<Item = u16> + Clone + '_ {
+        self.neighbors[node as usize].iter().copied()
+    }
+
+    fn contains_edge(&self, a: u16, b: u16) -> bool {
+        self.neighbors[a as usize].contains(&b)
+    }
+}
 
 fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
     let (input, list) =
@@ -22,7 +54,7 @@ fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
 pub fn process(input: &str) -> miette::Result<u32> {
     let (_input, data) = all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
 
-    let g = &UnGraphMap::<&str, ()>::from_edges(&data);
+    let g = &Lan::from_edges(&data);
 
     let result = g
         .nodes()
@@ -30,7 +62,10 @@ pub fn process(input: &str) -> miette::Result<u32> {
             g.neighbors(node)
                 .tuple_combinations()
                 .filter(move |(a, b)| {
-                    g.contains_edge(a, b) && [node, a, b].iter().any(|n| n.starts_with("t"))
+                    g.contains_edge(*a, *b)
+                        && [node, *a, *b]
+                            .iter()
+                            .any(|n| g.names.resolve(*n).starts_with('t'))
                 })
                 .map(move |(a, b)| {
                     let mut nodes = [node, a, b];
@@ -1,3 +1,4 @@
+use aoc_common::{dot::Graph, interner::Interner};
 use itertools::Itertools;
 use miette::miette;
 use nom::{
@@ -8,9 +9,40 @@ use nom::{
     sequence::separated_pair,
     IResult,
 };
-use petgraph::prelude::UnGraphMap;
 use tracing::debug;
 
+/// Computers are indexed by their interned id, each holding its neighbours.
+#[derive(Debug, Default)]
+struct Lan {
+    names: Interner<u16>,
+    neighbors: Vec<Vec<u16>>,
+}
+
+impl Lan {
+    fn from_edges(edges: &[(&str, &str)]) -> Lan {
+        let mut lan = Lan::default();
+        for (a, b) in edges {
+            let [a, b] = [a, b].map(|name| lan.names.intern(name));
+            lan.neighbors.resize(lan.names.len(), vec![]);
+            lan.neighbors[a as usize].push(b);
+            lan.neighbors[b as usize].push(a);
+        }
+        lan
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = u16> + '_ {
+        self.names.iter().map(|(id, _)| id)
+    }
+
+    fn neighbors(&self, node: u16) -> impl Iterator<Item = u16> + Clone + '_ {
+        self.neighbors[node as usize].iter().copied()
+    }
+
+    fn contains_edge(&self, a: u16, b: u16) -> bool {
+        self.neighbors[a as usize].contains(&b)
+    }
+}
+
 fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
     let (input, list) =
         separated_list1(line_ending, separated_pair(alpha1, tag("-"), alpha1))(input)?;
@@ -19,12 +51,9 @@ fn parse(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
     Ok((input, list))
 }
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str, expected_cluster_size: usize) -> miette::Result<String> {
-    let (_input, data) = all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
-
-    let g = &UnGraphMap::<&str, ()>::from_edges(&data);
-
+/// The single clique made of a node and `expected_cluster_size` of its
+/// neighbours, sorted by name.
+fn max_clique(g: &Lan, expected_cluster_size: usize) -> miette::Result<Vec<u16>> {
     let result = g
         .nodes()
         .flat_map(|node| {
@@ -34,13 +63,13 @@ pub fn process(input: &str, expected_cluster_size: usize) -> miette::Result<Stri
                     if neighbor_subset
                         .iter()
                         .tuple_combinations()
-                        .all(move |(a, b)| g.contains_edge(a, b))
+                        .all(move |(a, b)| g.contains_edge(*a, *b))
                     {
                         let mut nodes = vec![node]
                             .into_iter()
                             .chain(neighbor_subset.into_iter())
                             .collect::<Vec<_>>();
-                        nodes.sort();
+                        nodes.sort_by_key(|id| g.names.resolve(*id));
                         Some(nodes)
                     } else {
                         None
@@ -55,7 +84,44 @@ pub fn process(input: &str, expected_cluster_size: usize) -> miette::Result<Stri
         return Err(miette!("expected 1 result, got {}", result.len()));
     }
 
-    Ok(result[0].iter().join(","))
+    Ok(result.into_iter().next().unwrap())
+}
+
+/// The LAN with the computers of the LAN party and their links in red.
+pub fn dot(input: &str, expected_cluster_size: usize) -> miette::Result<Graph> {
+    let (_input, data) = all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
+
+    let g = &Lan::from_edges(&data);
+    let clique = max_clique(g, expected_cluster_size)?;
+
+    let mut graph = Graph::undirected();
+    for (id, name) in g.names.iter() {
+        match clique.contains(&id) {
+            true => graph.node(name, &[("style", "filled"), ("fillcolor", "salmon")]),
+            false => graph.node(name, &[]),
+        };
+    }
+    for (a, b) in data {
+        let in_clique = [a, b]
+            .iter()
+            .all(|name| g.names.get(name).is_some_and(|id| clique.contains(&id)));
+        match in_clique {
+            true => graph.edge(a, b, &[("color", "red"), ("penwidth", "2")]),
+            false => graph.edge(a, b, &[("color", "grey")]),
+        };
+    }
+
+    Ok(graph)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str, expected_cluster_size: usize) -> miette::Result<String> {
+    let (_input, data) = all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
+
+    let g = &Lan::from_edges(&data);
+    let clique = max_clique(g, expected_cluster_size)?;
+
+    Ok(clique.iter().map(|id| g.names.resolve(*id)).join(","))
 }
 
 #[cfg(test)]
@@ -100,6 +166,23 @@ td-yn";
         Ok(())
     }
 
+    #[test_log::test]
+    fn lan_party_graph() -> miette::Result<()> {
+        let input = "ka-co
+ta-co
+de-co
+ta-ka
+de-ta
+ka-de
+kh-ta
+tc-td";
+        let graph = dot(input, 3)?.to_string();
+        assert_eq!(4, graph.matches("fillcolor").count());
+        assert_eq!(6, graph.matches("color=\"red\"").count());
+        assert!(graph.contains(r#""kh" -- "ta" [color="grey"]"#));
+        Ok(())
+    }
+
     #[test_log::test]
     fn input() -> miette::Result<()> {
         let input = include_str!("../inputs/input.txt");
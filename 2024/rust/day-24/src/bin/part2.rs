@@ -1,13 +1,20 @@
-use day_24::part2::process;
+use day_24::part2::{dot, process};
 
-use miette::Context;
+use miette::{Context, IntoDiagnostic};
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
+    #[cfg(not(feature = "profile"))]
     tracing_subscriber::fmt::init();
+    #[cfg(feature = "profile")]
+    let _profile = aoc_common::profile::init(tracing_subscriber::fmt::layer());
 
     let input = include_str!("../../inputs/working-input.txt");
-    let output = process(input).context("process part 2")?;
+    if let Some(path) = aoc_common::dot::path_from_args() {
+        dot(input)?.write(path).into_diagnostic()?;
+    }
+
+    let output = aoc_common::memory::report(|| process(input)).context("process part 2")?;
     println!("Output is {output}");
     Ok(())
 }
@@ -1,15 +1,16 @@
 use std::collections::HashMap;
 
+use aoc_common::interner::Interner;
 use itertools::Itertools;
 use miette::miette;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alphanumeric1, line_ending, multispace1, space1},
+    character::complete::{alphanumeric1, line_ending, multispace1, space1},
     combinator::{all_consuming, opt, value},
     multi::separated_list1,
     sequence::{preceded, separated_pair, terminated, tuple},
-    IResult, Parser,
+    IResult,
 };
 
 use tracing::debug;
@@ -28,6 +29,14 @@ struct Gate<'a> {
     operation: Operation,
 }
 
+/// A gate with its wires interned, so values can live in a `Vec`.
+#[derive(Debug)]
+struct WireGate {
+    inputs: [u16; 2],
+    output: u16,
+    operation: Operation,
+}
+
 fn parse_gate(input: &str) -> IResult<&str, Gate> {
     let (input, elements) = tuple((
         terminated(alphanumeric1, space1),
@@ -56,11 +65,7 @@ fn parse(input: &str) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate>)> {
         separated_pair(
             alphanumeric1,
             tag(": "),
-            complete::u8.map(|v| match v {
-                0 => false,
-                1 => true,
-                _ => unreachable!("unexpected value"),
-            }),
+            alt((value(false, tag("0")), value(true, tag("1")))),
         ),
     )(input)?;
 
@@ -78,22 +83,40 @@ pub fn process(input: &str) -> miette::Result<String> {
         all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
     debug!(?map, ?gates);
 
-    let mut current_map = map.clone();
-    let mut pending_gates = gates.clone();
-    let mut processed_gates: Vec<Gate> = vec![];
+    let mut wires = Interner::<u16>::new();
+    let initial_values = map
+        .iter()
+        .map(|(wire, value)| (wires.intern(wire), *value))
+        .collect::<Vec<_>>();
+    let mut pending_gates = gates
+        .iter()
+        .map(|gate| WireGate {
+            inputs: [wires.intern(gate.inputs[0]), wires.intern(gate.inputs[1])],
+            output: wires.intern(gate.output),
+            operation: gate.operation.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut values: Vec<Option<bool>> = vec![None; wires.len()];
+    for (wire, value) in initial_values {
+        values[wire as usize] = Some(value);
+    }
 
     while !pending_gates.is_empty() {
         let ready_gates = pending_gates
-            .extract_if(.., |Gate { inputs, .. }| {
-                inputs
-                    .iter()
-                    .all(|input_key| current_map.contains_key(input_key))
+            .extract_if(.., |WireGate { inputs, .. }| {
+                inputs.iter().all(|input| values[*input as usize].is_some())
             })
             .collect::<Vec<_>>();
+        if ready_gates.is_empty() {
+            return Err(miette!(
+                "{} gates wait on wires nothing drives, or on each other",
+                pending_gates.len()
+            ));
+        }
 
         for gate in ready_gates {
-            let a = current_map.get(gate.inputs[0]).unwrap();
-            let b = current_map.get(gate.inputs[1]).unwrap();
+            let [a, b] = gate.inputs.map(|input| values[input as usize].unwrap());
 
             let value = match gate.operation {
                 Operation::AND => a & b,
@@ -101,20 +124,22 @@ pub fn process(input: &str) -> miette::Result<String> {
                 Operation::XOR => a ^ b,
             };
 
-            current_map.entry(&gate.output).or_insert(value);
-            processed_gates.push(gate);
+            values[gate.output as usize].get_or_insert(value);
         }
     }
 
-    let bitstring = current_map
+    let bitstring = wires
         .iter()
-        .filter(|(key, _)| key.starts_with('z'))
-        .sorted_by(|a, b| b.0.cmp(&a.0))
-        .into_iter()
-        .map(|(_, value)| (*value as u8).to_string())
-        .collect::<String>();
+        .filter(|(_, name)| name.starts_with('z'))
+        .sorted_by(|a, b| b.1.cmp(a.1))
+        .map(|(wire, name)| match values[wire as usize] {
+            Some(value) => Ok((value as u8).to_string()),
+            None => Err(miette!("nothing drives {name}")),
+        })
+        .collect::<miette::Result<String>>()?;
 
-    let result = u64::from_str_radix(&bitstring, 2).unwrap();
+    let result = u64::from_str_radix(&bitstring, 2)
+        .map_err(|e| miette!("z wires {bitstring:?} aren't a u64: {e}"))?;
 
     Ok(result.to_string())
 }
@@ -125,6 +150,17 @@ mod day_24_part1 {
 
     use rstest::rstest;
 
+    #[test_log::test]
+    fn gates_that_never_settle() {
+        for input in [
+            "x00: 1\n\nx00 AND y00 -> z00",
+            "x00: 1\n\nx00 AND z01 -> z00\nx00 OR z00 -> z01",
+            "x00: 2\n\nx00 AND x00 -> z00",
+        ] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
     #[rstest]
     #[case(
         "x00: 1
@@ -1,18 +1,19 @@
 use std::collections::HashMap;
 
+use aoc_common::dot::Graph;
+
 use itertools::Itertools;
 use miette::miette;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alphanumeric1, line_ending, multispace1, space1},
+    character::complete::{alphanumeric1, line_ending, multispace1, space1},
     combinator::{all_consuming, opt, value},
     multi::separated_list1,
     sequence::{preceded, separated_pair, terminated, tuple},
-    IResult, Parser,
+    IResult,
 };
 
-use petgraph::{dot::Dot, prelude::DiGraphMap};
 use tracing::debug;
 
 #[derive(Debug, Clone)]
@@ -57,11 +58,7 @@ fn parse(input: &str) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate>)> {
         separated_pair(
             alphanumeric1,
             tag(": "),
-            complete::u8.map(|v| match v {
-                0 => false,
-                1 => true,
-                _ => unreachable!("unexpected value"),
-            }),
+            alt((value(false, tag("0")), value(true, tag("1")))),
         ),
     )(input)?;
 
@@ -73,6 +70,87 @@ fn parse(input: &str) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate>)> {
     Ok((input, (map, gates)))
 }
 
+/// Runs every gate once its inputs are known, returning the value of every
+/// wire. Gates left waiting on wires nothing drives, or on each other, leave
+/// their output out.
+fn evaluate<'a>(map: &HashMap<&'a str, bool>, gates: &[Gate<'a>]) -> HashMap<&'a str, bool> {
+    let mut current_map = map.clone();
+    let mut pending_gates = gates.to_vec();
+
+    while !pending_gates.is_empty() {
+        let ready_gates = pending_gates
+            .extract_if(.., |Gate { inputs, .. }| {
+                inputs
+                    .iter()
+                    .all(|input_key| current_map.contains_key(input_key))
+            })
+            .collect::<Vec<_>>();
+        if ready_gates.is_empty() {
+            break;
+        }
+
+        for gate in ready_gates {
+            let a = current_map[gate.inputs[0]];
+            let b = current_map[gate.inputs[1]];
+
+            let value = match gate.operation {
+                Operation::AND => a & b,
+                Operation::OR => a | b,
+                Operation::XOR => a ^ b,
+            };
+
+            current_map.entry(gate.output).or_insert(value);
+        }
+    }
+
+    current_map
+}
+
+/// The gate network, one node per gate named after its output wire and
+/// edges labelled with the value each wire ends up carrying.
+pub fn dot(input: &str) -> miette::Result<Graph> {
+    let (_input, (map, gates)) =
+        all_consuming(parse)(input).map_err(|e| miette!("parse failed {}", e))?;
+    let current_map = evaluate(&map, &gates);
+
+    let mut graph = Graph::directed();
+    graph.attribute("rankdir", "LR");
+    for input in map.keys().sorted() {
+        graph.node(input, &[("shape", "plaintext")]);
+    }
+    for gate in gates.iter() {
+        let (operation, shape) = match gate.operation {
+            Operation::AND => ("AND", "box"),
+            Operation::OR => ("OR", "ellipse"),
+            Operation::XOR => ("XOR", "diamond"),
+        };
+        let label = format!("{}\n{}", gate.output, operation);
+        let color = match gate.output.starts_with('z') {
+            true => "lightblue",
+            false => "white",
+        };
+        graph.node(
+            gate.output,
+            &[
+                ("label", &label),
+                ("shape", shape),
+                ("style", "filled"),
+                ("fillcolor", color),
+            ],
+        );
+        for input in gate.inputs.iter() {
+            let value = match current_map.get(input) {
+                Some(true) => "1",
+                Some(false) => "0",
+                None => "?",
+            };
+            graph.edge(input, gate.output, &[("label", value)]);
+        }
+    }
+
+    Ok(graph)
+}
+
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String> {
     let (_input, (map, gates)) =
@@ -86,7 +164,7 @@ pub fn process(input: &str) -> miette::Result<String> {
         .into_iter()
         .map(|(_, value)| (*value as u8).to_string())
         .collect::<String>();
-    let output_x = u64::from_str_radix(&bitstring_x, 2).unwrap();
+    let output_x = u64::from_str_radix(&bitstring_x, 2).ok();
     debug!(?bitstring_x, ?output_x);
 
     let bitstring_y = map
@@ -96,79 +174,20 @@ pub fn process(input: &str) -> miette::Result<String> {
         .into_iter()
         .map(|(_, value)| (*value as u8).to_string())
         .collect::<String>();
-    let output_y = u64::from_str_radix(&bitstring_y, 2).unwrap();
+    let output_y = u64::from_str_radix(&bitstring_y, 2).ok();
     debug!(?bitstring_y, ?output_y);
 
-    debug!(
-        "binary_sum={:b}, sum={}",
-        output_x + output_y,
-        output_x + output_y
-    );
-
-    let mut current_map = map.clone();
-    let mut pending_gates = gates.clone();
-    let mut processed_gates: Vec<Gate> = vec![];
-
-    while !pending_gates.is_empty() {
-        let ready_gates = pending_gates
-            .extract_if(.., |Gate { inputs, .. }| {
-                inputs
-                    .iter()
-                    .all(|input_key| current_map.contains_key(input_key))
-            })
-            .collect::<Vec<_>>();
-
-        for gate in ready_gates {
-            let a = current_map.get(gate.inputs[0]).unwrap();
-            let b = current_map.get(gate.inputs[1]).unwrap();
-
-            let value = match gate.operation {
-                Operation::AND => a & b,
-                Operation::OR => a | b,
-                Operation::XOR => a ^ b,
-            };
-
-            current_map.entry(&gate.output).or_insert(value);
-            processed_gates.push(gate);
-        }
+    if let Some(sum) = output_x.zip(output_y).map(|(x, y)| x.wrapping_add(y)) {
+        debug!("binary_sum={:b}, sum={}", sum, sum);
     }
 
-    let node_names = gates
+    let current_map = evaluate(&map, &gates);
+    if let Some(gate) = gates
         .iter()
-        .map(|gate| {
-            (
-                gate.output,
-                format!(
-                    "{}\n{}",
-                    gate.output,
-                    match gate.operation {
-                        Operation::AND => "AND",
-                        Operation::OR => "OR",
-                        Operation::XOR => "XOR",
-                    }
-                ),
-            )
-        })
-        .collect::<HashMap<&str, String>>();
-
-    let edges = gates
-        .iter()
-        .flat_map(|Gate { inputs, output, .. }| {
-            inputs
-                .iter()
-                .map(|input| {
-                    (
-                        node_names.get(input).map(|v| v.as_str()).unwrap_or(input),
-                        node_names.get(output).map(|v| v.as_str()).unwrap_or(output),
-                        current_map.get(input).unwrap(),
-                    )
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let g = &DiGraphMap::<&str, &bool>::from_edges(&edges);
-    println!("{}", Dot::with_config(&g, &[]));
+        .find(|gate| !current_map.contains_key(gate.output))
+    {
+        return Err(miette!("{} never gets a value", gate.output));
+    }
 
     let bitstring = current_map
         .iter()
@@ -180,7 +199,43 @@ pub fn process(input: &str) -> miette::Result<String> {
 
     debug!(?bitstring);
 
-    let result = u64::from_str_radix(&bitstring, 2).unwrap();
+    let result = u64::from_str_radix(&bitstring, 2)
+        .map_err(|e| miette!("z wires {bitstring:?} aren't a u64: {e}"))?;
 
     Ok(result.to_string())
 }
+
+#[cfg(test)]
+mod day_24_part2 {
+    use super::*;
+
+    #[test_log::test]
+    fn gates_that_never_settle() {
+        for input in [
+            "x00: 1\n\nx00 AND y00 -> z00",
+            "x00: 1\n\nx00 AND z01 -> z00\nx00 OR z00 -> z01",
+            "x00: 2\n\nx00 AND x00 -> z00",
+        ] {
+            assert!(process(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test_log::test]
+    fn gate_graph() -> miette::Result<()> {
+        let input = "x00: 1
+x01: 1
+y00: 0
+y01: 1
+
+x00 XOR y00 -> z00
+x01 AND y01 -> z01";
+        let graph = dot(input)?.to_string();
+        assert!(graph.contains(r#""x00" [shape="plaintext"]"#));
+        assert!(graph.contains(
+            r#""z00" [label="z00\nXOR", shape="diamond", style="filled", fillcolor="lightblue"]"#
+        ));
+        assert!(graph.contains(r#""y00" -> "z00" [label="0"]"#));
+        assert!(graph.contains(r#""y01" -> "z01" [label="1"]"#));
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
+use aoc_common::parsers::sections;
 use itertools::Itertools;
 use miette::miette;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::line_ending,
-    combinator::{iterator, opt, peek},
-    multi::separated_list1,
-    sequence::{preceded, terminated, tuple},
+    character::complete::{line_ending, one_of},
+    combinator::peek,
+    multi::{count, separated_list1},
+    sequence::{preceded, tuple},
     IResult,
 };
 
@@ -22,21 +23,20 @@ struct Device {
     pins: [i32; 5],
 }
 
-fn pins(mut pins: [i32; 5]) -> impl FnMut(&str) -> IResult<&str, [i32; 5]> {
+fn pins(initial: [i32; 5]) -> impl FnMut(&str) -> IResult<&str, [i32; 5]> {
     move |input| {
-        let mut it = iterator(
-            input,
-            terminated(alt((tag("#"), tag("."))), opt(line_ending)),
-        );
-
-        for (i, value) in it.enumerate() {
-            pins[i % 5] += match value {
-                "#" => 1,
-                _ => 0,
-            };
+        let (input, rows) = separated_list1(line_ending, count(one_of("#."), 5))(input)?;
+
+        let mut pins = initial;
+        for row in rows {
+            for (i, value) in row.into_iter().enumerate() {
+                pins[i] += match value {
+                    '#' => 1,
+                    _ => 0,
+                };
+            }
         }
-        let res: IResult<_, _> = it.finish();
-        res.map(|(input, _)| (input, pins))
+        Ok((input, pins))
     }
 }
 
@@ -73,7 +73,7 @@ fn key(input: &str) -> IResult<&str, Device> {
 }
 
 fn parse(input: &str) -> IResult<&str, Vec<Device>> {
-    separated_list1(line_ending, alt((key, lock)))(input)
+    sections(alt((key, lock)))(input)
 }
 
 #[tracing::instrument(skip(input))]
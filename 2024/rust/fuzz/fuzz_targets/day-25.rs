@@ -0,0 +1,5 @@
+aoc_common::fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = day_25::part1::process(input);
+    }
+});
@@ -0,0 +1,326 @@
+use std::ops::{Add, AddAssign, BitOr, BitOrAssign};
+
+use glam::IVec2;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DirectionError {
+    #[error("invalid direction character {0:?}")]
+    InvalidChar(char),
+
+    #[error("{0} is not a unit step in any direction")]
+    InvalidDelta(IVec2),
+}
+
+/// A heading on a grid where `y` grows downwards, which is how every
+/// puzzle lays out its input. Variants are listed clockwise starting at
+/// north, so the discriminant is the number of eighth turns from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    fn from_eighths(eighths: u8) -> Direction {
+        Direction::ALL[(eighths % 8) as usize]
+    }
+
+    fn rotate(self, eighths: u8) -> Direction {
+        Direction::from_eighths(self as u8 + eighths)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        self as u8 & 1 == 0
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.is_cardinal()
+    }
+
+    pub fn to_ivec2(self) -> IVec2 {
+        match self {
+            Direction::North => IVec2::NEG_Y,
+            Direction::NorthEast => IVec2::new(1, -1),
+            Direction::East => IVec2::X,
+            Direction::SouthEast => IVec2::ONE,
+            Direction::South => IVec2::Y,
+            Direction::SouthWest => IVec2::new(-1, 1),
+            Direction::West => IVec2::NEG_X,
+            Direction::NorthWest => IVec2::NEG_ONE,
+        }
+    }
+
+    /// The arrow used to draw this heading, `^>v<` for the cardinal
+    /// directions as they appear in the puzzle inputs.
+    pub fn glyph(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::NorthEast => '↗',
+            Direction::East => '>',
+            Direction::SouthEast => '↘',
+            Direction::South => 'v',
+            Direction::SouthWest => '↙',
+            Direction::West => '<',
+            Direction::NorthWest => '↖',
+        }
+    }
+}
+
+impl From<Direction> for IVec2 {
+    fn from(direction: Direction) -> Self {
+        direction.to_ivec2()
+    }
+}
+
+impl TryFrom<IVec2> for Direction {
+    type Error = DirectionError;
+
+    fn try_from(delta: IVec2) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.to_ivec2() == delta)
+            .ok_or(DirectionError::InvalidDelta(delta))
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::North),
+            '>' | 'R' | 'E' => Ok(Direction::East),
+            'v' | 'D' | 'S' => Ok(Direction::South),
+            '<' | 'L' | 'W' => Ok(Direction::West),
+            _ => Err(DirectionError::InvalidChar(c)),
+        }
+    }
+}
+
+impl Add<Direction> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, direction: Direction) -> Self::Output {
+        self + direction.to_ivec2()
+    }
+}
+
+impl Add<Direction> for &IVec2 {
+    type Output = IVec2;
+
+    fn add(self, direction: Direction) -> Self::Output {
+        *self + direction.to_ivec2()
+    }
+}
+
+impl AddAssign<Direction> for IVec2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self += direction.to_ivec2();
+    }
+}
+
+/// A set of directions packed into a single byte, handy for tracking
+/// which headings a tile has already been visited with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const EMPTY: DirectionSet = DirectionSet(0);
+    pub const ALL: DirectionSet = DirectionSet(u8::MAX);
+
+    fn bit(direction: Direction) -> u8 {
+        1 << direction as u8
+    }
+
+    /// Returns whether the direction was newly inserted.
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let inserted = !self.contains(direction);
+        self.0 |= DirectionSet::bit(direction);
+        inserted
+    }
+
+    /// Returns whether the direction was present in the set.
+    pub fn remove(&mut self, direction: Direction) -> bool {
+        let removed = self.contains(direction);
+        self.0 &= !DirectionSet::bit(direction);
+        removed
+    }
+
+    pub fn contains(&self, direction: Direction) -> bool {
+        self.0 & DirectionSet::bit(direction) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Direction> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| self.contains(*direction))
+    }
+}
+
+impl From<Direction> for DirectionSet {
+    fn from(direction: Direction) -> Self {
+        DirectionSet(DirectionSet::bit(direction))
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<T: IntoIterator<Item = Direction>>(iter: T) -> Self {
+        let mut set = DirectionSet::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Direction> for DirectionSet {
+    fn extend<T: IntoIterator<Item = Direction>>(&mut self, iter: T) {
+        for direction in iter {
+            self.insert(direction);
+        }
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = DirectionSet;
+
+    fn bitor(self, rhs: DirectionSet) -> Self::Output {
+        DirectionSet(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<Direction> for DirectionSet {
+    type Output = DirectionSet;
+
+    fn bitor(self, rhs: Direction) -> Self::Output {
+        self | DirectionSet::from(rhs)
+    }
+}
+
+impl BitOrAssign<Direction> for DirectionSet {
+    fn bitor_assign(&mut self, rhs: Direction) {
+        self.insert(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn turning() {
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::NorthEast, Direction::NorthWest.turn_right());
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.reverse());
+
+        for direction in Direction::ALL {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.reverse(), direction.turn_right().turn_right());
+            assert_eq!(-direction.to_ivec2(), direction.reverse().to_ivec2());
+        }
+    }
+
+    #[test_log::test]
+    fn ivec2_round_trip() {
+        for direction in Direction::ALL {
+            assert_eq!(Ok(direction), Direction::try_from(IVec2::from(direction)));
+        }
+        assert_eq!(IVec2::new(3, 4), IVec2::new(2, 5) + Direction::NorthEast);
+        assert_eq!(
+            Err(DirectionError::InvalidDelta(IVec2::new(2, 0))),
+            Direction::try_from(IVec2::new(2, 0))
+        );
+    }
+
+    #[test_log::test]
+    fn from_char() {
+        for (chars, expected) in ["^UN", ">RE", "vDS", "<LW"].iter().zip(Direction::CARDINAL) {
+            for c in chars.chars() {
+                assert_eq!(Ok(expected), Direction::try_from(c));
+            }
+            assert_eq!(Ok(expected), Direction::try_from(expected.glyph()));
+        }
+        assert_eq!(
+            Err(DirectionError::InvalidChar('x')),
+            Direction::try_from('x')
+        );
+    }
+
+    #[test_log::test]
+    fn cardinal_and_diagonal() {
+        assert!(Direction::CARDINAL.iter().all(|d| d.is_cardinal()));
+        assert!(Direction::DIAGONAL.iter().all(|d| d.is_diagonal()));
+    }
+
+    #[test_log::test]
+    fn direction_set() {
+        let mut visited = DirectionSet::default();
+        assert!(visited.is_empty());
+        assert!(visited.insert(Direction::North));
+        assert!(!visited.insert(Direction::North));
+        visited |= Direction::West;
+
+        assert_eq!(2, visited.len());
+        assert_eq!(
+            vec![Direction::North, Direction::West],
+            visited.iter().collect::<Vec<_>>()
+        );
+        assert!(visited.remove(Direction::North));
+        assert!(!visited.contains(Direction::North));
+
+        let cardinal = Direction::CARDINAL.into_iter().collect::<DirectionSet>();
+        let diagonal = Direction::DIAGONAL.into_iter().collect::<DirectionSet>();
+        assert_eq!(DirectionSet::ALL, cardinal | diagonal);
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    env,
+    fmt::{self, Display, Write as _},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A Graphviz graph, built up node by node and edge by edge.
+///
+/// Ids and attribute values are quoted and escaped when written, so puzzle
+/// names can be used as they are.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    directed: bool,
+    attributes: Vec<String>,
+    nodes: Vec<String>,
+    edges: Vec<String>,
+}
+
+impl Graph {
+    pub fn directed() -> Graph {
+        Graph::new(true)
+    }
+
+    pub fn undirected() -> Graph {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Graph {
+        Graph {
+            directed,
+            attributes: vec![],
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Graph wide attribute, like `rankdir=LR`.
+    pub fn attribute(&mut self, key: &str, value: &str) -> &mut Self {
+        self.attributes.push(format!("{key}={}", quote(value)));
+        self
+    }
+
+    pub fn node(&mut self, id: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        self.nodes
+            .push(format!("{}{}", quote(id), attribute_list(attributes)));
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        let arrow = match self.directed {
+            true => "->",
+            false => "--",
+        };
+        self.edges.push(format!(
+            "{} {arrow} {}{}",
+            quote(from),
+            quote(to),
+            attribute_list(attributes)
+        ));
+        self
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.directed {
+            true => writeln!(f, "digraph {{")?,
+            false => writeln!(f, "graph {{")?,
+        }
+        for line in self.attributes.iter().chain(&self.nodes).chain(&self.edges) {
+            writeln!(f, "    {line};")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn attribute_list(attributes: &[(&str, &str)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+
+    let mut list = String::from(" [");
+    for (i, (key, value)) in attributes.iter().enumerate() {
+        if i > 0 {
+            list.push_str(", ");
+        }
+        write!(list, "{key}={}", quote(value)).unwrap();
+    }
+    list.push(']');
+    list
+}
+
+/// The file passed as `--dot <file>` or `--dot=<file>` on the command line.
+pub fn path_from_args() -> Option<PathBuf> {
+    path_from(env::args().skip(1))
+}
+
+fn path_from(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == "--dot" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--dot=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn quoted_output() {
+        let mut graph = Graph::directed();
+        graph
+            .attribute("rankdir", "LR")
+            .node("broadcaster", &[("shape", "box")])
+            .node("a", &[])
+            .edge(
+                "broadcaster",
+                "a",
+                &[("label", "x00\nAND"), ("color", "red")],
+            );
+
+        assert_eq!(
+            r#"digraph {
+    rankdir="LR";
+    "broadcaster" [shape="box"];
+    "a";
+    "broadcaster" -> "a" [label="x00\nAND", color="red"];
+}
+"#,
+            graph.to_string()
+        );
+        assert_eq!(
+            "graph {\n    \"say \\\"hi\\\"\" -- \"a\\\\b\";\n}\n",
+            Graph::undirected()
+                .edge("say \"hi\"", "a\\b", &[])
+                .to_string()
+        );
+    }
+
+    #[test_log::test]
+    fn dot_argument() {
+        let args = |args: &[&str]| path_from(args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(None, args(&[]));
+        assert_eq!(None, args(&["--dot"]));
+        assert_eq!(Some(PathBuf::from("g.dot")), args(&["--dot", "g.dot"]));
+        assert_eq!(Some(PathBuf::from("g.dot")), args(&["-v", "--dot=g.dot"]));
+    }
+}
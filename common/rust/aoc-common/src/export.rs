@@ -0,0 +1,464 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use glam::IVec2;
+use thiserror::Error;
+
+use crate::frame::{Color, Frame};
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Png(#[from] png::EncodingError),
+
+    #[error(transparent)]
+    Gif(#[from] gif::EncodingError),
+
+    #[error("invalid palette entry {0:?}, expected something like red=ff0000")]
+    InvalidPalette(String),
+
+    #[error("invalid {variable} {value:?}, expected a whole number")]
+    InvalidNumber {
+        variable: &'static str,
+        value: String,
+    },
+
+    #[error("frame of {0} cells doesn't fit in an image")]
+    TooLarge(IVec2),
+
+    #[error("frame of {found} cells in an animation of {expected} cells")]
+    SizeMismatch { expected: IVec2, found: IVec2 },
+}
+
+const COLORS: [(Color, &str); 9] = [
+    (Color::Default, "default"),
+    (Color::DarkGrey, "dark-grey"),
+    (Color::Red, "red"),
+    (Color::Green, "green"),
+    (Color::Yellow, "yellow"),
+    (Color::Blue, "blue"),
+    (Color::Magenta, "magenta"),
+    (Color::Cyan, "cyan"),
+    (Color::White, "white"),
+];
+
+/// RGB values for every [`Color`]. `Color::Default` is the background.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette([[u8; 3]; COLORS.len()]);
+
+impl Palette {
+    fn index(color: Color) -> usize {
+        COLORS.iter().position(|(c, _)| *c == color).unwrap()
+    }
+
+    pub fn set(mut self, color: Color, rgb: [u8; 3]) -> Self {
+        self.0[Palette::index(color)] = rgb;
+        self
+    }
+
+    pub fn get(&self, color: Color) -> [u8; 3] {
+        self.0[Palette::index(color)]
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette([
+            [0x0f, 0x0f, 0x23],
+            [0x55, 0x55, 0x66],
+            [0xe0, 0x40, 0x40],
+            [0x40, 0xc0, 0x40],
+            [0xff, 0xff, 0x66],
+            [0x50, 0x80, 0xff],
+            [0xd0, 0x60, 0xd0],
+            [0x40, 0xd0, 0xd0],
+            [0xcc, 0xcc, 0xcc],
+        ])
+    }
+}
+
+/// Overrides on top of the default palette, as `name=rrggbb` pairs
+/// separated by commas: `default=000000,green=00ff00`.
+impl FromStr for Palette {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .try_fold(Palette::default(), |palette, entry| {
+                let invalid = || ExportError::InvalidPalette(entry.to_string());
+                let (name, hex) = entry.split_once('=').ok_or_else(invalid)?;
+                let (color, _) = COLORS
+                    .iter()
+                    .find(|(_, n)| *n == name.trim())
+                    .ok_or_else(invalid)?;
+                let rgb = u32::from_str_radix(hex.trim().trim_start_matches('#'), 16)
+                    .ok()
+                    .filter(|_| hex.trim().trim_start_matches('#').len() == 6)
+                    .ok_or_else(invalid)?;
+                let [_, r, g, b] = rgb.to_be_bytes();
+                Ok(palette.set(*color, [r, g, b]))
+            })
+    }
+}
+
+/// Writes frames as PNG stills and animated GIFs, one pixel block of
+/// `scale`×`scale` per cell.
+pub struct Exporter {
+    directory: PathBuf,
+    scale: u32,
+    palette: Palette,
+    delay: Duration,
+    every: usize,
+    skip: usize,
+    /// The animation being written, with the size of its frames in cells.
+    gif: Option<(PathBuf, IVec2, gif::Encoder<BufWriter<File>>)>,
+}
+
+impl Exporter {
+    pub fn new(directory: impl Into<PathBuf>) -> Exporter {
+        Exporter {
+            directory: directory.into(),
+            scale: 4,
+            palette: Palette::default(),
+            delay: Duration::from_millis(50),
+            every: 1,
+            skip: 0,
+            gif: None,
+        }
+    }
+
+    /// Only exports when `AOC_EXPORT_DIR` is set. `AOC_EXPORT_SCALE`,
+    /// `AOC_EXPORT_EVERY` and `AOC_EXPORT_PALETTE` override the defaults, and
+    /// are reported when they don't parse rather than ignored.
+    pub fn from_env() -> Result<Option<Exporter>, ExportError> {
+        let Some(directory) = env::var_os("AOC_EXPORT_DIR") else {
+            return Ok(None);
+        };
+
+        let mut exporter = Exporter::new(directory);
+        if let Some(scale) = env_number("AOC_EXPORT_SCALE")? {
+            exporter = exporter.scale(scale);
+        }
+        if let Some(every) = env_number("AOC_EXPORT_EVERY")? {
+            exporter = exporter.every(every);
+        }
+        if let Ok(palette) = env::var("AOC_EXPORT_PALETTE") {
+            exporter = exporter.palette(palette.parse()?);
+        }
+        Ok(Some(exporter))
+    }
+
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Time each GIF frame stays on screen, in steps of 10ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Keep only one out of every `every` frames pushed to an animation, for
+    /// simulations that run for thousands of steps.
+    pub fn every(mut self, every: usize) -> Self {
+        self.every = every.max(1);
+        self
+    }
+
+    fn pixel_size(&self, frame: &impl Frame) -> Result<(u16, u16), ExportError> {
+        let size = frame.size();
+        let pixels = |cells: i32| {
+            u32::try_from(cells)
+                .ok()
+                .and_then(|cells| cells.checked_mul(self.scale))
+                .and_then(|pixels| u16::try_from(pixels).ok())
+                .ok_or(ExportError::TooLarge(size))
+        };
+        Ok((pixels(size.x)?, pixels(size.y)?))
+    }
+
+    /// Palette index of every pixel, row by row.
+    fn pixels(&self, frame: &impl Frame) -> Vec<u8> {
+        let size = frame.size();
+        let scale = self.scale as usize;
+        let mut pixels = Vec::with_capacity(size.x as usize * size.y as usize * scale * scale);
+        for y in 0..size.y {
+            let row = (0..size.x)
+                .flat_map(|x| {
+                    let color = frame.cell(IVec2::new(x, y)).color;
+                    std::iter::repeat_n(Palette::index(color) as u8, scale)
+                })
+                .collect::<Vec<u8>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+        pixels
+    }
+
+    fn create_file(&self, file_name: &str) -> Result<(PathBuf, BufWriter<File>), ExportError> {
+        fs::create_dir_all(&self.directory)?;
+        let path = self.directory.join(file_name);
+        let file = BufWriter::new(File::create(&path)?);
+        Ok((path, file))
+    }
+
+    /// Writes a single frame to `<name>.png`.
+    pub fn still(&self, name: &str, frame: &impl Frame) -> Result<PathBuf, ExportError> {
+        let (width, height) = self.pixel_size(frame)?;
+        let (path, file) = self.create_file(&format!("{name}.png"))?;
+
+        let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+
+        let rgb = self
+            .pixels(frame)
+            .into_iter()
+            .flat_map(|index| self.palette.0[index as usize])
+            .collect::<Vec<u8>>();
+        writer.write_image_data(&rgb)?;
+        writer.finish()?;
+
+        Ok(path)
+    }
+
+    /// Appends a frame to `<name>.gif`, closing the previous animation when
+    /// the name changes. Every frame of an animation must have the same size.
+    pub fn push(&mut self, name: &str, frame: &impl Frame) -> Result<(), ExportError> {
+        let (width, height) = self.pixel_size(frame)?;
+        let file_name = format!("{name}.gif");
+
+        if self
+            .gif
+            .as_ref()
+            .is_some_and(|(path, _, _)| !path.ends_with(&file_name))
+        {
+            self.finish();
+        }
+        if let Some((_, expected, _)) = &self.gif {
+            if *expected != frame.size() {
+                return Err(ExportError::SizeMismatch {
+                    expected: *expected,
+                    found: frame.size(),
+                });
+            }
+        }
+
+        if self.gif.is_none() {
+            self.skip = 0;
+            let (path, file) = self.create_file(&file_name)?;
+            let mut palette = self.palette.0.concat();
+            // global colour tables come in powers of two
+            palette.resize(16 * 3, 0);
+            let mut encoder = gif::Encoder::new(file, width, height, &palette)?;
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+            self.gif = Some((path, frame.size(), encoder));
+        }
+
+        if self.skip > 0 {
+            self.skip -= 1;
+            return Ok(());
+        }
+        self.skip = self.every - 1;
+
+        let mut gif_frame =
+            gif::Frame::from_indexed_pixels(width, height, self.pixels(frame), None);
+        gif_frame.delay = (self.delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+
+        let (_, _, encoder) = self.gif.as_mut().unwrap();
+        encoder.write_frame(&gif_frame)?;
+        Ok(())
+    }
+
+    /// Closes the current animation, returning where it was written.
+    pub fn finish(&mut self) -> Option<PathBuf> {
+        self.gif.take().map(|(path, _, _)| path)
+    }
+}
+
+/// The number in an environment variable, `None` when it isn't set.
+fn env_number<T: FromStr>(variable: &'static str) -> Result<Option<T>, ExportError> {
+    let invalid = |value: String| ExportError::InvalidNumber { variable, value };
+    match env::var(variable) {
+        Ok(value) => value.trim().parse().map(Some).map_err(|_| invalid(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(value)) => Err(invalid(value.to_string_lossy().into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Cell;
+
+    struct Stripes;
+
+    impl Frame for Stripes {
+        fn size(&self) -> IVec2 {
+            IVec2::new(2, 2)
+        }
+
+        fn cell(&self, position: IVec2) -> Cell {
+            match position.x {
+                0 => Cell::new('#', Color::Red),
+                _ => '.'.into(),
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn palette_overrides() {
+        let palette: Palette = "default=000000, green=#00ff00".parse().unwrap();
+        assert_eq!([0, 0, 0], palette.get(Color::Default));
+        assert_eq!([0, 0xff, 0], palette.get(Color::Green));
+        assert_eq!(Palette::default().get(Color::Red), palette.get(Color::Red));
+
+        for invalid in ["pink=ff00ff", "red", "red=ff00", "red=gg0000"] {
+            assert!(matches!(
+                invalid.parse::<Palette>(),
+                Err(ExportError::InvalidPalette(_))
+            ));
+        }
+    }
+
+    #[test_log::test]
+    fn numbers_from_env() {
+        let variable = "AOC_EXPORT_TEST_NUMBER";
+        assert!(matches!(env_number::<u32>(variable), Ok(None)));
+
+        env::set_var(variable, " 3 ");
+        assert!(matches!(env_number::<u32>(variable), Ok(Some(3))));
+        for invalid in ["three", "-1", "1.5", ""] {
+            env::set_var(variable, invalid);
+            assert!(matches!(
+                env_number::<u32>(variable),
+                Err(ExportError::InvalidNumber { value, .. }) if value == invalid
+            ));
+        }
+        env::remove_var(variable);
+    }
+
+    #[test_log::test]
+    fn scaled_pixels() {
+        let exporter = Exporter::new("unused").scale(2);
+        let red = Palette::index(Color::Red) as u8;
+        let background = Palette::index(Color::Default) as u8;
+        let row = [red, red, background, background];
+
+        assert_eq!((4, 4), exporter.pixel_size(&Stripes).unwrap());
+        assert_eq!([row; 4].concat(), exporter.pixels(&Stripes));
+    }
+
+    struct Blank(IVec2);
+
+    impl Frame for Blank {
+        fn size(&self) -> IVec2 {
+            self.0
+        }
+
+        fn cell(&self, _: IVec2) -> Cell {
+            '.'.into()
+        }
+    }
+
+    #[test_log::test]
+    fn frames_too_large() {
+        let exporter = Exporter::new("unused");
+        assert_eq!(
+            (65_532, 4),
+            exporter.pixel_size(&Blank(IVec2::new(16_383, 1))).unwrap()
+        );
+        for size in [IVec2::new(16_384, 1), IVec2::new(-1, 1)] {
+            assert!(matches!(
+                exporter.pixel_size(&Blank(size)),
+                Err(ExportError::TooLarge(_))
+            ));
+        }
+        assert!(matches!(
+            Exporter::new("unused")
+                .scale(u32::MAX)
+                .pixel_size(&Blank(IVec2::new(2, 1))),
+            Err(ExportError::TooLarge(_))
+        ));
+    }
+
+    #[test_log::test]
+    fn frames_of_another_size() {
+        let directory = env::temp_dir().join(format!("aoc-export-size-{}", std::process::id()));
+        let mut exporter = Exporter::new(&directory);
+
+        exporter.push("stripes", &Stripes).unwrap();
+        assert!(matches!(
+            exporter.push("stripes", &Blank(IVec2::new(3, 2))),
+            Err(ExportError::SizeMismatch { expected, found })
+                if expected == IVec2::new(2, 2) && found == IVec2::new(3, 2)
+        ));
+        // a new animation can have any size
+        exporter.push("blank", &Blank(IVec2::new(3, 2))).unwrap();
+        exporter.finish();
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test_log::test]
+    fn png_and_gif_files() {
+        let directory = env::temp_dir().join(format!("aoc-export-{}", std::process::id()));
+        let mut exporter = Exporter::new(&directory).scale(3);
+
+        let still = exporter.still("stripes", &Stripes).unwrap();
+        assert!(fs::read(&still).unwrap().starts_with(b"\x89PNG"));
+
+        exporter.push("stripes", &Stripes).unwrap();
+        exporter.push("stripes", &Stripes).unwrap();
+        exporter.push("more-stripes", &Stripes).unwrap();
+        let animation = exporter.finish().unwrap();
+        assert!(animation.ends_with("more-stripes.gif"));
+        for name in ["stripes.gif", "more-stripes.gif"] {
+            assert!(fs::read(directory.join(name))
+                .unwrap()
+                .starts_with(b"GIF89a"));
+        }
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test_log::test]
+    fn every_nth_frame() {
+        let directory = env::temp_dir().join(format!("aoc-export-every-{}", std::process::id()));
+        let mut exporter = Exporter::new(&directory).every(2);
+        for _ in 0..5 {
+            exporter.push("stripes", &Stripes).unwrap();
+        }
+        let animation = exporter.finish().unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(animation).unwrap())
+            .unwrap();
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(3, frames);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+use glam::IVec2;
+
+/// The handful of colours every output backend knows how to draw.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    #[default]
+    Default,
+    DarkGrey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, color: Color) -> Cell {
+        Cell { glyph, color }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        Cell::new(glyph, Color::Default)
+    }
+}
+
+/// A single step of a grid simulation. Days implement this for a view over
+/// their state and hand it to whichever renderer is enabled.
+pub trait Frame {
+    /// Width and height of the grid, in cells.
+    fn size(&self) -> IVec2;
+
+    /// Called for every position inside `size`, row by row.
+    fn cell(&self, position: IVec2) -> Cell;
+
+    /// A line of text shown along with the grid, like the step number.
+    fn caption(&self) -> String {
+        String::new()
+    }
+
+    /// The grid without colours, one line per row.
+    fn render_plain(&self) -> String {
+        let size = self.size();
+        let mut output = String::with_capacity(((size.x + 1) * size.y) as usize);
+        for y in 0..size.y {
+            for x in 0..size.x {
+                output.push(self.cell(IVec2::new(x, y)).glyph);
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Checkerboard;
+
+    impl Frame for Checkerboard {
+        fn size(&self) -> IVec2 {
+            IVec2::new(3, 2)
+        }
+
+        fn cell(&self, position: IVec2) -> Cell {
+            match (position.x + position.y) % 2 {
+                0 => Cell::new('#', Color::White),
+                _ => '.'.into(),
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn render_plain() {
+        assert_eq!("#.#\n.#.\n", Checkerboard.render_plain());
+        assert_eq!("", Checkerboard.caption());
+    }
+}
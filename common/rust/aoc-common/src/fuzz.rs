@@ -0,0 +1,394 @@
+use std::{
+    env, fmt, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Defines `main` for a fuzz target, like `libfuzzer-sys` does, so targets
+/// in `fuzz/fuzz_targets/` read the same as `cargo fuzz` ones.
+///
+/// The corpus is read from `corpus/<target>/` and crashes are written to
+/// `artifacts/<target>/`, both next to the fuzz crate's manifest.
+#[macro_export]
+macro_rules! fuzz_target {
+    (|$data:ident: &[u8]| $body:block) => {
+        fn main() -> std::process::ExitCode {
+            $crate::fuzz::Fuzzer::new(env!("CARGO_BIN_NAME"), env!("CARGO_MANIFEST_DIR"))
+                .run(|$data: &[u8]| $body)
+        }
+    };
+}
+
+/// A mutation based fuzzer with no coverage feedback: every run takes an
+/// input from the corpus, applies a few random mutations to it and hands it
+/// to the target. A panic or a run slower than the timeout is a finding.
+///
+/// Takes libFuzzer style flags (`-runs=N`, `-seed=N`, `-timeout=SECS`,
+/// `-max_len=N`); any other argument is an artifact to replay instead.
+#[derive(Debug, Clone)]
+pub struct Fuzzer {
+    name: String,
+    corpus: PathBuf,
+    artifacts: PathBuf,
+    runs: u64,
+    seed: u64,
+    timeout: Duration,
+    max_len: usize,
+    replay: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    Crash { input: Vec<u8>, message: String },
+    Timeout { input: Vec<u8> },
+}
+
+impl Fuzzer {
+    pub fn new(name: &str, dir: impl AsRef<Path>) -> Fuzzer {
+        let dir = dir.as_ref();
+        Fuzzer {
+            name: name.to_string(),
+            corpus: dir.join("corpus").join(name),
+            artifacts: dir.join("artifacts").join(name),
+            runs: 100_000,
+            seed: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(1, |since| since.as_nanos() as u64),
+            timeout: Duration::from_secs(2),
+            max_len: 4096,
+            replay: vec![],
+        }
+    }
+
+    pub fn args(mut self, args: impl IntoIterator<Item = String>) -> Result<Fuzzer, String> {
+        for arg in args {
+            let Some((flag, value)) = arg.strip_prefix('-').and_then(|flag| flag.split_once('='))
+            else {
+                self.replay.push(PathBuf::from(arg));
+                continue;
+            };
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|e| format!("invalid value for -{flag}: {e}"))
+            };
+            match flag {
+                "runs" => self.runs = number()?,
+                "seed" => self.seed = number()?,
+                "timeout" => self.timeout = Duration::from_secs(number()?),
+                "max_len" => self.max_len = number()? as usize,
+                _ => return Err(format!("unknown flag -{flag}")),
+            }
+        }
+        Ok(self)
+    }
+
+    /// Fuzzes `target` with the command line arguments, writing and printing
+    /// the first finding.
+    pub fn run(self, target: impl Fn(&[u8])) -> ExitCode {
+        let fuzzer = match self.args(env::args().skip(1)) {
+            Ok(fuzzer) => fuzzer,
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::from(2);
+            }
+        };
+
+        if !fuzzer.replay.is_empty() {
+            for path in fuzzer.replay.iter() {
+                eprintln!("running {}", path.display());
+                match fs::read(path) {
+                    Ok(input) => target(&input),
+                    Err(e) => {
+                        eprintln!("can't read {}: {e}", path.display());
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            return ExitCode::SUCCESS;
+        }
+
+        let corpus = match fuzzer.corpus() {
+            Ok(corpus) => corpus,
+            Err(e) => {
+                eprintln!("can't read the corpus in {}: {e}", fuzzer.corpus.display());
+                return ExitCode::FAILURE;
+            }
+        };
+        eprintln!(
+            "fuzzing {} with seed {}, {} corpus inputs",
+            fuzzer.name,
+            fuzzer.seed,
+            corpus.len()
+        );
+
+        let running = Running::default();
+        fuzzer.watch(running.clone());
+
+        let start = Instant::now();
+        let finding = fuzzer.find(&corpus, |input| {
+            *running.lock().unwrap() = Some((Instant::now(), input.to_vec()));
+            target(input);
+            *running.lock().unwrap() = None;
+        });
+
+        match finding {
+            Some(finding) => {
+                fuzzer.report(&finding);
+                ExitCode::FAILURE
+            }
+            None => {
+                eprintln!(
+                    "done: {} runs in {:.1?}, nothing found",
+                    fuzzer.runs,
+                    start.elapsed()
+                );
+                ExitCode::SUCCESS
+            }
+        }
+    }
+
+    /// Runs the corpus as is and then mutated inputs until `target` panics
+    /// or the runs are used up.
+    pub fn find(&self, corpus: &[Vec<u8>], target: impl Fn(&[u8])) -> Option<Finding> {
+        let message = Arc::new(Mutex::new(String::new()));
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new({
+            let message = message.clone();
+            move |info| *message.lock().unwrap() = info.to_string()
+        }));
+
+        let mut rng = Rng::new(self.seed);
+        let mut finding = None;
+        for run in 0..self.runs {
+            let input = match corpus.get(run as usize) {
+                Some(input) => input.clone(),
+                None if corpus.is_empty() => rng.mutated(&[], corpus, self.max_len),
+                None => {
+                    let input = &corpus[rng.below(corpus.len())];
+                    rng.mutated(input, corpus, self.max_len)
+                }
+            };
+
+            if panic::catch_unwind(AssertUnwindSafe(|| target(&input))).is_err() {
+                finding = Some(Finding::Crash {
+                    input,
+                    message: message.lock().unwrap().clone(),
+                });
+                break;
+            }
+            if run.is_power_of_two() && run >= 1024 {
+                eprintln!("#{run}");
+            }
+        }
+
+        panic::set_hook(hook);
+        finding
+    }
+
+    fn corpus(&self) -> std::io::Result<Vec<Vec<u8>>> {
+        if !self.corpus.exists() {
+            return Ok(vec![]);
+        }
+        let mut paths = fs::read_dir(&self.corpus)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+        paths.into_iter().map(fs::read).collect()
+    }
+
+    /// Reports the running input and exits once it takes longer than the
+    /// timeout, since there's no stopping the target from the outside.
+    fn watch(&self, running: Running) {
+        let fuzzer = self.clone();
+        let interval = (self.timeout / 4).max(Duration::from_millis(10));
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let timed_out = running
+                .lock()
+                .unwrap()
+                .as_ref()
+                .filter(|(since, _)| since.elapsed() > fuzzer.timeout)
+                .map(|(_, input)| input.clone());
+            if let Some(input) = timed_out {
+                fuzzer.report(&Finding::Timeout { input });
+                std::process::exit(1);
+            }
+        });
+    }
+
+    fn report(&self, finding: &Finding) {
+        let (kind, input) = match finding {
+            Finding::Crash { input, message } => {
+                eprintln!("{message}");
+                ("crash", input)
+            }
+            Finding::Timeout { input } => {
+                eprintln!("the run took longer than {:?}", self.timeout);
+                ("timeout", input)
+            }
+        };
+
+        let mut hasher = DefaultHasher::new();
+        input.hash(&mut hasher);
+        let path = self
+            .artifacts
+            .join(format!("{kind}-{:016x}", hasher.finish()));
+        match fs::create_dir_all(&self.artifacts).and_then(|_| fs::write(&path, input)) {
+            Ok(()) => eprintln!("{kind}, input written to {}", path.display()),
+            Err(e) => eprintln!("{kind}, can't write {}: {e}", path.display()),
+        }
+        eprintln!("{}", Escaped(input));
+    }
+}
+
+/// The input being run and since when, `None` in between runs.
+type Running = Arc<Mutex<Option<(Instant, Vec<u8>)>>>;
+
+/// xorshift64*, plenty for picking mutations.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    fn mutated(&mut self, input: &[u8], corpus: &[Vec<u8>], max_len: usize) -> Vec<u8> {
+        const BYTES: &[u8] = b"0123456789-+,.:;=#<>{}[]()%&|/ \n\r\tABRSamsxz";
+        const NUMBERS: &[&str] = &[
+            "0",
+            "1",
+            "-1",
+            "7",
+            "8",
+            "255",
+            "65536",
+            "2147483647",
+            "-2147483648",
+            "4294967296",
+            "9223372036854775807",
+            "18446744073709551616",
+        ];
+
+        let mut data = input.to_vec();
+        for _ in 0..=self.below(8) {
+            let at = self.below(data.len() + 1);
+            match self.below(7) {
+                0 if at < data.len() => data[at] ^= 1 << self.below(8),
+                1 if at < data.len() => data[at] = *self.pick(BYTES),
+                2 => data.insert(at, *self.pick(BYTES)),
+                3 if at < data.len() => {
+                    let end = at + 1 + self.below((data.len() - at).min(16));
+                    data.drain(at..end);
+                }
+                4 if at < data.len() => {
+                    let end = at + 1 + self.below((data.len() - at).min(64));
+                    let chunk = data[at..end].to_vec();
+                    let to = self.below(data.len() + 1);
+                    data.splice(to..to, chunk);
+                }
+                5 if !corpus.is_empty() => {
+                    let other = self.pick(corpus);
+                    let from = self.below(other.len() + 1);
+                    let end = from + self.below(other.len() - from + 1).min(64);
+                    data.splice(at..at, other[from..end].iter().copied());
+                }
+                6 => {
+                    let start = data[..at]
+                        .iter()
+                        .rposition(|b| !b.is_ascii_digit())
+                        .map_or(0, |i| i + 1);
+                    let end = data[at..]
+                        .iter()
+                        .position(|b| !b.is_ascii_digit())
+                        .map_or(data.len(), |i| at + i);
+                    data.splice(start..end, self.pick(NUMBERS).bytes());
+                }
+                _ => data.push(self.next() as u8),
+            }
+        }
+        data.truncate(max_len);
+        data
+    }
+}
+
+/// The input as an escaped string, so it can be pasted into a test.
+struct Escaped<'a>(&'a [u8]);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0.escape_ascii())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzer(args: &[&str]) -> Fuzzer {
+        Fuzzer::new("target", "fuzz")
+            .args(args.iter().map(|arg| arg.to_string()))
+            .expect("should take the flags")
+    }
+
+    #[test_log::test]
+    fn flags() {
+        let fuzzer = fuzzer(&["-runs=10", "-seed=3", "-timeout=5", "crash-1"]);
+        assert_eq!(10, fuzzer.runs);
+        assert_eq!(3, fuzzer.seed);
+        assert_eq!(Duration::from_secs(5), fuzzer.timeout);
+        assert_eq!(vec![PathBuf::from("crash-1")], fuzzer.replay);
+        assert_eq!(Path::new("fuzz/corpus/target"), fuzzer.corpus);
+        assert!(Fuzzer::new("target", "fuzz")
+            .args(["-jobs=4".to_string()])
+            .is_err());
+    }
+
+    #[test_log::test]
+    fn finds_a_crash() {
+        let corpus = vec![b"Register A: 7".to_vec()];
+        let finding = fuzzer(&["-runs=100000", "-seed=1"]).find(&corpus, |input| {
+            let input = String::from_utf8_lossy(input);
+            if input.contains("A: 2147483647") {
+                panic!("overflow");
+            }
+        });
+
+        let Some(Finding::Crash { input, message }) = finding else {
+            panic!("should find the crash, found {finding:?}");
+        };
+        assert!(String::from_utf8_lossy(&input).contains("A: 2147483647"));
+        assert!(message.contains("overflow"));
+        assert_eq!(None, fuzzer(&["-runs=1000"]).find(&corpus, |_| {}));
+    }
+
+    #[test_log::test]
+    fn mutations_stay_short() {
+        let mut rng = Rng::new(7);
+        let corpus = vec![vec![b'#'; 100]];
+        for _ in 0..1000 {
+            assert!(rng.mutated(&corpus[0], &corpus, 64).len() <= 64);
+        }
+        assert_eq!(r#""a\n\"""#, Escaped(b"a\n\"").to_string());
+    }
+}
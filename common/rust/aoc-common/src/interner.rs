@@ -0,0 +1,118 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+/// Dense identifiers handed out by an [`Interner`], usable directly as
+/// indices into a `Vec`.
+pub trait InternId: Copy + Eq + Hash + Debug {
+    fn from_index(index: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_intern_id {
+    ($($ty:ty),*) => {
+        $(
+            impl InternId for $ty {
+                fn from_index(index: usize) -> Self {
+                    <$ty>::try_from(index).expect(concat!("interner ran out of ", stringify!($ty), " ids"))
+                }
+
+                fn index(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_intern_id!(u16, u32);
+
+/// Maps puzzle identifiers (node names, wire names, ...) to dense ids in
+/// the order they are first seen, keeping the names around for display.
+#[derive(Debug, Clone)]
+pub struct Interner<I = u32> {
+    ids: HashMap<Box<str>, I>,
+    names: Vec<Box<str>>,
+}
+
+impl<I: InternId> Default for Interner<I> {
+    fn default() -> Self {
+        Interner {
+            ids: HashMap::new(),
+            names: Vec::new(),
+        }
+    }
+}
+
+impl<I: InternId> Interner<I> {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    pub fn intern(&mut self, name: &str) -> I {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = I::from_index(self.names.len());
+        self.names.push(name.into());
+        self.ids.insert(name.into(), id);
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<I> {
+        self.ids.get(name).copied()
+    }
+
+    /// Panics if the id wasn't handed out by this interner.
+    pub fn resolve(&self, id: I) -> &str {
+        &self.names[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (I, &str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (I::from_index(index), name.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn dense_ids_in_insertion_order() {
+        let mut interner = Interner::<u16>::new();
+        assert!(interner.is_empty());
+
+        assert_eq!(0, interner.intern("broadcaster"));
+        assert_eq!(1, interner.intern("a"));
+        assert_eq!(0, interner.intern("broadcaster"));
+        assert_eq!(2, interner.intern("inv"));
+
+        assert_eq!(3, interner.len());
+        assert_eq!(Some(1), interner.get("a"));
+        assert_eq!(None, interner.get("rx"));
+        assert_eq!("inv", interner.resolve(2));
+        assert_eq!(
+            vec![(0, "broadcaster"), (1, "a"), (2, "inv")],
+            interner.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test_log::test]
+    #[should_panic(expected = "interner ran out of u16 ids")]
+    fn overflowing_ids() {
+        let mut interner = Interner::<u16>::new();
+        for i in 0..=u16::MAX as u32 + 1 {
+            interner.intern(&i.to_string());
+        }
+    }
+}
@@ -0,0 +1,19 @@
+pub mod direction;
+pub mod dot;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod frame;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod interner;
+pub mod matcher;
+pub mod memory;
+pub mod parsers;
+#[cfg(feature = "profile")]
+pub mod profile;
+#[cfg(feature = "runner")]
+pub mod runner;
+#[cfg(feature = "visualize")]
+pub mod terminal;
+#[cfg(feature = "tui")]
+pub mod tui;
@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+
+/// An occurrence of one of a [`Matcher`]'s patterns at `start..end` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'a, T> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a T,
+}
+
+/// Aho–Corasick automaton over a vocabulary of byte patterns, each mapped to
+/// a value, that finds every occurrence (overlapping ones included) in a
+/// single pass over the haystack.
+///
+/// A reversed matcher holds its patterns back to front and scans from the
+/// right end, so the first thing it finds is the rightmost occurrence.
+#[derive(Debug, Clone)]
+pub struct Matcher<T> {
+    /// 256 transitions per state with the failure links folded in, so
+    /// scanning is one lookup per byte.
+    transitions: Vec<u32>,
+    /// Patterns recognised on reaching each state, longest first.
+    outputs: Vec<Vec<usize>>,
+    patterns: Vec<(usize, T)>,
+    max_len: usize,
+    reversed: bool,
+}
+
+impl<T> Matcher<T> {
+    /// Panics on an empty pattern.
+    pub fn new<P: AsRef<[u8]>>(vocabulary: impl IntoIterator<Item = (P, T)>) -> Self {
+        Matcher::build(vocabulary, false)
+    }
+
+    /// Like [`Matcher::new`], scanning right to left.
+    pub fn reversed<P: AsRef<[u8]>>(vocabulary: impl IntoIterator<Item = (P, T)>) -> Self {
+        Matcher::build(vocabulary, true)
+    }
+
+    fn build<P: AsRef<[u8]>>(vocabulary: impl IntoIterator<Item = (P, T)>, reversed: bool) -> Self {
+        // state 0 is the root, which is never a child, so 0 doubles as
+        // "no transition" while the trie is built
+        let mut transitions = vec![0; 256];
+        let mut outputs = vec![Vec::new()];
+        let mut patterns = Vec::new();
+
+        for (pattern, value) in vocabulary {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "matcher patterns can't be empty");
+
+            let mut state = 0;
+            for i in 0..pattern.len() {
+                let byte = if reversed {
+                    pattern[pattern.len() - 1 - i]
+                } else {
+                    pattern[i]
+                };
+                let slot = state * 256 + byte as usize;
+                if transitions[slot] == 0 {
+                    transitions[slot] = outputs.len() as u32;
+                    transitions.extend([0; 256]);
+                    outputs.push(Vec::new());
+                }
+                state = transitions[slot] as usize;
+            }
+            outputs[state].push(patterns.len());
+            patterns.push((pattern.len(), value));
+        }
+
+        // breadth first, so a state's failure target is complete before the
+        // state borrows its transitions and outputs
+        let mut fail = vec![0; outputs.len()];
+        let mut queue = transitions[..256]
+            .iter()
+            .filter(|&&child| child != 0)
+            .map(|&child| child as usize)
+            .collect::<VecDeque<usize>>();
+        while let Some(state) = queue.pop_front() {
+            // inherited patterns are suffixes of this state's own, so shorter
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+
+            for byte in 0..256 {
+                let fallback = transitions[fail[state] * 256 + byte];
+                match transitions[state * 256 + byte] {
+                    0 => transitions[state * 256 + byte] = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+
+        let max_len = patterns.iter().map(|(len, _)| *len).max().unwrap_or(0);
+        Matcher {
+            transitions,
+            outputs,
+            patterns,
+            max_len,
+            reversed,
+        }
+    }
+
+    /// Every occurrence, in the order the scan finishes them.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = Match<'a, T>> + 'a {
+        let mut state = 0;
+        (1..=haystack.len()).flat_map(move |scanned| {
+            state = self.step(state, haystack, scanned);
+            self.outputs[state]
+                .iter()
+                .map(move |&pattern| self.matched(haystack, scanned, pattern))
+        })
+    }
+
+    /// The occurrence closest to where the scan starts, the longest one on
+    /// ties. Stops as soon as no later occurrence could be closer.
+    pub fn first<'a>(&'a self, haystack: &'a [u8]) -> Option<Match<'a, T>> {
+        // (distance from the scan's start, pattern, bytes scanned)
+        let mut best: Option<(usize, usize, usize)> = None;
+        let mut state = 0;
+
+        for scanned in 1..=haystack.len() {
+            if best.is_some_and(|(distance, ..)| distance + self.max_len < scanned) {
+                break;
+            }
+
+            state = self.step(state, haystack, scanned);
+            if let Some(&pattern) = self.outputs[state].first() {
+                let distance = scanned - self.patterns[pattern].0;
+                // a later end at the same distance is a longer pattern
+                if best.is_none_or(|(best_distance, ..)| distance <= best_distance) {
+                    best = Some((distance, pattern, scanned));
+                }
+            }
+        }
+
+        best.map(|(_, pattern, scanned)| self.matched(haystack, scanned, pattern))
+    }
+
+    fn step(&self, state: usize, haystack: &[u8], scanned: usize) -> usize {
+        let byte = if self.reversed {
+            haystack[haystack.len() - scanned]
+        } else {
+            haystack[scanned - 1]
+        };
+        self.transitions[state * 256 + byte as usize] as usize
+    }
+
+    fn matched(&self, haystack: &[u8], scanned: usize, pattern: usize) -> Match<'_, T> {
+        let (len, value) = &self.patterns[pattern];
+        let (start, end) = if self.reversed {
+            (haystack.len() - scanned, haystack.len() - scanned + len)
+        } else {
+            (scanned - len, scanned)
+        };
+        Match { start, end, value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans<'a, T: Copy + 'a>(
+        matches: impl Iterator<Item = Match<'a, T>>,
+    ) -> Vec<(usize, usize, T)> {
+        matches.map(|m| (m.start, m.end, *m.value)).collect()
+    }
+
+    #[test_log::test]
+    fn overlapping_matches() {
+        let matcher = Matcher::new([("he", 0), ("she", 1), ("his", 2), ("hers", 3)]);
+        assert_eq!(
+            vec![(1, 4, 1), (2, 4, 0), (2, 6, 3)],
+            spans(matcher.find_iter(b"ushers"))
+        );
+
+        let reversed = Matcher::reversed([("he", 0), ("she", 1), ("his", 2), ("hers", 3)]);
+        let mut expected = spans(matcher.find_iter(b"ushers"));
+        let mut found = spans(reversed.find_iter(b"ushers"));
+        expected.sort();
+        found.sort();
+        assert_eq!(expected, found);
+    }
+
+    #[test_log::test]
+    fn first_from_either_end() {
+        let vocabulary = [
+            ("two", 2),
+            ("one", 1),
+            ("eight", 8),
+            ("bcd", 0),
+            ("abcde", 5),
+        ];
+        let matcher = Matcher::new(vocabulary);
+        let reversed = Matcher::reversed(vocabulary);
+
+        assert_eq!(Some(2), matcher.first(b"xtwone3").map(|m| *m.value));
+        assert_eq!(Some(1), reversed.first(b"xtwone3").map(|m| *m.value));
+        // `bcd` finishes first, but `abcde` starts further left
+        assert_eq!(
+            Some(Match {
+                start: 0,
+                end: 5,
+                value: &5
+            }),
+            matcher.first(b"abcdeight")
+        );
+        assert_eq!(Some(8), reversed.first(b"abcdeight").map(|m| *m.value));
+        assert_eq!(None, matcher.first(b"nothing here"));
+    }
+
+    #[test_log::test]
+    fn longest_of_nested_patterns() {
+        // the shorter pattern finishes first, the longer one must still win
+        let prefixes = Matcher::new([("a", 1), ("abc", 3)]);
+        assert_eq!(
+            Some(Match {
+                start: 0,
+                end: 3,
+                value: &3
+            }),
+            prefixes.first(b"abc")
+        );
+        assert_eq!(Some(1), prefixes.first(b"abx").map(|m| *m.value));
+
+        let suffixes = Matcher::reversed([("c", 1), ("abc", 3)]);
+        assert_eq!(
+            Some(Match {
+                start: 0,
+                end: 3,
+                value: &3
+            }),
+            suffixes.first(b"abc")
+        );
+        assert_eq!(Some(1), suffixes.first(b"xbc").map(|m| *m.value));
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, keeping count of live bytes, the peak of live
+/// bytes and the number of allocations. Installed as the global allocator
+/// with the `memory` feature.
+pub struct Counting;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[allow(unsafe_code)]
+unsafe impl std::alloc::GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = std::alloc::System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            Counting::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+impl Counting {
+    fn allocated(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Whether the counting allocator is the one handing out memory.
+    pub fn is_installed() -> bool {
+        // the runtime allocates before main, so this is set by the time
+        // anyone asks
+        INSTALLED.load(Ordering::Relaxed)
+    }
+}
+
+/// Memory used while running a closure, from all threads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    /// Highest amount of bytes live at once, on top of what was live before.
+    pub peak_bytes: usize,
+    pub allocations: usize,
+    /// Bytes allocated during the run and still live after it, usually the
+    /// output.
+    pub live_bytes: isize,
+}
+
+/// Runs `f`, returning the memory it used. All zeros unless the counting
+/// allocator is installed.
+///
+/// Calls may nest, an outer one still seeing the peak of an inner one. The
+/// counters are process-wide though, so measuring from two threads at once
+/// mixes up both reports: only call this from one thread at a time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Report) {
+    let live_before = LIVE.load(Ordering::Relaxed);
+    let outer_peak = PEAK.swap(live_before, Ordering::Relaxed);
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let output = f();
+
+    let live_after = LIVE.load(Ordering::Relaxed);
+    // an enclosing call's peak was only set aside, so put it back
+    let peak = PEAK.fetch_max(outer_peak, Ordering::Relaxed);
+    let report = Report {
+        peak_bytes: peak.saturating_sub(live_before),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations_before,
+        live_bytes: live_after as isize - live_before as isize,
+    };
+    (output, report)
+}
+
+/// Runs `f`, printing its [`Report`] to stderr when the counting allocator is
+/// installed.
+pub fn report<T>(f: impl FnOnce() -> T) -> T {
+    let (output, report) = measure(f);
+    if Counting::is_installed() {
+        eprintln!("memory: {report}");
+    }
+    output
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations, {}{} live",
+            Bytes(self.peak_bytes),
+            self.allocations,
+            if self.live_bytes < 0 { "-" } else { "" },
+            Bytes(self.live_bytes.unsigned_abs()),
+        )
+    }
+}
+
+struct Bytes(usize);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn human_readable_report() {
+        let report = Report {
+            peak_bytes: 3 * 1024 * 1024 + 512 * 1024,
+            allocations: 42,
+            live_bytes: -100,
+        };
+        assert_eq!(
+            "peak 3.5 MiB, 42 allocations, -100 B live",
+            report.to_string()
+        );
+        assert_eq!("1.0 KiB", Bytes(1024).to_string());
+    }
+
+    #[cfg(feature = "memory")]
+    #[test_log::test]
+    fn counts_allocations() {
+        let ((), report) = measure(|| {
+            let scratch = vec![0u8; 1 << 20];
+            drop(scratch);
+            drop(Vec::<u64>::with_capacity(16));
+        });
+
+        assert!(Counting::is_installed());
+        assert!(report.peak_bytes >= 1 << 20);
+        assert!(report.allocations >= 2);
+    }
+
+    #[cfg(feature = "memory")]
+    #[test_log::test]
+    fn nested_measures() {
+        // the inner call sets the peak aside while the outer one's is 1 MiB
+        let (inner, outer) = measure(|| {
+            drop(vec![0u8; 1 << 20]);
+            measure(|| drop(vec![0u8; 1 << 10])).1
+        });
+
+        assert!(inner.peak_bytes >= 1 << 10);
+        assert!(outer.peak_bytes >= 1 << 20);
+    }
+}
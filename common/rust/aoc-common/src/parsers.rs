@@ -0,0 +1,189 @@
+use std::str::FromStr;
+
+use glam::{I64Vec3, IVec2, IVec3};
+use nom::{
+    character::complete::{self, char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map_res, opt, recognize},
+    error::Error,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    IResult, Parser,
+};
+
+/// An optionally signed integer, `-3` and `+3` included.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Numbers separated by any amount of spaces: `7  15   30`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    preceded(space0, separated_list1(space1, number))(input)
+}
+
+/// The empty line between two sections of the input.
+pub fn blank_line(input: &str) -> IResult<&str, (&str, &str)> {
+    pair(line_ending, line_ending)(input)
+}
+
+/// Blank-line separated sections, each one parsed by `section`.
+pub fn sections<'a, O, F>(section: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(blank_line, section)
+}
+
+/// A `key: a b c` line, as found in adjacency lists and labelled rows.
+pub fn adjacency<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, Vec<V>)>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, pair(char(':'), space0), separated_list1(space1, value))
+}
+
+/// A `key=value` pair, also accepting `key = value`.
+pub fn key_value<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, delimited(space0, char('='), space0), value)
+}
+
+/// Comma separated `key=value` pairs: `x=787,m=2655`.
+pub fn record<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<(K, V)>>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_list1(comma, key_value(key, value))
+}
+
+fn comma(input: &str) -> IResult<&str, (char, &str)> {
+    pair(char(','), space0)(input)
+}
+
+/// `x,y`, with optional spaces after the comma.
+pub fn ivec2(input: &str) -> IResult<&str, IVec2> {
+    let (input, (x, y)) = separated_pair(complete::i32, comma, complete::i32)(input)?;
+    Ok((input, IVec2::new(x, y)))
+}
+
+/// `x,y,z`, with optional spaces after the commas.
+pub fn ivec3(input: &str) -> IResult<&str, IVec3> {
+    let (input, (x, _, y, _, z)) =
+        tuple((complete::i32, comma, complete::i32, comma, complete::i32))(input)?;
+    Ok((input, IVec3::new(x, y, z)))
+}
+
+/// `x, y, z`, with optional spaces after the commas.
+pub fn i64vec3(input: &str) -> IResult<&str, I64Vec3> {
+    let (input, (x, _, y, _, z)) =
+        tuple((complete::i64, comma, complete::i64, comma, complete::i64))(input)?;
+    Ok((input, I64Vec3::new(x, y, z)))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete::{alpha1, alphanumeric1, not_line_ending},
+        sequence::terminated,
+    };
+
+    use super::*;
+
+    #[test_log::test]
+    fn signed_numbers() {
+        assert_eq!(Ok(("", -42)), number::<i32>("-42"));
+        assert_eq!(Ok(("", 42)), number::<i64>("+42"));
+        assert_eq!(Ok((" 7", 3)), number::<u64>("3 7"));
+        assert!(number::<u64>("-3").is_err());
+        assert!(number::<u8>("256").is_err());
+    }
+
+    #[test_log::test]
+    fn spaced_numbers() {
+        assert_eq!(Ok(("", vec![7, 15, 30])), numbers::<u64>("  7  15   30"));
+        assert_eq!(Ok(("\n1", vec![-1, 2])), numbers::<i32>("-1 2\n1"));
+    }
+
+    #[test_log::test]
+    fn blank_line_sections() {
+        let input = "seeds: 79 14\n\nsoil:\n50 98 2\n52 50 48\n\nfertilizer:\n0 15 37\n";
+        let section = separated_pair(
+            not_line_ending,
+            line_ending,
+            separated_list1(line_ending, numbers::<u64>),
+        );
+
+        let (input, header) = terminated(not_line_ending, blank_line)(input).unwrap();
+        assert_eq!("seeds: 79 14", header);
+        assert_eq!(
+            Ok((
+                "\n",
+                vec![
+                    ("soil:", vec![vec![50, 98, 2], vec![52, 50, 48]]),
+                    ("fertilizer:", vec![vec![0, 15, 37]]),
+                ]
+            )),
+            sections(section)(input)
+        );
+    }
+
+    #[test_log::test]
+    fn adjacency_lines() {
+        assert_eq!(
+            Ok(("", ("jqt", vec!["rhn", "xhk", "nvd"]))),
+            adjacency(alpha1, alpha1)("jqt: rhn xhk nvd")
+        );
+        assert_eq!(
+            Ok(("", ("Time", vec![7, 15, 30]))),
+            adjacency(alpha1, number::<u64>)("Time:      7  15   30")
+        );
+        assert_eq!(
+            Ok(("", (3267, vec![81, 40, 27]))),
+            adjacency(number::<u64>, number::<u64>)("3267: 81 40 27")
+        );
+    }
+
+    #[test_log::test]
+    fn key_value_pairs() {
+        assert_eq!(
+            Ok((" v=3,-3", ('p', IVec2::new(0, 4)))),
+            key_value(char('p'), ivec2)("p=0,4 v=3,-3")
+        );
+        assert_eq!(
+            Ok(("", ("AAA", "(BBB, CCC)"))),
+            key_value(alphanumeric1, not_line_ending)("AAA = (BBB, CCC)")
+        );
+    }
+
+    #[test_log::test]
+    fn records() {
+        assert_eq!(
+            Ok(("}", vec![("x", 787), ("m", 2655), ("a", 1222)])),
+            record(alpha1, number::<u64>)("x=787,m=2655,a=1222}")
+        );
+    }
+
+    #[test_log::test]
+    fn coordinates() {
+        assert_eq!(Ok(("", IVec2::new(-3, 4))), ivec2("-3,4"));
+        assert_eq!(Ok(("~1,2,3", IVec3::new(1, 0, 1))), ivec3("1,0,1~1,2,3"));
+        assert_eq!(
+            Ok((" @ -2,  1, -2", I64Vec3::new(19, 13, 30))),
+            i64vec3("19, 13, 30 @ -2,  1, -2")
+        );
+        assert!(ivec3("1,2").is_err());
+    }
+}
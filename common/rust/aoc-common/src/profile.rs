@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{self, Display},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{span, Subscriber};
+use tracing_subscriber::{
+    layer::Context, prelude::*, registry::LookupSpan, EnvFilter, Layer, Registry,
+};
+
+/// Installs `fmt` filtered by `RUST_LOG` like the bins normally do, along
+/// with the profiling layers. Spans are recorded regardless of `RUST_LOG`.
+///
+/// `AOC_TRACE_CHROME=<file>` writes a trace for `chrome://tracing` or
+/// Perfetto, `AOC_TRACE_FOLDED=<file>` writes folded stacks for
+/// `inferno-flamegraph`. A timing summary per span is printed to stderr
+/// when the returned [`Profile`] is dropped, so keep it alive until the end
+/// of `main`.
+pub fn init<L>(fmt: L) -> Profile
+where
+    L: Layer<Registry> + Send + Sync + 'static,
+{
+    let timings = Timings::default();
+    let mut profile = Profile {
+        timings: timings.clone(),
+        chrome: None,
+        folded: None,
+    };
+
+    let mut layers = vec![timings.boxed()];
+    if let Some(path) = env::var_os("AOC_TRACE_CHROME") {
+        let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new().file(path).build();
+        layers.push(layer.boxed());
+        profile.chrome = Some(guard);
+    }
+    if let Some(path) = env::var_os("AOC_TRACE_FOLDED") {
+        let (layer, guard) = tracing_flame::FlameLayer::with_file(path)
+            .expect("should create the folded stacks file");
+        layers.push(layer.with_threads_collapsed(true).boxed());
+        profile.folded = Some(guard);
+    }
+
+    tracing_subscriber::registry()
+        .with(fmt.with_filter(EnvFilter::from_default_env()))
+        .with(layers)
+        .init();
+
+    profile
+}
+
+/// Flushes the trace files and prints the span timings when dropped.
+pub struct Profile {
+    timings: Timings,
+    chrome: Option<tracing_chrome::FlushGuard>,
+    folded: Option<tracing_flame::FlushGuard<std::io::BufWriter<std::fs::File>>>,
+}
+
+impl Drop for Profile {
+    fn drop(&mut self) {
+        // both guards flush their file when dropped
+        self.folded.take();
+        self.chrome.take();
+        eprint!("{}", self.timings);
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Timing {
+    calls: u32,
+    busy: Duration,
+}
+
+/// Time spent inside each span, keyed by the span's module and name. A
+/// span's time includes the time spent in the spans it encloses.
+#[derive(Debug, Default, Clone)]
+struct Timings(Arc<Mutex<HashMap<(&'static str, &'static str), Timing>>>);
+
+struct Entered {
+    since: Option<Instant>,
+    busy: Duration,
+}
+
+impl<S> Layer<S> for Timings
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Entered {
+                since: None,
+                busy: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(entered) = span.extensions_mut().get_mut::<Entered>() {
+                entered.since = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(entered) = span.extensions_mut().get_mut::<Entered>() {
+                if let Some(since) = entered.since.take() {
+                    entered.busy += since.elapsed();
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(busy) = span.extensions().get::<Entered>().map(|e| e.busy) else {
+            return;
+        };
+
+        let metadata = span.metadata();
+        let mut timings = self.0.lock().unwrap();
+        let timing = timings
+            .entry((metadata.target(), metadata.name()))
+            .or_default();
+        timing.calls += 1;
+        timing.busy += busy;
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timings = self.0.lock().unwrap();
+        if timings.is_empty() {
+            return Ok(());
+        }
+
+        let mut rows = timings.iter().collect::<Vec<_>>();
+        rows.sort_by(|a, b| b.1.busy.cmp(&a.1.busy).then(a.0.cmp(b.0)));
+
+        let width = rows
+            .iter()
+            .map(|((target, name), _)| target.len() + name.len() + 2)
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        writeln!(
+            f,
+            "{:width$}  {:>8}  {:>12}  {:>12}",
+            "span", "calls", "total", "mean"
+        )?;
+        for ((target, name), timing) in rows {
+            writeln!(
+                f,
+                "{:width$}  {:>8}  {:>12}  {:>12}",
+                format!("{target}::{name}"),
+                timing.calls,
+                format!("{:.2?}", timing.busy),
+                format!("{:.2?}", timing.busy / timing.calls.max(1)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::info_span;
+
+    use super::*;
+
+    #[test_log::test]
+    fn span_timings() {
+        let timings = Timings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _process = info_span!("process").entered();
+            for _ in 0..3 {
+                let _settle = info_span!("settle_bricks").entered();
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        let timings_by_name = timings
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((_, name), timing)| (*name, *timing))
+            .collect::<HashMap<_, _>>();
+        let process = timings_by_name["process"];
+        let settle = timings_by_name["settle_bricks"];
+        assert_eq!(1, process.calls);
+        assert_eq!(3, settle.calls);
+        assert!(settle.busy >= Duration::from_millis(3));
+        assert!(process.busy >= settle.busy);
+
+        let summary = timings.to_string();
+        assert!(summary.starts_with("span"));
+        assert!(summary.contains("aoc_common::profile::tests::settle_bricks"));
+    }
+}
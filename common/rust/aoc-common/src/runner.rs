@@ -0,0 +1,824 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    env, fmt,
+    fmt::{Display, Write},
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Solves a part, with the answer or error as a string so every day's
+/// `process` fits the same signature.
+pub type Solve = fn(&str) -> Result<String, String>;
+
+/// The answer or error of a `process` call, for [`Solve`] functions.
+pub fn answer<T: Display, E: Display>(result: Result<T, E>) -> Result<String, String> {
+    result
+        .map(|answer| answer.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// A year's parts, and where to find their inputs.
+#[derive(Debug)]
+pub struct Registry {
+    pub year: u16,
+    /// The year's workspace, which holds a directory per day.
+    pub workspace: &'static str,
+    /// Each day's input, relative to its directory, unless the part says
+    /// otherwise.
+    pub input: &'static str,
+    pub parts: &'static [Part],
+    /// Whether the days were built with their `visualize` feature, so the
+    /// parts that can draw themselves do.
+    pub visualize: bool,
+}
+
+/// A day's part, which the registry can run on its input.
+#[derive(Debug, Clone, Copy)]
+pub struct Part {
+    /// The day's crate, like `day-05`, or `day-12-dfa` for another take on
+    /// a day.
+    pub day: &'static str,
+    pub part: u8,
+    pub solve: Solve,
+    pub input: Option<&'static str>,
+    /// The answer the puzzle accepted, if the day's tests have it.
+    pub answer: Option<&'static str>,
+    /// Whether the day draws itself in the terminal with its `visualize`
+    /// feature.
+    pub visualize: bool,
+}
+
+impl Part {
+    pub const fn new(day: &'static str, part: u8, solve: Solve) -> Part {
+        Part {
+            day,
+            part,
+            solve,
+            input: None,
+            answer: None,
+            visualize: false,
+        }
+    }
+
+    pub const fn input(mut self, input: &'static str) -> Part {
+        self.input = Some(input);
+        self
+    }
+
+    pub const fn answer(mut self, answer: &'static str) -> Part {
+        self.answer = Some(answer);
+        self
+    }
+
+    pub const fn visualize(mut self) -> Part {
+        self.visualize = true;
+        self
+    }
+
+    /// Whether `day` names this part's day, as in `5`, `05`, `day-05` or
+    /// `day-12-dfa`.
+    pub fn is_day(&self, day: &str) -> bool {
+        self.day == day
+            || day
+                .parse::<u8>()
+                .is_ok_and(|day| self.day == format!("day-{day:02}"))
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part {}", self.day, self.part)
+    }
+}
+
+impl Registry {
+    pub fn input_path(&self, part: &Part) -> PathBuf {
+        Path::new(self.workspace)
+            .join(part.day)
+            .join(part.input.unwrap_or(self.input))
+    }
+
+    /// The part's input, `None` when it's missing or blank, as inputs aren't
+    /// checked in.
+    pub fn read_input(&self, part: &Part) -> Option<String> {
+        fs::read_to_string(self.input_path(part))
+            .ok()
+            .filter(|input| !input.trim().is_empty())
+    }
+
+    /// The parts of `day`, or of every day, and only `part` if given.
+    pub fn select(&self, day: Option<&str>, part: Option<u8>) -> Vec<&'static Part> {
+        self.parts
+            .iter()
+            .filter(|p| day.is_none_or(|day| p.is_day(day)))
+            .filter(|p| part.is_none_or(|part| p.part == part))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// `verified` says whether the answer is the accepted one, if known.
+    Solved {
+        answer: String,
+        verified: Option<bool>,
+    },
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+    NoInput,
+    /// Never started, as every worker was stuck on a timed out part.
+    NotRun,
+}
+
+impl Status {
+    /// The answer, checked against `expected` if verified, or what went
+    /// wrong, in a few words.
+    pub fn describe(&self, expected: Option<&str>) -> String {
+        match self {
+            Status::Solved {
+                answer,
+                verified: Some(true),
+            } => format!("{answer} ✓"),
+            Status::Solved {
+                answer,
+                verified: Some(false),
+            } => format!("{answer} ✗ expected {}", expected.unwrap_or("?")),
+            Status::Solved {
+                answer,
+                verified: None,
+            } => answer.clone(),
+            Status::Failed(_) => "failed".to_string(),
+            Status::Panicked(_) => "panicked".to_string(),
+            Status::TimedOut => "timed out".to_string(),
+            Status::NoInput => "no input".to_string(),
+            Status::NotRun => "not run".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub part: &'static Part,
+    pub status: Status,
+    /// How long the part ran, `None` if it didn't finish.
+    pub duration: Option<Duration>,
+}
+
+thread_local! {
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps panics in parts off stderr, where they'd garble the summary or the
+/// terminal UI, and has [`solve`] report them with their location instead.
+pub fn capture_panics() {
+    panic::set_hook(Box::new(|info| {
+        PANIC.with(|message| *message.borrow_mut() = Some(info.to_string()));
+    }));
+}
+
+/// Solves `part`, turning a panic into [`Status::Panicked`].
+pub fn solve(part: &'static Part, input: &str) -> Outcome {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (part.solve)(input)));
+    let duration = start.elapsed();
+
+    let status = match result {
+        Ok(Ok(answer)) => Status::Solved {
+            verified: part.answer.map(|expected| expected == answer),
+            answer,
+        },
+        Ok(Err(e)) => Status::Failed(e),
+        Err(payload) => Status::Panicked(
+            PANIC
+                .with(|message| message.borrow_mut().take())
+                .unwrap_or_else(|| panic_message(payload.as_ref())),
+        ),
+    };
+    Outcome {
+        part,
+        status,
+        duration: Some(duration),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub threads: usize,
+    pub timeout: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+enum Event {
+    Started(usize, Instant),
+    Finished(usize, Outcome),
+}
+
+/// Runs `parts` on a pool of `options.threads` workers, in the order given.
+///
+/// A part still running after the timeout is reported as timed out and left
+/// to finish on its own, as there's no stopping it, so it keeps its worker
+/// busy. Once every worker is stuck like that, the parts yet to start are
+/// reported as not run instead of waiting on them.
+pub fn run_all(registry: &Registry, parts: &[&'static Part], options: &Options) -> Vec<Outcome> {
+    run_each(registry, parts, options, |_, _| {})
+}
+
+/// Like [`run_all`], calling `report` with each part's index and outcome as
+/// soon as it's known.
+pub fn run_each(
+    registry: &Registry,
+    parts: &[&'static Part],
+    options: &Options,
+    mut report: impl FnMut(usize, &Outcome),
+) -> Vec<Outcome> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.threads)
+        .thread_name(|i| format!("aoc-runner-{i}"))
+        .build()
+        .expect("the runner's thread pool should start");
+    let (sender, receiver) = mpsc::channel();
+
+    let mut outcomes = vec![None; parts.len()];
+    let mut finish = |outcomes: &mut Vec<Option<Outcome>>, i: usize, outcome: Outcome| {
+        report(i, &outcome);
+        outcomes[i] = Some(outcome);
+    };
+    for (i, &part) in parts.iter().enumerate() {
+        let Some(input) = registry.read_input(part) else {
+            let outcome = Outcome {
+                part,
+                status: Status::NoInput,
+                duration: None,
+            };
+            finish(&mut outcomes, i, outcome);
+            continue;
+        };
+        let sender = sender.clone();
+        pool.spawn(move || {
+            // the coordinator may have moved on, leaving no one to tell
+            let _ = sender.send(Event::Started(i, Instant::now()));
+            let _ = sender.send(Event::Finished(i, solve(part, &input)));
+        });
+    }
+    drop(sender);
+
+    let mut running: HashMap<usize, Instant> = HashMap::new();
+    let mut timed_out = HashSet::new();
+    while outcomes.iter().any(Option::is_none) {
+        if timed_out.len() >= options.threads {
+            break;
+        }
+        let deadline = running.values().min().map(|&since| since + options.timeout);
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match event {
+            Ok(Event::Started(i, since)) => {
+                running.insert(i, since);
+            }
+            Ok(Event::Finished(i, outcome)) => {
+                running.remove(&i);
+                if !timed_out.remove(&i) {
+                    finish(&mut outcomes, i, outcome);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                running.retain(|&i, since| {
+                    if now.duration_since(*since) < options.timeout {
+                        return true;
+                    }
+                    let outcome = Outcome {
+                        part: parts[i],
+                        status: Status::TimedOut,
+                        duration: None,
+                    };
+                    finish(&mut outcomes, i, outcome);
+                    timed_out.insert(i);
+                    false
+                });
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    for (i, &part) in parts.iter().enumerate() {
+        if outcomes[i].is_none() {
+            let outcome = Outcome {
+                part,
+                status: Status::NotRun,
+                duration: None,
+            };
+            finish(&mut outcomes, i, outcome);
+        }
+    }
+    outcomes.into_iter().flatten().collect()
+}
+
+/// The outcomes of a run, displayed as a table or written as JSON.
+#[derive(Debug)]
+pub struct Summary<'a> {
+    pub year: u16,
+    pub outcomes: &'a [Outcome],
+    pub elapsed: Duration,
+}
+
+impl Summary<'_> {
+    fn count(&self, matches: impl Fn(&Status) -> bool) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches(&outcome.status))
+            .count()
+    }
+
+    fn solved(&self, verified: Option<bool>) -> usize {
+        self.count(|status| matches!(status, Status::Solved { verified: v, .. } if *v == verified))
+    }
+
+    /// Whether every part ran and got the accepted answer, where known.
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(|outcome| {
+            matches!(
+                outcome.status,
+                Status::Solved {
+                    verified: None | Some(true),
+                    ..
+                } | Status::NoInput
+            )
+        })
+    }
+
+    pub fn json(&self) -> String {
+        let mut json = format!("{{\n  \"year\": {},\n  \"parts\": [", self.year);
+        for (i, outcome) in self.outcomes.iter().enumerate() {
+            let (status, answer, verified, error) = match &outcome.status {
+                Status::Solved { answer, verified } => ("solved", Some(answer), *verified, None),
+                Status::Failed(e) => ("failed", None, None, Some(e)),
+                Status::Panicked(e) => ("panicked", None, None, Some(e)),
+                Status::TimedOut => ("timed out", None, None, None),
+                Status::NoInput => ("no input", None, None, None),
+                Status::NotRun => ("not run", None, None, None),
+            };
+            let separator = if i == 0 { "" } else { "," };
+            let _ = write!(
+                json,
+                "{separator}\n    {{\"day\": {}, \"part\": {}, \"status\": {}",
+                Json(outcome.part.day),
+                outcome.part.part,
+                Json(status)
+            );
+            if let Some(answer) = answer {
+                let _ = write!(json, ", \"answer\": {}", Json(answer));
+            }
+            if let Some(verified) = verified {
+                let _ = write!(json, ", \"verified\": {verified}");
+            }
+            if let Some(error) = error {
+                let _ = write!(json, ", \"error\": {}", Json(error));
+            }
+            if let Some(duration) = outcome.duration {
+                let _ = write!(json, ", \"seconds\": {}", duration.as_secs_f64());
+            }
+            json.push('}');
+        }
+        let _ = write!(
+            json,
+            "\n  ],\n  \"solved\": {},\n  \"verified\": {},\n  \"wrong\": {},\n  \"failed\": {},\n  \"panicked\": {},\n  \"timed_out\": {},\n  \"no_input\": {},\n  \"not_run\": {},\n  \"seconds\": {}\n}}",
+            self.count(|status| matches!(status, Status::Solved { .. })),
+            self.solved(Some(true)),
+            self.solved(Some(false)),
+            self.count(|status| matches!(status, Status::Failed(_))),
+            self.count(|status| matches!(status, Status::Panicked(_))),
+            self.count(|status| *status == Status::TimedOut),
+            self.count(|status| *status == Status::NoInput),
+            self.count(|status| *status == Status::NotRun),
+            self.elapsed.as_secs_f64()
+        );
+        json
+    }
+}
+
+impl Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outcome in self.outcomes {
+            let status = outcome.status.describe(outcome.part.answer);
+            let duration = outcome
+                .duration
+                .map(|duration| format!("{duration:.1?}"))
+                .unwrap_or_default();
+            writeln!(f, "{:<18} {status:<40} {duration:>10}", outcome.part)?;
+            if let Status::Failed(e) | Status::Panicked(e) = &outcome.status {
+                for line in e.lines() {
+                    writeln!(f, "    {line}")?;
+                }
+            }
+        }
+        write!(
+            f,
+            "{}: {} solved ({} verified, {} wrong), {} failed, {} panicked, {} timed out, {} without input",
+            self.year,
+            self.count(|status| matches!(status, Status::Solved { .. })),
+            self.solved(Some(true)),
+            self.solved(Some(false)),
+            self.count(|status| matches!(status, Status::Failed(_))),
+            self.count(|status| matches!(status, Status::Panicked(_))),
+            self.count(|status| *status == Status::TimedOut),
+            self.count(|status| *status == Status::NoInput),
+        )?;
+        let not_run = self.count(|status| *status == Status::NotRun);
+        if not_run > 0 {
+            write!(f, ", {not_run} not run")?;
+        }
+        write!(f, " in {:.1?}", self.elapsed)
+    }
+}
+
+/// A string as a JSON string literal.
+struct Json<'a>(&'a str);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// What to do, from the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `list`: every part, whether its input is there and its answer.
+    List,
+    /// `run --all` or `run <day> [<part>]`, with `--json`, `--timeout SECS`
+    /// and `--threads N`.
+    Run {
+        day: Option<String>,
+        part: Option<u8>,
+        json: bool,
+        options: Options,
+    },
+    /// `tui`: browse the parts, run them and watch their logs, with
+    /// `--timeout SECS` and `--threads N` for the runs.
+    Tui { options: Options },
+}
+
+impl Command {
+    /// Parses the arguments after the binary's name, checking `--year`, if
+    /// given, is `year`.
+    pub fn parse(year: u16, args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+        let mut args = args.into_iter();
+        let command = args.next();
+        let mut all = false;
+        let mut json = false;
+        let mut options = Options::default();
+        let mut positional = vec![];
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| {
+                args.next()
+                    .ok_or_else(|| format!("{flag} needs a value"))
+                    .and_then(|value| {
+                        value
+                            .parse::<u64>()
+                            .map_err(|e| format!("invalid value for {flag}: {e}"))
+                    })
+            };
+            match arg.as_str() {
+                "--all" => all = true,
+                "--json" => json = true,
+                "--timeout" => options.timeout = Duration::from_secs(value("--timeout")?),
+                "--threads" => options.threads = value("--threads")?.max(1) as usize,
+                "--year" => {
+                    let asked = value("--year")?;
+                    if asked != u64::from(year) {
+                        return Err(format!(
+                            "this runner is built from the {year} workspace, run {asked} from its own"
+                        ));
+                    }
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown flag {flag}")),
+                _ => positional.push(arg),
+            }
+        }
+
+        match command.as_deref() {
+            Some("list") if positional.is_empty() => Ok(Command::List),
+            Some("tui") if positional.is_empty() && !all && !json => Ok(Command::Tui { options }),
+            Some("run") => {
+                let (day, part) = match (all, positional.as_slice()) {
+                    (true, []) => (None, None),
+                    (false, [day]) => (Some(day.clone()), None),
+                    (false, [day, part]) => (
+                        Some(day.clone()),
+                        Some(
+                            part.parse()
+                                .map_err(|e| format!("invalid part {part}: {e}"))?,
+                        ),
+                    ),
+                    _ => return Err("run takes --all, or a day and maybe a part".to_string()),
+                };
+                Ok(Command::Run {
+                    day,
+                    part,
+                    json,
+                    options,
+                })
+            }
+            _ => Err(format!(
+                "usage: aoc list\n       aoc tui [--timeout SECS] [--threads N]\n       aoc run (--all | <day> [<part>]) [--year {year}] [--json] [--timeout SECS] [--threads N]"
+            )),
+        }
+    }
+}
+
+/// Runs the command line against `registry`, for an `aoc` binary's `main`.
+pub fn main(registry: &'static Registry) -> ExitCode {
+    let command = match Command::parse(registry.year, env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::List => {
+            for part in registry.parts {
+                let input = match registry.read_input(part) {
+                    Some(_) => "input",
+                    None => "no input",
+                };
+                println!("{:<18} {input:<9} {}", part, part.answer.unwrap_or("-"));
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Run {
+            day,
+            part,
+            json,
+            options,
+        } => {
+            let parts = registry.select(day.as_deref(), part);
+            if parts.is_empty() {
+                eprintln!("no {} parts match", registry.year);
+                return ExitCode::from(2);
+            }
+
+            capture_panics();
+            // parts drawing themselves side by side would garble the summary
+            #[cfg(feature = "visualize")]
+            crate::terminal::set_playing(false);
+            let start = Instant::now();
+            let outcomes = run_all(registry, &parts, &options);
+            let summary = Summary {
+                year: registry.year,
+                outcomes: &outcomes,
+                elapsed: start.elapsed(),
+            };
+            if json {
+                println!("{}", summary.json());
+            } else {
+                println!("{summary}");
+            }
+            // timed out parts are still running, and exiting is the only
+            // way to stop them
+            if summary.is_success() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui { options } => crate::tui::main(registry, options),
+        #[cfg(not(feature = "tui"))]
+        Command::Tui { .. } => {
+            eprintln!("this runner is built without the tui feature");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARTS: &[Part] = &[
+        Part::new("day-01", 1, |input| answer::<_, String>(Ok(input.len()))).answer("0"),
+        Part::new("day-01", 2, |_| Err("no numbers".to_string())),
+        Part::new("day-02", 1, |_| panic!("no numbers either")),
+        Part::new("day-02", 2, |_| {
+            thread::sleep(Duration::from_secs(5));
+            Ok("late".to_string())
+        }),
+        Part::new("day-03", 1, |_| Ok("3".to_string())).answer("3"),
+        Part::new("day-03", 2, |_| Ok("3".to_string())).input("missing.txt"),
+    ];
+
+    /// A workspace in the temp dir with an input for every day, though
+    /// day-03's part 2 looks for another.
+    fn registry() -> Registry {
+        let workspace = env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        for day in ["day-01", "day-02", "day-03"] {
+            fs::create_dir_all(workspace.join(day)).expect("should create the day");
+            fs::write(workspace.join(day).join("input.txt"), "1 2 3\n")
+                .expect("should write the input");
+        }
+        Registry {
+            year: 2023,
+            workspace: workspace.to_string_lossy().into_owned().leak(),
+            input: "input.txt",
+            parts: PARTS,
+            visualize: false,
+        }
+    }
+
+    fn options(threads: usize) -> Options {
+        Options {
+            threads,
+            timeout: Duration::from_millis(200),
+        }
+    }
+
+    fn statuses(outcomes: &[Outcome]) -> Vec<Status> {
+        outcomes
+            .iter()
+            .map(|outcome| outcome.status.clone())
+            .collect()
+    }
+
+    #[test_log::test]
+    fn runs_every_part() {
+        let registry = registry();
+        let parts = PARTS.iter().collect::<Vec<_>>();
+        let outcomes = run_all(&registry, &parts, &options(4));
+
+        let statuses = statuses(&outcomes);
+        assert!(matches!(
+            &statuses[0],
+            Status::Solved {
+                verified: Some(false),
+                ..
+            }
+        ));
+        assert_eq!(Status::Failed("no numbers".to_string()), statuses[1]);
+        assert!(
+            matches!(&statuses[2], Status::Panicked(message) if message.contains("no numbers either"))
+        );
+        assert_eq!(Status::TimedOut, statuses[3]);
+        assert_eq!(
+            Status::Solved {
+                answer: "3".to_string(),
+                verified: Some(true)
+            },
+            statuses[4]
+        );
+        assert_eq!(Status::NoInput, statuses[5]);
+        assert!(outcomes[3].duration.is_none());
+    }
+
+    #[test_log::test]
+    fn stops_once_every_worker_is_stuck() {
+        let parts = [&PARTS[3], &PARTS[4]];
+        let outcomes = run_all(&registry(), &parts, &options(1));
+        assert_eq!(vec![Status::TimedOut, Status::NotRun], statuses(&outcomes));
+    }
+
+    #[test_log::test]
+    fn reports_each_outcome_as_it_comes() {
+        let parts = [&PARTS[4], &PARTS[5], &PARTS[3]];
+        let mut reported = vec![];
+        run_each(&registry(), &parts, &options(1), |i, outcome| {
+            reported.push((i, outcome.status.clone()))
+        });
+        assert_eq!(
+            vec![
+                (1, Status::NoInput),
+                (
+                    0,
+                    Status::Solved {
+                        answer: "3".to_string(),
+                        verified: Some(true)
+                    }
+                ),
+                (2, Status::TimedOut),
+            ],
+            reported
+        );
+    }
+
+    #[test_log::test]
+    fn selects_days() {
+        let registry = registry();
+        assert_eq!(6, registry.select(None, None).len());
+        assert_eq!(2, registry.select(Some("2"), None).len());
+        assert_eq!(1, registry.select(Some("day-03"), Some(2)).len());
+        assert!(Part::new("day-12-dfa", 1, |_| Ok(String::new())).is_day("day-12-dfa"));
+        assert!(!Part::new("day-12-dfa", 1, |_| Ok(String::new())).is_day("12"));
+    }
+
+    #[test_log::test]
+    fn summarises() {
+        let outcomes = vec![
+            Outcome {
+                part: &PARTS[4],
+                status: Status::Solved {
+                    answer: "3".to_string(),
+                    verified: Some(true),
+                },
+                duration: Some(Duration::from_millis(1500)),
+            },
+            Outcome {
+                part: &PARTS[1],
+                status: Status::Failed("line \"1\"\nis empty".to_string()),
+                duration: Some(Duration::ZERO),
+            },
+        ];
+        let summary = Summary {
+            year: 2023,
+            outcomes: &outcomes,
+            elapsed: Duration::from_secs(2),
+        };
+        assert!(!summary.is_success());
+
+        let human = summary.to_string();
+        assert!(human.contains("day-03 part 1"));
+        assert!(human.contains("    is empty"));
+        assert!(human.ends_with(
+            "2023: 1 solved (1 verified, 0 wrong), 1 failed, 0 panicked, 0 timed out, 0 without input in 2.0s"
+        ));
+
+        let json = summary.json();
+        assert!(json.contains(
+            r#"{"day": "day-03", "part": 1, "status": "solved", "answer": "3", "verified": true, "seconds": 1.5}"#
+        ));
+        assert!(json.contains(r#""error": "line \"1\"\nis empty""#));
+        assert!(json.contains(r#""failed": 1,"#));
+    }
+
+    #[test_log::test]
+    fn parses_commands() {
+        let parse = |args: &[&str]| Command::parse(2023, args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(Ok(Command::List), parse(&["list"]));
+        let Ok(Command::Run {
+            day: None,
+            part: None,
+            json: true,
+            options,
+        }) = parse(&["run", "--all", "--json", "--year", "2023", "--timeout", "5"])
+        else {
+            panic!("should run everything");
+        };
+        assert_eq!(Duration::from_secs(5), options.timeout);
+        assert!(matches!(
+            parse(&["run", "day-05", "2", "--threads", "2"]),
+            Ok(Command::Run { day: Some(day), part: Some(2), options: Options { threads: 2, .. }, .. }) if day == "day-05"
+        ));
+        assert!(matches!(
+            parse(&["tui", "--timeout", "300"]),
+            Ok(Command::Tui { options }) if options.timeout == Duration::from_secs(300)
+        ));
+        assert!(parse(&["tui", "day-05"]).is_err());
+        assert!(parse(&["run", "--all", "--year", "2024"]).is_err());
+        assert!(parse(&["run", "--all", "5"]).is_err());
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "--all", "--jobs", "2"]).is_err());
+        assert!(parse(&[]).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Stdout, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, MoveToNextLine, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{self, Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use glam::IVec2;
+
+use crate::frame::{Color, Frame};
+
+const DEFAULT_FPS: u32 = 30;
+const MAX_FPS: u32 = 960;
+
+static PLAYING: AtomicBool = AtomicBool::new(true);
+
+/// Whether players created from now on draw, for runners that solve parts
+/// side by side or keep the terminal for themselves.
+pub fn set_playing(playing: bool) {
+    PLAYING.store(playing, Ordering::Relaxed);
+}
+
+/// Plays frames in the terminal as they are produced.
+///
+/// Space pauses, `n` or → advances a single frame while paused, `+` and `-`
+/// change the speed and `q` stops drawing and lets the simulation finish on
+/// its own. Nothing is drawn when stdout isn't a terminal, or after
+/// [`set_playing`] turned players off.
+pub struct Player {
+    stdout: Stdout,
+    fps: u32,
+    paused: bool,
+    enabled: bool,
+    started: bool,
+    status_row: u16,
+}
+
+impl Player {
+    pub fn new(fps: u32) -> Player {
+        let stdout = io::stdout();
+        Player {
+            enabled: stdout.is_terminal() && PLAYING.load(Ordering::Relaxed),
+            stdout,
+            fps: fps.clamp(1, MAX_FPS),
+            paused: false,
+            started: false,
+            status_row: 0,
+        }
+    }
+
+    /// Reads the frame rate from `AOC_FPS`, starting paused if `AOC_PAUSED`
+    /// is set.
+    pub fn from_env() -> Player {
+        let fps = env::var("AOC_FPS")
+            .ok()
+            .and_then(|fps| fps.parse().ok())
+            .unwrap_or(DEFAULT_FPS);
+
+        let mut player = Player::new(fps);
+        player.paused = env::var_os("AOC_PAUSED").is_some();
+        player
+    }
+
+    pub fn show(&mut self, frame: &impl Frame) {
+        if !self.enabled {
+            return;
+        }
+
+        let result = self
+            .start()
+            .and_then(|_| self.draw(frame))
+            .and_then(|_| self.wait());
+        if result.is_err() {
+            self.stop();
+        }
+    }
+
+    fn start(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            terminal::enable_raw_mode()?;
+            execute!(self.stdout, EnterAlternateScreen, Hide)?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.enabled = false;
+        if self.started {
+            self.started = false;
+            let _ = execute!(self.stdout, ResetColor, Show, LeaveAlternateScreen);
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    fn draw(&mut self, frame: &impl Frame) -> io::Result<()> {
+        queue!(self.stdout, MoveTo(0, 0))?;
+
+        // leave room for the caption and the status line, anything that
+        // doesn't fit in the terminal is cut off
+        let size = match terminal::size()? {
+            (0, _) | (_, 0) => frame.size(),
+            (columns, rows) => frame
+                .size()
+                .min(IVec2::new(columns as i32, rows.saturating_sub(2) as i32)),
+        };
+
+        let mut color = None;
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let cell = frame.cell(IVec2::new(x, y));
+                if color != Some(cell.color) {
+                    color = Some(cell.color);
+                    queue!(self.stdout, SetForegroundColor(cell.color.into()))?;
+                }
+                queue!(self.stdout, Print(cell.glyph))?;
+            }
+            queue!(
+                self.stdout,
+                Clear(ClearType::UntilNewLine),
+                MoveToNextLine(1)
+            )?;
+        }
+
+        queue!(
+            self.stdout,
+            ResetColor,
+            Print(frame.caption()),
+            Clear(ClearType::UntilNewLine),
+            MoveToNextLine(1),
+        )?;
+        self.status_row = size.y as u16 + 1;
+        self.draw_status()
+    }
+
+    fn draw_status(&mut self) -> io::Result<()> {
+        let state = match self.paused {
+            true => "paused",
+            false => "playing",
+        };
+        queue!(
+            self.stdout,
+            MoveTo(0, self.status_row),
+            SetForegroundColor(style::Color::DarkGrey),
+            Print(format!(
+                "{state} at {} fps · space pause · n step · +/- speed · q quit",
+                self.fps
+            )),
+            ResetColor,
+            Clear(ClearType::FromCursorDown),
+        )?;
+        self.stdout.flush()
+    }
+
+    fn wait(&mut self) -> io::Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(1) / self.fps;
+
+        loop {
+            if !self.paused && !event::poll(deadline.saturating_duration_since(Instant::now()))? {
+                return Ok(());
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(()),
+                KeyCode::Char('+') => self.fps = (self.fps * 2).min(MAX_FPS),
+                KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.stop();
+                    return Ok(());
+                }
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.stop();
+                    return Ok(());
+                }
+                _ => continue,
+            }
+            self.draw_status()?;
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl From<Color> for style::Color {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Default => style::Color::Reset,
+            Color::DarkGrey => style::Color::DarkGrey,
+            Color::Red => style::Color::Red,
+            Color::Green => style::Color::Green,
+            Color::Yellow => style::Color::Yellow,
+            Color::Blue => style::Color::Blue,
+            Color::Magenta => style::Color::Magenta,
+            Color::Cyan => style::Color::Cyan,
+            Color::White => style::Color::White,
+        }
+    }
+}
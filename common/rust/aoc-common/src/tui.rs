@@ -0,0 +1,728 @@
+use std::{
+    collections::VecDeque,
+    env, fs,
+    io::{self, Stdout, Write},
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, MoveToNextLine, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tracing_subscriber::{fmt::MakeWriter, prelude::*, reload, EnvFilter, Registry as Layers};
+
+use crate::runner::{self, Options, Outcome, Part, Registry, Status};
+
+/// `RUST_LOG` levels `+` and `-` step through.
+const LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+/// How many lines of logs the pane keeps.
+const LOG_LINES: usize = 2000;
+/// How often the screen is redrawn while nothing happens, to show how long
+/// parts have been running.
+const TICK: Duration = Duration::from_millis(100);
+
+/// Browses `registry`'s parts in the terminal: runs them, with `options`,
+/// shows their `tracing` logs at a `RUST_LOG` level that can change while
+/// they run, and lets the days that can draw themselves do so.
+///
+/// The last run of each part is kept in the workspace's `target` directory,
+/// so its answer and runtime are there next time.
+pub fn main(registry: &'static Registry, options: Options) -> ExitCode {
+    match App::new(registry, options).and_then(|app| app.run()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The last lines `tracing` wrote, for the log pane.
+#[derive(Debug, Clone, Default)]
+struct Logs(Arc<Mutex<VecDeque<String>>>);
+
+impl Logs {
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<String>> {
+        // a part panicking while logging leaves the lines as they were
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, text: &str) {
+        let mut lines = self.lock();
+        lines.extend(text.lines().map(String::from));
+        let excess = lines.len().saturating_sub(LOG_LINES);
+        lines.drain(..excess);
+    }
+
+    fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Up to `count` lines, ending `scroll` lines before the last one.
+    fn tail(&self, count: usize, scroll: usize) -> Vec<String> {
+        let lines = self.lock();
+        let end = lines.len().saturating_sub(scroll);
+        let start = end.saturating_sub(count);
+        lines.range(start..end).cloned().collect()
+    }
+
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+}
+
+/// Collects what's written and adds it to the logs when dropped, as `fmt`
+/// makes a writer per event.
+struct LogWriter {
+    logs: Logs,
+    buf: Vec<u8>,
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        self.logs.push(&String::from_utf8_lossy(&self.buf));
+    }
+}
+
+impl<'a> MakeWriter<'a> for Logs {
+    type Writer = LogWriter;
+
+    fn make_writer(&'a self) -> LogWriter {
+        LogWriter {
+            logs: self.clone(),
+            buf: vec![],
+        }
+    }
+}
+
+/// The `RUST_LOG` directives, which can be swapped while parts are logging.
+struct Filter {
+    directives: String,
+    handle: reload::Handle<EnvFilter, Layers>,
+}
+
+impl Filter {
+    /// Sends `fmt` events, filtered by `RUST_LOG` or `info` without it, to
+    /// `logs`.
+    fn install(logs: Logs) -> Filter {
+        let directives = env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
+        let (filter, handle) = reload::Layer::new(
+            EnvFilter::try_new(&directives).unwrap_or_else(|_| EnvFilter::new("info")),
+        );
+        let fmt = tracing_subscriber::fmt::layer()
+            .with_writer(logs)
+            .with_ansi(false)
+            .without_time();
+        // a subscriber set before is left in charge, and the pane stays empty
+        let _ = tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt)
+            .try_init();
+
+        Filter { directives, handle }
+    }
+
+    fn step(&mut self, by: isize) {
+        self.directives = step(&self.directives, by).to_string();
+        let _ = self.handle.reload(EnvFilter::new(&self.directives));
+    }
+}
+
+/// The level `by` steps more verbose than `directives`, counting from
+/// `info` for directives that aren't just a level.
+fn step(directives: &str, by: isize) -> &'static str {
+    let level = LEVELS
+        .iter()
+        .position(|level| level.eq_ignore_ascii_case(directives.trim()))
+        .unwrap_or(3);
+    LEVELS[level.saturating_add_signed(by).min(LEVELS.len() - 1)]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Run {
+    Running(Instant),
+    Done(Status, Option<Duration>),
+}
+
+/// Writes the finished runs, a line per part with its day, part, status,
+/// seconds and answer or error.
+fn save(path: &Path, parts: &[Part], runs: &[Option<Run>]) -> io::Result<()> {
+    let mut history = String::new();
+    for (part, run) in parts.iter().zip(runs) {
+        let Some(Run::Done(status, duration)) = run else {
+            continue;
+        };
+        let (status, detail) = match status {
+            Status::Solved { answer, .. } => ("solved", answer.as_str()),
+            Status::Failed(e) => ("failed", e.as_str()),
+            Status::Panicked(e) => ("panicked", e.as_str()),
+            Status::TimedOut => ("timed out", ""),
+            Status::NoInput | Status::NotRun => continue,
+        };
+        let seconds = duration.map_or(String::new(), |duration| duration.as_secs_f64().to_string());
+        let detail = detail.replace(['\t', '\n', '\r'], " ");
+        history.push_str(&format!(
+            "{}\t{}\t{status}\t{seconds}\t{detail}\n",
+            part.day, part.part
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, history)
+}
+
+/// Reads the runs [`save`] wrote, checking answers against `parts` as they
+/// are now.
+fn load(path: &Path, parts: &[Part]) -> Vec<Option<Run>> {
+    let mut runs = vec![None; parts.len()];
+    let history = fs::read_to_string(path).unwrap_or_default();
+    for line in history.lines() {
+        let [day, part, status, seconds, detail] = line.splitn(5, '\t').collect::<Vec<_>>()[..]
+        else {
+            continue;
+        };
+        let Some(i) = parts
+            .iter()
+            .position(|p| p.day == day && part.parse() == Ok(p.part))
+        else {
+            continue;
+        };
+        let status = match status {
+            "solved" => Status::Solved {
+                answer: detail.to_string(),
+                verified: parts[i].answer.map(|answer| answer == detail),
+            },
+            "failed" => Status::Failed(detail.to_string()),
+            "panicked" => Status::Panicked(detail.to_string()),
+            "timed out" => Status::TimedOut,
+            _ => continue,
+        };
+        let duration = seconds.parse().ok().map(Duration::from_secs_f64);
+        runs[i] = Some(Run::Done(status, duration));
+    }
+    runs
+}
+
+/// The terminal, in raw mode on the alternate screen until dropped, unless
+/// suspended for something else to use it.
+struct Screen {
+    stdout: Stdout,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        let mut screen = Screen {
+            stdout: io::stdout(),
+        };
+        screen.resume()?;
+        Ok(screen)
+    }
+
+    fn resume(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(self.stdout, EnterAlternateScreen, Hide)
+    }
+
+    fn suspend(&mut self) {
+        let _ = execute!(self.stdout, ResetColor, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        self.suspend();
+    }
+}
+
+struct App {
+    registry: &'static Registry,
+    options: Options,
+    runs: Vec<Option<Run>>,
+    inputs: Vec<bool>,
+    history: PathBuf,
+    /// The other years' workspaces, which have their own `aoc`.
+    others: Vec<(u16, PathBuf)>,
+    logs: Logs,
+    filter: Filter,
+    selected: usize,
+    top: usize,
+    scroll: usize,
+    message: Option<String>,
+    sender: Sender<(usize, Outcome)>,
+    receiver: Receiver<(usize, Outcome)>,
+}
+
+impl App {
+    fn new(registry: &'static Registry, options: Options) -> io::Result<App> {
+        if registry.parts.is_empty() {
+            return Err(io::Error::other(format!(
+                "no {} parts are registered",
+                registry.year
+            )));
+        }
+
+        let history = Path::new(registry.workspace)
+            .join("target")
+            .join("aoc-tui.tsv");
+        let logs = Logs::default();
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            registry,
+            options,
+            runs: load(&history, registry.parts),
+            inputs: vec![],
+            history,
+            others: other_years(registry),
+            filter: Filter::install(logs.clone()),
+            logs,
+            selected: 0,
+            top: 0,
+            scroll: 0,
+            message: None,
+            sender,
+            receiver,
+        };
+        app.check_inputs();
+        Ok(app)
+    }
+
+    fn run(mut self) -> io::Result<()> {
+        runner::capture_panics();
+        // only parts opened with `v` may draw, the rest would garble the UI
+        crate::terminal::set_playing(false);
+        let mut screen = Screen::enter()?;
+
+        loop {
+            while let Ok((i, outcome)) = self.receiver.try_recv() {
+                self.finish(i, outcome);
+            }
+            self.draw(&mut screen.stdout)?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            self.message = None;
+            let parts = self.registry.parts.len();
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(parts - 1)
+                }
+                KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+                KeyCode::End | KeyCode::Char('G') => self.selected = parts - 1,
+                KeyCode::Enter | KeyCode::Char('r') => self.start(vec![self.selected]),
+                KeyCode::Char('a') => self.start((0..parts).collect()),
+                KeyCode::Char('v') => self.visualize(&mut screen)?,
+                KeyCode::Char('+') => self.filter.step(1),
+                KeyCode::Char('-') => self.filter.step(-1),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char('c') => {
+                    self.logs.clear();
+                    self.scroll = 0;
+                }
+                KeyCode::PageUp => self.scroll = (self.scroll + 10).min(self.logs.len()),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::Char('y') => self.switch_year(&mut screen)?,
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                _ => {}
+            }
+        }
+
+        // parts still running stop when the process exits
+        Ok(())
+    }
+
+    fn check_inputs(&mut self) {
+        self.inputs = self
+            .registry
+            .parts
+            .iter()
+            .map(|part| self.registry.read_input(part).is_some())
+            .collect();
+    }
+
+    /// Runs the parts at `indices` that aren't running already, in the
+    /// background on the runner's pool.
+    fn start(&mut self, indices: Vec<usize>) {
+        let indices = indices
+            .into_iter()
+            .filter(|&i| !matches!(self.runs[i], Some(Run::Running(_))))
+            .collect::<Vec<_>>();
+        if indices.is_empty() {
+            self.message = Some("already running".to_string());
+            return;
+        }
+
+        let now = Instant::now();
+        for &i in &indices {
+            self.runs[i] = Some(Run::Running(now));
+        }
+        let registry = self.registry;
+        let options = self.options.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let parts = indices
+                .iter()
+                .map(|&i| &registry.parts[i])
+                .collect::<Vec<_>>();
+            runner::run_each(registry, &parts, &options, |i, outcome| {
+                let _ = sender.send((indices[i], outcome.clone()));
+            });
+        });
+    }
+
+    fn finish(&mut self, i: usize, outcome: Outcome) {
+        self.runs[i] = Some(Run::Done(outcome.status, outcome.duration));
+        self.check_inputs();
+        if let Err(e) = save(&self.history, self.registry.parts, &self.runs) {
+            self.message = Some(format!(
+                "couldn't keep the run in {}: {e}",
+                self.history.display()
+            ));
+        }
+    }
+
+    /// Hands the terminal to the selected part so it can draw itself while
+    /// it runs.
+    fn visualize(&mut self, screen: &mut Screen) -> io::Result<()> {
+        let i = self.selected;
+        let part = &self.registry.parts[i];
+        if !part.visualize {
+            self.message = Some(format!("{part} has no visualization"));
+            return Ok(());
+        }
+        if !self.registry.visualize {
+            self.message = Some(format!(
+                "rebuild with `--features visualize` to watch {part}"
+            ));
+            return Ok(());
+        }
+        if matches!(self.runs[i], Some(Run::Running(_))) {
+            self.message = Some("already running".to_string());
+            return Ok(());
+        }
+        let Some(input) = self.registry.read_input(part) else {
+            self.message = Some(format!("{part} has no input"));
+            return Ok(());
+        };
+
+        screen.suspend();
+        crate::terminal::set_playing(true);
+        let outcome = runner::solve(part, &input);
+        crate::terminal::set_playing(false);
+        screen.resume()?;
+        self.finish(i, outcome);
+        Ok(())
+    }
+
+    /// Runs the next year's `aoc tui` from its workspace until it quits, as
+    /// the years can't share a binary.
+    fn switch_year(&mut self, screen: &mut Screen) -> io::Result<()> {
+        let Some((year, workspace)) = self
+            .others
+            .iter()
+            .find(|(year, _)| *year > self.registry.year)
+            .or(self.others.first())
+        else {
+            self.message = Some("there are no other years".to_string());
+            return Ok(());
+        };
+
+        screen.suspend();
+        // let the other workspace pick its own toolchain
+        let status = process::Command::new("cargo")
+            .args(["run", "--release", "--package", "aoc"])
+            .args(["--features", "visualize", "--", "tui"])
+            .env_remove("RUSTUP_TOOLCHAIN")
+            .current_dir(workspace)
+            .status();
+        screen.resume()?;
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => self.message = Some(format!("{year} exited with {status}")),
+            Err(e) => self.message = Some(format!("couldn't run {year}: {e}")),
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, stdout: &mut Stdout) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let columns = columns as usize;
+        let rows = rows as usize;
+        // the title, the list's header, the log pane's title and the footer
+        // take a row each, the list and logs split the rest
+        let list_rows = (rows.saturating_sub(4) / 2)
+            .max(1)
+            .min(self.registry.parts.len());
+        let log_rows = rows.saturating_sub(list_rows + 4);
+
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + list_rows {
+            self.top = self.selected + 1 - list_rows;
+        }
+
+        queue!(
+            stdout,
+            MoveTo(0, 0),
+            SetAttribute(Attribute::Bold),
+            Print(fit(&format!("aoc {}", self.registry.year), columns)),
+            SetAttribute(Attribute::Reset),
+            Clear(ClearType::UntilNewLine),
+            MoveToNextLine(1),
+            SetForegroundColor(Color::DarkGrey),
+            Print(fit(
+                &format!(
+                    "{:<12} {:<4} {:<8} {:<44} {:>10}",
+                    "day", "part", "input", "last run", "runtime"
+                ),
+                columns
+            )),
+            ResetColor,
+            Clear(ClearType::UntilNewLine),
+            MoveToNextLine(1),
+        )?;
+
+        for i in self.top..self.top + list_rows {
+            let part = &self.registry.parts[i];
+            let (status, color, runtime) = match &self.runs[i] {
+                None => ("-".to_string(), Color::DarkGrey, None),
+                Some(Run::Running(since)) => {
+                    ("running".to_string(), Color::Cyan, Some(since.elapsed()))
+                }
+                Some(Run::Done(status, duration)) => {
+                    let color = match status {
+                        Status::Solved {
+                            verified: Some(true),
+                            ..
+                        } => Color::Green,
+                        Status::Solved { verified: None, .. } => Color::Reset,
+                        Status::TimedOut => Color::Yellow,
+                        Status::NoInput | Status::NotRun => Color::DarkGrey,
+                        _ => Color::Red,
+                    };
+                    let mut text = status.describe(part.answer);
+                    if let Status::Failed(e) | Status::Panicked(e) = status {
+                        text = format!("{text}: {}", e.lines().next().unwrap_or_default());
+                    }
+                    (text, color, *duration)
+                }
+            };
+            let input = match self.inputs[i] {
+                true => "✓",
+                false => "missing",
+            };
+            let runtime = runtime
+                .map(|runtime| format!("{runtime:.1?}"))
+                .unwrap_or_default();
+            let status = fit(&status, 44);
+
+            if i == self.selected {
+                queue!(stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(
+                stdout,
+                Print(fit(
+                    &format!("{:<12} {:<4} {input:<8} ", part.day, part.part),
+                    columns
+                )),
+                SetForegroundColor(color),
+                Print(fit(
+                    &format!("{status:<44} {runtime:>10}"),
+                    columns.saturating_sub(27)
+                )),
+                ResetColor,
+                SetAttribute(Attribute::Reset),
+                Clear(ClearType::UntilNewLine),
+                MoveToNextLine(1),
+            )?;
+        }
+
+        self.scroll = self.scroll.min(self.logs.len());
+        let title = match self.scroll {
+            0 => format!("── tracing · RUST_LOG={} ", self.filter.directives),
+            scroll => format!(
+                "── tracing · RUST_LOG={} · {scroll} lines up ",
+                self.filter.directives
+            ),
+        };
+        queue!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(fit(&format!("{title}{}", "─".repeat(columns)), columns)),
+            ResetColor,
+            MoveToNextLine(1),
+        )?;
+        let lines = self.logs.tail(log_rows, self.scroll);
+        for row in 0..log_rows {
+            let line = lines.get(row).map_or("", String::as_str);
+            queue!(
+                stdout,
+                Print(fit(line, columns)),
+                Clear(ClearType::UntilNewLine),
+                MoveToNextLine(1),
+            )?;
+        }
+
+        let footer = self.message.clone().unwrap_or_else(|| {
+            let mut keys =
+                "↑↓ select · enter run · a run all · +/- RUST_LOG · pgup/pgdn scroll · c clear"
+                    .to_string();
+            if self.registry.parts[self.selected].visualize {
+                keys.push_str(" · v visualize");
+            }
+            if !self.others.is_empty() {
+                keys.push_str(" · y other year");
+            }
+            keys + " · q quit"
+        });
+        queue!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(fit(&footer, columns)),
+            ResetColor,
+            Clear(ClearType::FromCursorDown),
+        )?;
+        stdout.flush()
+    }
+}
+
+/// The first `columns` characters of `text`, without control characters
+/// that would move the cursor.
+fn fit(text: &str, columns: usize) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(columns)
+        .collect()
+}
+
+/// The workspaces of the years next to `registry`'s, like `2024/rust` next to
+/// `2023/rust`, which have an `aoc` of their own.
+fn other_years(registry: &Registry) -> Vec<(u16, PathBuf)> {
+    let Some(root) = fs::canonicalize(registry.workspace)
+        .ok()
+        .and_then(|workspace| Some(workspace.parent()?.parent()?.to_path_buf()))
+    else {
+        return vec![];
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return vec![];
+    };
+
+    let mut years = entries
+        .flatten()
+        .filter_map(|entry| {
+            let year = entry.file_name().to_str()?.parse::<u16>().ok()?;
+            let workspace = entry.path().join("rust");
+            let has_runner = workspace.join("aoc").join("Cargo.toml").is_file();
+            (year != registry.year && has_runner).then_some((year, workspace))
+        })
+        .collect::<Vec<_>>();
+    years.sort();
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn keeps_the_last_lines() {
+        let logs = Logs::default();
+        for i in 0..LOG_LINES {
+            writeln!(logs.make_writer(), "line {i}").expect("should log");
+        }
+        logs.push("more\nand more\n");
+
+        assert_eq!(LOG_LINES, logs.len());
+        assert_eq!(vec!["more", "and more"], logs.tail(2, 0));
+        assert_eq!(vec!["line 1999"], logs.tail(1, 2));
+        assert_eq!(vec!["line 2", "line 3"], logs.tail(2, LOG_LINES - 2));
+        assert!(logs.tail(5, LOG_LINES).is_empty());
+    }
+
+    #[test_log::test]
+    fn steps_through_levels() {
+        assert_eq!("debug", step("info", 1));
+        assert_eq!("trace", step("TRACE", 1));
+        assert_eq!("off", step("error", -2));
+        assert_eq!("warn", step("day_16=debug", -1));
+    }
+
+    #[test_log::test]
+    fn keeps_runs_between_sessions() {
+        const PARTS: &[Part] = &[
+            Part::new("day-01", 1, |_| Ok(String::new())).answer("7"),
+            Part::new("day-01", 2, |_| Ok(String::new())).answer("8"),
+            Part::new("day-02", 1, |_| Ok(String::new())),
+            Part::new("day-02", 2, |_| Ok(String::new())),
+        ];
+        let path = env::temp_dir()
+            .join(format!("aoc-tui-{}", std::process::id()))
+            .join("aoc-tui.tsv");
+        let runs = vec![
+            Some(Run::Done(
+                Status::Solved {
+                    answer: "7".to_string(),
+                    verified: Some(true),
+                },
+                Some(Duration::from_millis(1500)),
+            )),
+            Some(Run::Done(
+                Status::Failed("line 1\tis\nempty".to_string()),
+                Some(Duration::ZERO),
+            )),
+            Some(Run::Done(Status::TimedOut, None)),
+            Some(Run::Running(Instant::now())),
+        ];
+
+        save(&path, PARTS, &runs).expect("should save the runs");
+        assert_eq!(
+            vec![
+                runs[0].clone(),
+                Some(Run::Done(
+                    Status::Failed("line 1 is empty".to_string()),
+                    Some(Duration::ZERO)
+                )),
+                runs[2].clone(),
+                None
+            ],
+            load(&path, PARTS)
+        );
+        assert_eq!(
+            vec![None; 2],
+            load(&path.with_extension("missing"), &PARTS[..2])
+        );
+    }
+}